@@ -23,7 +23,7 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
 
       let repo_icons = match RepoIcons::load(owner, repo).await {
         Ok(repo_icons) => repo_icons,
-        Err(err) => return Response::error(err.to_string(), 500),
+        Err(err) => return error_json(err.to_string(), 500),
       };
 
       let repo_icon = repo_icons.closest_match();
@@ -41,7 +41,7 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
 
       let mut response = match Fetch::Request(request).send().await {
         Ok(mut response) => response.cloned()?,
-        Err(err) => return Response::error(err.to_string(), 404),
+        Err(err) => return error_json(err.to_string(), 404),
       };
 
       response.headers_mut().set(
@@ -62,7 +62,7 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
 
       let repo_icons = match RepoIcons::load(owner, repo).await {
         Ok(repo_icons) => repo_icons,
-        Err(err) => return Response::error(err.to_string(), 404),
+        Err(err) => return error_json(err.to_string(), 404),
       };
 
       from_json_pretty(&repo_icons)
@@ -73,7 +73,7 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
 
       let images = match Readme::load(owner, repo).await {
         Ok(readme) => readme.images().await,
-        Err(err) => return Response::error(err.to_string(), 404),
+        Err(err) => return error_json(err.to_string(), 404),
       };
 
       from_json_pretty(&images)
@@ -82,6 +82,17 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
     .await
 }
 
+/// Errors are returned as `{ "code": .., "message": .. }` so clients can handle them
+fn error_json(message: String, code: u16) -> Result<Response> {
+  #[derive(Serialize)]
+  struct ResponseError {
+    code: u16,
+    message: String,
+  }
+
+  from_json_pretty(&ResponseError { code, message }).map(|res| res.with_status(code))
+}
+
 fn from_json_pretty<B: Serialize>(value: &B) -> Result<Response> {
   if let Ok(data) = serde_json::to_string_pretty(value) {
     let mut headers = Headers::new();
@@ -0,0 +1,73 @@
+#![feature(test)]
+
+extern crate test;
+
+use futures::executor::block_on;
+use repo_icons::{parse_simple_readme, Readme};
+use scraper::Html;
+use test::{black_box, Bencher};
+
+/// A large readme as github renders it: a centered logo, badges, and many
+/// sections of syntax highlighted code
+fn large_readme() -> String {
+  let mut html = String::from(
+    r#"<div id="readme" class="md" data-path="README.md"><article class="markdown-body entry-content container-lg" itemprop="text"><p align="center">
+  <a href="https://widget.dev" rel="nofollow"><img src="https://raw.githubusercontent.com/widget/widget/main/assets/logo.png" alt="Widget logo" width="200" style="max-width: 100%;"></a>
+</p>
+<p align="center"><a href="https://github.com/widget/widget/actions"><img src="https://github.com/widget/widget/actions/workflows/ci.yml/badge.svg" alt="CI" style="max-width: 100%;"></a> <a href="https://crates.io/crates/widget" rel="nofollow"><img src="https://camo.githubusercontent.com/0a1b/68747470733a2f2f696d672e736869656c64732e696f" alt="crates.io" data-canonical-src="https://img.shields.io/crates/v/widget" style="max-width: 100%;"></a></p>
+"#,
+  );
+
+  for section in 0..200 {
+    html.push_str(&format!(
+      r##"<div class="markdown-heading" dir="auto"><h2 tabindex="-1" class="heading-element" dir="auto">Section {0}</h2><a id="user-content-section-{0}" class="anchor" aria-label="Permalink: Section {0}" href="#section-{0}"><svg class="octicon octicon-link" viewBox="0 0 16 16" version="1.1" width="16" height="16" aria-hidden="true"><path d="m7.775 3.275 1.25-1.25a3.5 3.5 0 1 1 4.95 4.95l-2.5 2.5a3.5 3.5 0 0 1-4.95 0Z"></path></svg></a></div>
+<p dir="auto">How to use the <code>widget::Section{0}</code> api, with <a href="https://docs.rs/widget">its docs</a>.</p>
+<div class="highlight highlight-source-rust notranslate position-relative overflow-auto" dir="auto"><pre>"##,
+      section
+    ));
+    for line in 0..20 {
+      html.push_str(&format!(
+        r#"<span class="pl-k">let</span> <span class="pl-s1">value_{0}</span> <span class="pl-c1">=</span> <span class="pl-v">Widget</span><span class="pl-kos">::</span><span class="pl-en">new</span><span class="pl-kos">(</span><span class="pl-s"><span class="pl-pds">"</span>line {0}<span class="pl-pds">"</span></span><span class="pl-kos">)</span><span class="pl-kos">;</span>
+"#,
+        line
+      ));
+    }
+    html.push_str(
+      r#"</pre><div class="zeroclipboard-container"><clipboard-copy aria-label="Copy" class="ClipboardButton btn" value="let value = Widget::new();" tabindex="0" role="button"><svg aria-hidden="true" height="16" viewBox="0 0 16 16" version="1.1" width="16" class="octicon octicon-copy js-clipboard-copy-icon"><path d="M0 6.75C0 5.784.784 5 1.75 5h1.5Z"></path></svg></clipboard-copy></div></div>
+"#,
+    );
+  }
+
+  html.push_str("</article></div>");
+  html
+}
+
+#[bench]
+fn parse_document(b: &mut Bencher) {
+  let html = large_readme();
+  b.iter(|| Html::parse_document(black_box(&html)));
+}
+
+#[bench]
+fn parse_simple_document(b: &mut Bencher) {
+  let html = large_readme();
+  assert!(parse_simple_readme(&html).is_some());
+  b.iter(|| parse_simple_readme(black_box(&html)));
+}
+
+fn images(document: Html) -> usize {
+  let readme = Readme::from_document("widget", "widget", document, false, "main", None);
+  block_on(readme.images_offline()).len()
+}
+
+#[bench]
+fn images_of_parsed_document(b: &mut Bencher) {
+  let html = large_readme();
+  b.iter(|| images(Html::parse_document(black_box(&html))));
+}
+
+#[bench]
+fn images_of_simple_document(b: &mut Bencher) {
+  let html = large_readme();
+  b.iter(|| images(parse_simple_readme(black_box(&html)).unwrap()));
+}
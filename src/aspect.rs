@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Aspect {
+  Square,
+  Portrait,
+  Wide,
+  Ultrawide,
+}
+
+impl Aspect {
+  pub fn from_size(width: u32, height: u32) -> Option<Self> {
+    if width == 0 || height == 0 {
+      return None;
+    }
+
+    let ratio = width as f32 / height as f32;
+
+    Some(if ratio < 0.8 {
+      Aspect::Portrait
+    } else if ratio <= 1.25 {
+      Aspect::Square
+    } else if ratio < 3.0 {
+      Aspect::Wide
+    } else {
+      Aspect::Ultrawide
+    })
+  }
+}
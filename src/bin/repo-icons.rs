@@ -1,7 +1,7 @@
 use clap::Parser;
 use env_logger::Builder;
 use log::LevelFilter;
-use repo_icons::{set_token, RepoIcons};
+use repo_icons::{set_token, set_user_agent, OutputFormat, RepoIcons};
 use std::error::Error;
 
 #[derive(Parser)]
@@ -10,11 +10,17 @@ struct Opts {
   #[clap(long)]
   json: bool,
   #[clap(long)]
+  /// Print the icons as a url, json, table or csv
+  format: Option<OutputFormat>,
+  #[clap(long)]
   /// Print out errors that occurred for skipped items
   debug: bool,
   #[clap(long)]
   /// Use a github token to get icons for private repos
   token: Option<String>,
+  #[clap(long)]
+  /// Override the User-Agent sent on outbound requests
+  user_agent: Option<String>,
 }
 
 macro_rules! regex {
@@ -38,6 +44,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     set_token(token);
   }
 
+  if let Some(user_agent) = opts.user_agent {
+    set_user_agent(user_agent)?;
+  }
+
   let slug = regex!("([^/]+)/(.+)")
     .captures(&opts.slug)
     .unwrap()
@@ -47,8 +57,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
   let icons = RepoIcons::load(user, repo).await?;
 
-  if opts.json {
-    println!("{}", serde_json::to_string_pretty(&icons)?)
+  if let Some(format) = opts.format {
+    println!("{}", icons.format(format))
+  } else if opts.json {
+    println!("{}", icons.format(OutputFormat::Json))
   } else {
     for icon in icons {
       println!("{} {} {}", icon.url, icon.kind, icon.info);
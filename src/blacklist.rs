@@ -34,6 +34,26 @@ static BADGE_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
   .to_vec()
 });
 
+// Generic images that say nothing about the project
+static PLACEHOLDER_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+  regexes![
+    // social previews github generates for repos without a custom one
+    r"^opengraph.githubassets.com"
+  ]
+  .to_vec()
+});
+
+// Decorative images used to break up a readme
+static DECORATIVE_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+  regexes![
+    r"divider",
+    r"separator",
+    r"spacer",
+    r"(?<![a-z])line(?![a-z])"
+  ]
+  .to_vec()
+});
+
 static BLACKLISTED_HOMEPAGES: Lazy<Vec<Regex>> = Lazy::new(|| {
   regexes![
     r"^stackblitz.com/edit",
@@ -49,7 +69,23 @@ static BLACKLISTED_HOMEPAGES: Lazy<Vec<Regex>> = Lazy::new(|| {
   .to_vec()
 });
 
+/// Images uploaded to github (eg. pasted into an issue or readme), which are
+/// named by an id, often without an extension. Their paths say nothing about
+/// the image, so they're never matched against the url patterns, and their
+/// format & size always come from their content
+pub fn is_github_upload(url: &Url) -> bool {
+  match url.domain() {
+    Some("user-images.githubusercontent.com" | "private-user-images.githubusercontent.com") => true,
+    Some("github.com") => url.path().starts_with("/user-attachments/assets/"),
+    _ => false,
+  }
+}
+
 pub fn is_badge(url: &Url) -> bool {
+  if is_github_upload(url) {
+    return false;
+  }
+
   let domain = if let Some(domain) = url.domain() {
     domain
   } else {
@@ -62,6 +98,98 @@ pub fn is_badge(url: &Url) -> bool {
     .any(|url_regex| url_regex.is_match(&url).unwrap())
 }
 
+pub fn is_placeholder(url: &Url) -> bool {
+  let domain = if let Some(domain) = url.domain() {
+    domain
+  } else {
+    return false;
+  };
+  let url = format!("{}{}", domain, url.path());
+
+  PLACEHOLDER_PATTERNS
+    .iter()
+    .any(|url_regex| url_regex.is_match(&url).unwrap())
+}
+
+pub fn is_decorative(url: &Url) -> bool {
+  if is_github_upload(url) {
+    return false;
+  }
+
+  let file_name = url
+    .path_segments()
+    .and_then(|mut segments| segments.next_back())
+    .unwrap_or("")
+    .to_lowercase();
+
+  DECORATIVE_PATTERNS
+    .iter()
+    .any(|url_regex| url_regex.is_match(&file_name).unwrap())
+}
+
+/// Hosts serving many unrelated sites from their subdomains
+const SHARED_HOSTS: [&str; 16] = [
+  "github.io",
+  "gitlab.io",
+  "vercel.app",
+  "netlify.app",
+  "netlify.com",
+  "herokuapp.com",
+  "pages.dev",
+  "web.app",
+  "firebaseapp.com",
+  "surge.sh",
+  "readthedocs.io",
+  "gitbook.io",
+  "glitch.me",
+  "fly.dev",
+  "onrender.com",
+  "azurewebsites.net",
+];
+
+/// Check if the subdomains of a host are sites of their own, either a shared
+/// host or a public suffix like `co.uk`, so the host isn't their root site
+pub fn is_shared_host(host: &str) -> bool {
+  let host = host.to_lowercase();
+  if SHARED_HOSTS.contains(&host.as_str()) {
+    return true;
+  }
+
+  // a country's second level domains, eg. `co.uk` & `com.au`
+  matches!(
+    host.split('.').collect::<Vec<_>>().as_slice(),
+    [second_level, country] if country.len() == 2
+      && ["co", "com", "org", "net", "gov", "edu", "ac", "ne", "or"].contains(second_level)
+  )
+}
+
+// Hosts of github itself, trusted along with the homepage by an allowlist
+const GITHUB_HOSTS: [&str; 3] = ["github.com", "githubusercontent.com", "githubassets.com"];
+
+/// Check the url is served from one of the `allowed_hosts` (or a subdomain of one),
+/// github or the homepage. Data uris are inline, so they're always allowed
+pub fn is_allowed_host(allowed_hosts: &[String], url: &Url, homepage: Option<&Url>) -> bool {
+  if url.scheme() == "data" {
+    return true;
+  }
+
+  let host = match url.host_str() {
+    Some(host) => host.to_lowercase(),
+    None => return false,
+  };
+  let homepage_host = homepage.and_then(|homepage| homepage.host_str());
+
+  allowed_hosts
+    .iter()
+    .map(String::as_str)
+    .chain(GITHUB_HOSTS)
+    .chain(homepage_host)
+    .any(|allowed_host| {
+      let allowed_host = allowed_host.to_lowercase();
+      host == allowed_host || host.ends_with(&format!(".{}", allowed_host))
+    })
+}
+
 pub fn is_blacklisted_homepage(url: &Url) -> bool {
   let domain = if let Some(domain) = url.domain() {
     domain
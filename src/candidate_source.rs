@@ -0,0 +1,47 @@
+use crate::RepoIcon;
+use futures::future::LocalBoxFuture;
+use reqwest::Client;
+use url::Url;
+
+/// The repo a [`CandidateSource`] is discovering icons for
+#[derive(Debug, Clone)]
+pub struct RepoContext {
+  pub owner: String,
+  pub repo: String,
+  pub homepage: Option<Url>,
+  /// a client with the configured user agent, without the GitHub token
+  pub client: Client,
+}
+
+/// A custom source of icons, which are ranked & deduplicated
+/// alongside the built-in sources
+///
+/// ```no_run
+/// # use futures::future::LocalBoxFuture;
+/// # use reqwest::{header::HeaderMap, Url};
+/// # use repo_icons::{CandidateSource, IconInfo, IconKind, RepoContext, RepoIcon, RepoIconKind, RepoIconsBuilder};
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// struct Cdn;
+///
+/// impl CandidateSource for Cdn {
+///   fn discover<'a>(&'a self, ctx: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+///     Box::pin(async move {
+///       let url: Url = format!("https://cdn.example.com/{}.svg", ctx.repo).parse().unwrap();
+///       match IconInfo::load(url.clone(), HeaderMap::new(), None).await {
+///         Ok(info) => vec![RepoIcon::new(url, RepoIconKind::Site(IconKind::SiteLogo), info)],
+///         Err(_) => vec![],
+///       }
+///     })
+///   }
+/// }
+///
+/// let icons = RepoIconsBuilder::new()
+///   .add_source(Cdn)
+///   .load("facebook", "react")
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait CandidateSource: Send + Sync {
+  fn discover<'a>(&'a self, ctx: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>>;
+}
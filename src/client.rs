@@ -0,0 +1,234 @@
+use crate::{github_api::record_rate_limit, observer::observe_request, RepoIconsBuilder};
+use futures::{future::LocalBoxFuture, Future};
+use once_cell::sync::Lazy;
+use reqwest::{
+  header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT as USER_AGENT_HEADER},
+  Client, ClientBuilder, Request, RequestBuilder, Response, Url,
+};
+use std::{
+  error::Error,
+  sync::{Arc, RwLock},
+};
+
+static USER_AGENT: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+/// the user agent github requests are sent with when none is configured, as
+/// github's api rejects requests without one
+const GITHUB_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+static TOKEN_PROVIDER: Lazy<RwLock<Option<Arc<dyn TokenProvider>>>> =
+  Lazy::new(|| RwLock::new(None));
+
+/// Supplies the GitHub token, called for every request so
+/// expiring tokens (eg. GitHub App installation tokens) can be rotated
+///
+/// ```
+/// # use repo_icons::set_token_provider;
+/// # fn installation_token() -> String { String::new() }
+/// set_token_provider(|| Some(installation_token()));
+/// ```
+pub trait TokenProvider: Send + Sync {
+  fn token(&self) -> Option<String>;
+}
+
+impl<F: Fn() -> Option<String> + Send + Sync> TokenProvider for F {
+  fn token(&self) -> Option<String> {
+    self()
+  }
+}
+
+struct StaticToken(String);
+
+impl TokenProvider for StaticToken {
+  fn token(&self) -> Option<String> {
+    Some(self.0.clone())
+  }
+}
+
+pub fn set_token_provider<P: TokenProvider + 'static>(provider: P) {
+  *TOKEN_PROVIDER.write().unwrap() = Some(Arc::new(provider));
+}
+
+/// Use the same token for every request
+pub fn set_token<T: ToString>(token: T) {
+  set_token_provider(StaticToken(token.to_string()))
+}
+
+pub fn get_token() -> Option<String> {
+  let provider = TOKEN_PROVIDER.read().unwrap().clone();
+  provider.and_then(|provider| provider.token())
+}
+
+/// Override the User-Agent sent on all outbound requests.
+///
+/// The only exception is `site_icons`, which uses its own client to probe icon
+/// sizes & load manifests, so those requests keep its user agent. See
+/// [`crate::RepoIconsBuilder::user_agent`] to override it for a single load
+pub fn set_user_agent<T: ToString>(user_agent: T) -> Result<(), Box<dyn Error>> {
+  let user_agent = user_agent.to_string();
+  if user_agent.trim().is_empty() {
+    return Err("user agent must not be empty".into());
+  }
+  HeaderValue::from_str(&user_agent)?;
+
+  *USER_AGENT.write().unwrap() = Some(user_agent);
+  Ok(())
+}
+
+pub fn get_user_agent() -> Option<String> {
+  USER_AGENT.read().unwrap().clone()
+}
+
+/// Sends the requests made while loading the icons, see
+/// [`crate::RepoIconsBuilder::http_client`]. Requests are built with reqwest
+/// (with the token, user agent & headers), but can be sent with any client
+/// on any runtime. A [`Response`] can be built from an `http::Response`.
+///
+/// Requests made by `site_icons` bypass this: probing the sizes of readme,
+/// blob & avatar icons, and scraping the homepage (turn it off with
+/// [`crate::RepoIconsBuilder::website`]). They use its own reqwest client, so
+/// still need a tokio runtime
+///
+/// ```
+/// # use futures::future::LocalBoxFuture;
+/// # use reqwest::{Request, Response};
+/// # use repo_icons::HttpClient;
+/// # use std::error::Error;
+/// struct Mock;
+///
+/// impl HttpClient for Mock {
+///   fn execute(&self, request: Request) -> LocalBoxFuture<'_, Result<Response, Box<dyn Error>>> {
+///     Box::pin(async move {
+///       let res = http::Response::builder().status(404).body("")?;
+///       Ok(res.into())
+///     })
+///   }
+/// }
+/// ```
+pub trait HttpClient: Send + Sync {
+  fn execute(&self, request: Request) -> LocalBoxFuture<'_, Result<Response, Box<dyn Error>>>;
+}
+
+/// Send a request with the configured [`HttpClient`], or reqwest
+pub(crate) async fn execute(
+  options: &RepoIconsBuilder,
+  request: RequestBuilder,
+) -> Result<Response, Box<dyn Error>> {
+  let http_client = match &options.http_client {
+    Some(http_client) => http_client,
+    None => return Ok(request.send().await?),
+  };
+
+  // reqwest only adds a client's default headers when it sends the request,
+  // so they're added here for the custom client
+  let mut request = request.build()?;
+  let mut defaults = HeaderMap::new();
+  if let Some(user_agent) = user_agent(options)? {
+    defaults.insert(USER_AGENT_HEADER, user_agent);
+  }
+  if is_github_host(request.url()) {
+    for (name, value) in github_headers() {
+      if let Some(name) = name {
+        defaults.entry(name).or_insert(value);
+      }
+    }
+  }
+  for (name, value) in defaults {
+    if let Some(name) = name {
+      request.headers_mut().entry(name).or_insert(value);
+    }
+  }
+
+  http_client.execute(request).await
+}
+
+/// The hosts requested with [`github_client`], that the token is sent to
+fn is_github_host(url: &Url) -> bool {
+  matches!(
+    url.host_str(),
+    Some("api.github.com" | "raw.githubusercontent.com")
+  )
+}
+
+/// The token, and the accept & user agent github's api requires
+fn github_headers() -> HeaderMap {
+  let mut headers = HeaderMap::new();
+  if let Some(authorization) =
+    get_token().and_then(|token| HeaderValue::from_str(&format!("Bearer {}", token)).ok())
+  {
+    headers.insert(AUTHORIZATION, authorization);
+  }
+  headers.insert(
+    ACCEPT,
+    HeaderValue::from_static("application/vnd.github.v3+json"),
+  );
+  headers.insert(
+    USER_AGENT_HEADER,
+    HeaderValue::from_static(GITHUB_USER_AGENT),
+  );
+  headers
+}
+
+/// A client for the GitHub API, authenticated with the token
+pub(crate) fn github_client_builder(options: &RepoIconsBuilder) -> ClientBuilder {
+  configure(options, Client::builder().default_headers(github_headers()))
+}
+
+pub(crate) fn github_client(options: &RepoIconsBuilder) -> Client {
+  github_client_builder(options).build().unwrap()
+}
+
+/// A client for third-party hosts, which never sends the token
+pub(crate) fn client(options: &RepoIconsBuilder) -> Client {
+  configure(options, Client::builder()).build().unwrap()
+}
+
+/// The load's user agent, or the global one
+fn user_agent(options: &RepoIconsBuilder) -> Result<Option<HeaderValue>, Box<dyn Error>> {
+  match &options.user_agent {
+    Some(user_agent) => Ok(Some(user_agent.clone())),
+    None => match get_user_agent() {
+      Some(user_agent) => Ok(Some(HeaderValue::from_str(&user_agent)?)),
+      None => Ok(None),
+    },
+  }
+}
+
+/// Apply the user agent and proxy. Without a proxy, reqwest uses the
+/// `HTTP_PROXY` / `HTTPS_PROXY` / `NO_PROXY` env vars
+fn configure(options: &RepoIconsBuilder, builder: ClientBuilder) -> ClientBuilder {
+  let builder = match user_agent(options) {
+    Ok(Some(user_agent)) => builder.user_agent(user_agent),
+    _ => builder,
+  };
+
+  #[cfg(not(target_arch = "wasm32"))]
+  if let Some(proxy) = &options.proxy {
+    return builder.proxy(proxy.clone());
+  }
+
+  #[cfg(target_arch = "wasm32")]
+  let _ = options;
+
+  builder
+}
+
+/// Send a request within the load's request budget, reporting it to the observer
+/// and recording the rate limit it leaves
+pub(crate) async fn send(
+  options: &RepoIconsBuilder,
+  request: RequestBuilder,
+) -> Result<Response, Box<dyn Error>> {
+  let res = with_permit(options, observe_request(options, request)).await?;
+  record_rate_limit(&res);
+  Ok(res)
+}
+
+/// Run a future that makes requests outside of [`send`] (eg. in `site_icons`),
+/// counting it as a single request against the load's budget
+pub(crate) async fn with_permit<F: Future>(options: &RepoIconsBuilder, future: F) -> F::Output {
+  let _permit = match &options.request_budget {
+    Some(budget) => Some(budget.acquire().await),
+    None => None,
+  };
+
+  future.await
+}
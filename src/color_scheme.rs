@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorScheme {
+  Light,
+  Dark,
+  /// no scheme was declared, valid for both light & dark
+  #[default]
+  Any,
+}
+
+impl ColorScheme {
+  /// Parse the scheme out of a media query, eg. `(prefers-color-scheme: dark)`
+  pub fn from_media(media: &str) -> Self {
+    match regex!(r"prefers-color-scheme:\s*(dark|light)")
+      .captures(&media.to_lowercase())
+      .unwrap()
+    {
+      Some(res) if &res[1] == "dark" => ColorScheme::Dark,
+      Some(_) => ColorScheme::Light,
+      None => ColorScheme::Any,
+    }
+  }
+}
@@ -0,0 +1,157 @@
+use crate::{
+  primary_icon::LogoScore, Candidate, ColorScheme, IconDebug, PrimaryPreference, RepoIcon,
+  RepoIcons, SourceWarning, VectorSize,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use site_icons::IconInfo;
+use std::{collections::BTreeMap, convert::TryInto, error::Error};
+use url::Url;
+
+/// [`RepoIcons`] with the metadata the json skips, so a decoded load
+/// ranks & reports the same as the original
+#[derive(Serialize, Deserialize)]
+struct CompactRepoIcons {
+  icons: Vec<CompactIcon>,
+  truncated: bool,
+  private: bool,
+  candidates: Vec<Candidate>,
+  logo_score: Option<LogoScore>,
+  etag: Option<String>,
+  prefer_vector: bool,
+  primary_preference: PrimaryPreference,
+  warnings: Vec<SourceWarning>,
+  min_confidence: Option<f32>,
+}
+
+/// [`RepoIcon`] without the flattened & internally tagged fields, which only
+/// self-describing formats like json can deserialize
+#[derive(Serialize, Deserialize)]
+struct CompactIcon {
+  url: String,
+  /// sorted, so the same icons always encode to the same bytes
+  headers: BTreeMap<String, String>,
+  kind: String,
+  info: CompactInfo,
+  color_scheme: ColorScheme,
+  vector_size: Option<(u32, u32)>,
+  pseudo_vector: bool,
+  default_avatar: bool,
+  mask_color: Option<String>,
+  sourced_from_repo: bool,
+  label: Option<String>,
+  debug: Option<IconDebug>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CompactInfo {
+  Png(u32, u32),
+  Jpeg(u32, u32),
+  Ico(Vec<(u32, u32)>),
+  Svg,
+}
+
+impl From<&RepoIcon> for CompactIcon {
+  fn from(icon: &RepoIcon) -> Self {
+    CompactIcon {
+      url: icon.url.to_string(),
+      headers: icon.headers.clone().into_iter().collect(),
+      kind: icon.kind.to_string(),
+      info: match &icon.info {
+        IconInfo::PNG { size } => CompactInfo::Png(size.width, size.height),
+        IconInfo::JPEG { size } => CompactInfo::Jpeg(size.width, size.height),
+        IconInfo::ICO { sizes } => {
+          CompactInfo::Ico(sizes.iter().map(|size| (size.width, size.height)).collect())
+        }
+        IconInfo::SVG => CompactInfo::Svg,
+      },
+      color_scheme: icon.color_scheme,
+      vector_size: icon.vector_size.map(|size| (size.width, size.height)),
+      pseudo_vector: icon.pseudo_vector,
+      default_avatar: icon.default_avatar,
+      mask_color: icon.mask_color.clone(),
+      sourced_from_repo: icon.sourced_from_repo,
+      label: icon.label.clone(),
+      debug: icon.debug.clone(),
+    }
+  }
+}
+
+impl CompactIcon {
+  fn into_icon(self) -> Result<RepoIcon, Box<dyn Error>> {
+    // site_icons doesn't export its size type, so the info is built from json
+    let size = |(width, height)| format!("{}x{}", width, height);
+    let info = serde_json::from_value(match self.info {
+      CompactInfo::Png(width, height) => json!({ "type": "png", "size": size((width, height)) }),
+      CompactInfo::Jpeg(width, height) => json!({ "type": "jpeg", "size": size((width, height)) }),
+      CompactInfo::Ico(sizes) => json!({
+        "type": "ico",
+        "sizes": sizes.into_iter().map(size).collect::<Vec<_>>(),
+      }),
+      CompactInfo::Svg => json!({ "type": "svg" }),
+    })?;
+
+    let mut icon = RepoIcon::new_with_headers(
+      Url::parse(&self.url)?,
+      self.headers.into_iter().collect(),
+      self.kind.parse()?,
+      info,
+    );
+    icon.color_scheme = self.color_scheme;
+    icon.vector_size = self
+      .vector_size
+      .map(|(width, height)| VectorSize { width, height });
+    icon.pseudo_vector = self.pseudo_vector;
+    icon.default_avatar = self.default_avatar;
+    icon.mask_color = self.mask_color;
+    icon.sourced_from_repo = self.sourced_from_repo;
+    icon.label = self.label;
+    icon.debug = self.debug;
+
+    Ok(icon)
+  }
+}
+
+impl RepoIcons {
+  /// Encode the icons with `postcard`, which is much smaller than json for
+  /// caching many repos. Unlike the json, the load's metadata (eg. the etag,
+  /// warnings & ranking preferences) is kept too
+  pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+    let compact = CompactRepoIcons {
+      icons: self.iter().map(CompactIcon::from).collect(),
+      truncated: self.truncated,
+      private: self.private,
+      candidates: self.candidates.clone(),
+      logo_score: self.logo_score,
+      etag: self.etag.clone(),
+      prefer_vector: self.prefer_vector,
+      primary_preference: self.primary_preference,
+      warnings: self.warnings.clone(),
+      min_confidence: self.min_confidence,
+    };
+    Ok(postcard::to_allocvec(&compact)?)
+  }
+
+  /// Decode icons encoded with [`RepoIcons::to_bytes`]
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+    let compact = postcard::from_bytes::<CompactRepoIcons>(bytes)?;
+    let icons = compact
+      .icons
+      .into_iter()
+      .map(CompactIcon::into_icon)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RepoIcons {
+      icons: icons.try_into().map_err(|_| "no icons were encoded")?,
+      truncated: compact.truncated,
+      private: compact.private,
+      candidates: compact.candidates,
+      logo_score: compact.logo_score,
+      etag: compact.etag,
+      prefer_vector: compact.prefer_vector,
+      primary_preference: compact.primary_preference,
+      warnings: compact.warnings,
+      min_confidence: compact.min_confidence,
+    })
+  }
+}
@@ -0,0 +1,129 @@
+use crate::{IconFormat, RepoIcon, RepoIcons, RepoIconsBuilder};
+use futures::{stream, StreamExt};
+use std::{
+  collections::HashMap,
+  error::Error,
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// how many icons are downloaded at once
+const CONCURRENCY: usize = 4;
+
+/// An icon saved by [`RepoIcons::download_all`]
+#[derive(Debug, Clone)]
+pub struct DownloadedIcon {
+  pub icon: RepoIcon,
+  pub path: PathBuf,
+  pub format: IconFormat,
+  /// whether the file was already in the directory, so it wasn't downloaded
+  pub skipped: bool,
+}
+
+impl RepoIcons {
+  /// Save the icons to `dir` (created if missing), in their ranked order, eg. for
+  /// static sites & asset caches. Files are named `{fingerprint}.{extension}`
+  /// (see [`RepoIcon::fingerprint`]), with the extension detected from the
+  /// data, so the same icon is always saved to the same file. Icons already in
+  /// the directory aren't downloaded again, and icons that fail to download are
+  /// left out
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIcons;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIcons::load("facebook", "react").await?;
+  /// for downloaded in icons.download_all("./icons").await? {
+  ///   println!("{}", downloaded.path.display());
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn download_all<P: AsRef<Path>>(
+    &self,
+    dir: P,
+  ) -> Result<Vec<DownloadedIcon>, Box<dyn Error>> {
+    self.download_all_with(&RepoIconsBuilder::new(), dir).await
+  }
+
+  /// [`RepoIcons::download_all`] with the load's client, user agent & request budget
+  pub async fn download_all_with<P: AsRef<Path>>(
+    &self,
+    options: &RepoIconsBuilder,
+    dir: P,
+  ) -> Result<Vec<DownloadedIcon>, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let existing = existing_files(dir)?;
+
+    let downloads = stream::iter(self.into_iter().cloned())
+      .map(|icon| {
+        let existing = &existing;
+        async move {
+          let fingerprint = icon.fingerprint();
+          if let Some(format) = existing.get(&fingerprint) {
+            let path = dir.join(format!("{}.{}", fingerprint, format.extension()));
+            return Ok(Some(DownloadedIcon {
+              icon,
+              path,
+              format: *format,
+              skipped: true,
+            }));
+          }
+
+          let data = match icon.data_with(options).await {
+            Ok(data) => data,
+            Err(err) => {
+              warn!("failed to download {}: {}", icon.url, err);
+              return Ok(None);
+            }
+          };
+          let format = match IconFormat::detect(&data) {
+            Some(format) => format,
+            None => {
+              warn!("unrecognized icon format for {}", icon.url);
+              return Ok(None);
+            }
+          };
+
+          let path = dir.join(format!("{}.{}", fingerprint, format.extension()));
+          fs::write(&path, &data)?;
+
+          Ok::<_, Box<dyn Error>>(Some(DownloadedIcon {
+            icon,
+            path,
+            format,
+            skipped: false,
+          }))
+        }
+      })
+      .buffered(CONCURRENCY)
+      .collect::<Vec<_>>()
+      .await;
+
+    let mut downloaded = Vec::new();
+    for download in downloads {
+      downloaded.extend(download?);
+    }
+
+    Ok(downloaded)
+  }
+}
+
+/// The fingerprints of the icons already saved in the directory
+fn existing_files(dir: &Path) -> Result<HashMap<String, IconFormat>, Box<dyn Error>> {
+  let mut existing = HashMap::new();
+
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    let stem = path.file_stem().and_then(|stem| stem.to_str());
+    let extension = path.extension().and_then(|extension| extension.to_str());
+
+    if let (Some(stem), Some(extension)) = (stem, extension) {
+      if let Some(format) = IconFormat::from_extension(extension) {
+        existing.insert(stem.to_string(), format);
+      }
+    }
+  }
+
+  Ok(existing)
+}
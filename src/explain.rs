@@ -0,0 +1,105 @@
+use crate::{Phase, RepoIconKind, Signal};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Why a candidate was left out of the icons
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Exclusion {
+  /// a status / shields.io style badge
+  Badge,
+  /// a divider / separator / spacer
+  Decorative,
+  /// an image repeated throughout the readme
+  Repeated,
+  /// `hidden`, `display: none` or in a `<template>` / `<noscript>`
+  Hidden,
+  /// the src couldn't be resolved to a url
+  InvalidUrl,
+  /// neither linked to the project or in the repo, with
+  /// [`crate::RepoIconsBuilder::only_project_linked`]
+  NotProjectLinked,
+  /// didn't resolve, with [`crate::RepoIconsBuilder::verify_urls`]
+  Unreachable,
+  /// not in the primary heading or the centered first image
+  NotLogoCandidate,
+  /// a higher weighted readme image was chosen
+  Outranked,
+  /// not served from [`crate::RepoIconsBuilder::allowed_hosts`]
+  UntrustedHost,
+  /// processing the image panicked
+  Failed,
+}
+
+impl Display for Exclusion {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    let exclusion = serde_json::to_value(self).map_err(|_| fmt::Error)?;
+    write!(f, "{}", exclusion.as_str().unwrap_or_default())
+  }
+}
+
+/// An optional source that failed while loading, without failing the load,
+/// see [`crate::RepoIcons::warnings`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceWarning {
+  pub source: Phase,
+  pub message: String,
+}
+
+impl Display for SourceWarning {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    write!(f, "{:?}: {}", self.source, self.message)
+  }
+}
+
+/// A candidate considered while loading the icons, see [`crate::RepoIcons::explain`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Candidate {
+  /// the url, or the raw src if it couldn't be resolved
+  pub url: String,
+  #[serde(with = "serde_with::rust::display_fromstr")]
+  pub kind: RepoIconKind,
+  /// `None` if the candidate is one of the icons
+  pub excluded: Option<Exclusion>,
+  /// only known for readme images
+  pub weight: Option<u8>,
+  pub signals: Vec<Signal>,
+}
+
+impl Candidate {
+  pub(crate) fn excluded<U: ToString>(url: U, kind: RepoIconKind, exclusion: Exclusion) -> Self {
+    Candidate {
+      url: url.to_string(),
+      kind,
+      excluded: Some(exclusion),
+      weight: None,
+      signals: Vec::new(),
+    }
+  }
+}
+
+impl Display for Candidate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    match self.weight {
+      Some(weight) => write!(f, "{:>3} ", weight)?,
+      None => write!(f, "  - ")?,
+    }
+
+    write!(f, "{} {}", self.kind, self.url)?;
+
+    if let Some(exclusion) = self.excluded {
+      write!(f, " (excluded: {})", exclusion)?;
+    }
+
+    if !self.signals.is_empty() {
+      let signals = self
+        .signals
+        .iter()
+        .map(|signal| format!("{:?}", signal))
+        .collect::<Vec<_>>();
+      write!(f, " [{}]", signals.join(", "))?;
+    }
+
+    Ok(())
+  }
+}
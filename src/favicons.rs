@@ -0,0 +1,51 @@
+use crate::{
+  blacklist::is_badge, client::with_permit, homepage_backgrounds::load_homepage, RepoIcon,
+  RepoIconKind, RepoIconsBuilder,
+};
+use site_icons::Icons;
+use std::error::Error;
+use url::Url;
+
+/// Load the icons of a website on its own, without a repo.
+/// Ordered from highest to lowest resolution
+///
+/// ```no_run
+/// # use repo_icons::favicons_for_site;
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let icons = favicons_for_site("https://reactjs.org".parse()?).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn favicons_for_site(url: Url) -> Result<Vec<RepoIcon>, Box<dyn Error>> {
+  favicons_for_site_with(&RepoIconsBuilder::new(), url).await
+}
+
+/// [`favicons_for_site`] with the options of a load, finding the same icons as
+/// a repo's homepage: the mask icons, background logos, color schemes & labels
+pub async fn favicons_for_site_with(
+  options: &RepoIconsBuilder,
+  url: Url,
+) -> Result<Vec<RepoIcon>, Box<dyn Error>> {
+  let mut icons = Icons::new();
+  let scan = load_homepage(options, &mut icons, &url).await?;
+
+  let mut site_icons = with_permit(options, icons.entries())
+    .await
+    .into_iter()
+    .filter(|entry| !is_badge(&entry.url))
+    .map(|entry| {
+      let mut icon = RepoIcon::new_with_headers(
+        entry.url,
+        entry.headers,
+        RepoIconKind::Site(entry.kind),
+        entry.info,
+      );
+      scan.apply(&mut icon);
+      icon
+    })
+    .collect::<Vec<_>>();
+
+  site_icons.sort_by(|a, b| a.info.cmp(&b.info));
+
+  Ok(site_icons)
+}
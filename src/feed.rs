@@ -0,0 +1,111 @@
+use crate::{RepoIcon, RepoIcons};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// how many icons besides the primary one each entry has
+const ALTERNATES: usize = 2;
+
+type RepoResult = ((String, String), Result<RepoIcons, Box<dyn Error>>);
+
+/// The results of [`crate::RepoIconsBuilder::load_many`], see [`RepoIconsBatch::to_feed`]
+pub struct RepoIconsBatch(pub Vec<RepoResult>);
+
+impl From<Vec<RepoResult>> for RepoIconsBatch {
+  fn from(results: Vec<RepoResult>) -> Self {
+    RepoIconsBatch(results)
+  }
+}
+
+/// A repo's entry in [`RepoIconsBatch::to_feed`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeedEntry {
+  pub owner: String,
+  pub repo: String,
+  /// `None` if the load failed, or the icon is below
+  /// [`crate::RepoIconsBuilder::min_confidence`]
+  pub primary: Option<FeedIcon>,
+  pub alternates: Vec<FeedIcon>,
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeedIcon {
+  pub url: String,
+  /// the icon's [`crate::RepoIconKind`], eg. `readme_image`
+  pub source: String,
+  /// `None` for svgs without a declared size
+  pub size: Option<FeedSize>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeedSize {
+  pub width: u32,
+  pub height: u32,
+}
+
+impl From<&RepoIcon> for FeedIcon {
+  fn from(icon: &RepoIcon) -> Self {
+    FeedIcon {
+      url: icon.url.to_string(),
+      source: icon.kind.to_string(),
+      size: icon
+        .dimensions()
+        .map(|(width, height)| FeedSize { width, height }),
+    }
+  }
+}
+
+impl RepoIconsBatch {
+  /// Flatten the batch into one entry per repo, with its primary icon and
+  /// up to 2 alternates, eg. to cache a dashboard's icons as a single blob.
+  /// Repos that failed to load are kept, with their error
+  ///
+  /// ```no_run
+  /// # use repo_icons::{RepoIconsBatch, RepoIconsBuilder};
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let batch = RepoIconsBatch::from(
+  ///   RepoIconsBuilder::new()
+  ///     .load_many(&[("facebook", "react"), ("rust-lang", "rust")])
+  ///     .await,
+  /// );
+  /// println!("{}", serde_json::to_string(&batch.to_feed())?);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn to_feed(&self) -> Vec<FeedEntry> {
+    self
+      .0
+      .iter()
+      .map(|((owner, repo), result)| match result {
+        Ok(icons) => {
+          let primary = icons.primary().map(|primary| primary.icon);
+          let alternates = icons
+            .iter()
+            .filter(|icon| {
+              primary
+                .as_ref()
+                .is_none_or(|primary| icon.url != primary.url)
+            })
+            .take(ALTERNATES)
+            .map(FeedIcon::from)
+            .collect();
+
+          FeedEntry {
+            owner: owner.clone(),
+            repo: repo.clone(),
+            primary: primary.as_ref().map(FeedIcon::from),
+            alternates,
+            error: None,
+          }
+        }
+        Err(err) => FeedEntry {
+          owner: owner.clone(),
+          repo: repo.clone(),
+          primary: None,
+          alternates: Vec::new(),
+          error: Some(err.to_string()),
+        },
+      })
+      .collect()
+  }
+}
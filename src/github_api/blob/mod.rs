@@ -1,6 +1,9 @@
 mod repo_files;
 
-use crate::RepoBlob;
+use crate::{
+  client::{github_client, send},
+  RepoBlob, RepoIconsBuilder,
+};
 use fancy_regex::{escape, Regex};
 use futures::future::join_all;
 use repo_files::{get_repo_files, File, FileType};
@@ -8,20 +11,23 @@ use std::error::Error;
 use std::path::Path;
 
 fn is_valid_blob(file: &File) -> bool {
-  matches!(file.r#type, FileType::Blob)
-    && (file.path.ends_with(".png")
-      || file.path.ends_with(".jpg")
-      || file.path.ends_with(".jpeg")
-      || file.path.ends_with(".ico")
-      || file.path.ends_with(".svg"))
+  matches!(file.r#type, FileType::Blob) && is_icon_path(&file.path)
 }
 
-fn get_weight(owner: &str, repo: &str, file: &File) -> u8 {
+pub(crate) fn is_icon_path(path: &str) -> bool {
+  path.ends_with(".png")
+    || path.ends_with(".jpg")
+    || path.ends_with(".jpeg")
+    || path.ends_with(".ico")
+    || path.ends_with(".svg")
+}
+
+fn get_weight(owner: &str, repo: &str, path: &str) -> u8 {
   let owner = owner.to_lowercase();
   let owner = owner.rsplit_once('-').unwrap_or((&owner, "")).0;
   let repo = repo.to_lowercase();
 
-  let fullpath = file.path.to_lowercase();
+  let fullpath = path.to_lowercase();
   let (path, filename) = get_path_and_filename(&fullpath);
 
   let mut weight = 0;
@@ -40,7 +46,8 @@ fn get_weight(owner: &str, repo: &str, file: &File) -> u8 {
 
   let fixtures = regex!("(e2e|fixtures|third[-_]party|test(s)?)/");
   if !fixtures.is_match(&fullpath).unwrap() {
-    if filename.contains(owner) {
+    // local repos don't have an owner
+    if !owner.is_empty() && filename.contains(owner) {
       matches_icon = true;
       weight += 1;
     }
@@ -77,6 +84,7 @@ fn get_weight(owner: &str, repo: &str, file: &File) -> u8 {
 }
 
 async fn get_package_json_icon(
+  options: &RepoIconsBuilder,
   owner: &str,
   repo: &str,
   commit_sha: &str,
@@ -100,14 +108,17 @@ async fn get_package_json_icon(
           icon: String,
         }
 
-        let package_json = gh_get!(
-          "https://raw.githubusercontent.com/{}/{}/{}/{}",
-          owner,
-          repo,
-          commit_sha,
-          file.path
+        let package_json = send(
+          options,
+          gh_get!(
+            github_client(options),
+            "https://raw.githubusercontent.com/{}/{}/{}/{}",
+            owner,
+            repo,
+            commit_sha,
+            file.path
+          ),
         )
-        .send()
         .await
         .ok()?
         .json::<PackageJSON>()
@@ -147,44 +158,20 @@ async fn get_package_json_icon(
   }
 }
 
-pub async fn get_blob(owner: &str, repo: &str) -> Result<Option<(bool, RepoBlob)>, Box<dyn Error>> {
-  let (commit_sha, files) = get_repo_files(owner, repo).await?;
-
-  let result = if let Some(result) = get_package_json_icon(owner, repo, &commit_sha, &files).await {
-    Some((true, result))
-  } else {
-    let mut results = files
-      .into_iter()
-      .filter(|file| is_valid_blob(file))
-      .map(|file| {
-        let weight = get_weight(owner, repo, &file);
-        (file, weight)
-      })
-      .filter(|(_, weight)| *weight > 0)
-      .collect::<Vec<_>>();
-
-    results.sort_by(|(_, a_weight), (_, b_weight)| b_weight.cmp(&a_weight));
+pub async fn get_blob(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+) -> Result<Option<(bool, RepoBlob)>, Box<dyn Error>> {
+  let (commit_sha, files) = get_repo_files(options, owner, repo).await?;
 
-    results.get(0).cloned().map(|(file, weight)| {
-      let final_results = results
-        .into_iter()
-        .filter(|(_, other_weight)| weight == *other_weight)
-        .collect::<Vec<_>>();
-
-      let (file, _) = final_results
-        .iter()
-        .cloned()
-        .find(|(file, _)| file.path.ends_with(".svg"))
-        .or_else(|| {
-          final_results
-            .into_iter()
-            .find(|(file, _)| file.path.ends_with(".png"))
-        })
-        .unwrap_or((file, weight));
-
-      (false, file)
-    })
-  };
+  let result =
+    if let Some(result) = get_package_json_icon(options, owner, repo, &commit_sha, &files).await {
+      Some((true, result))
+    } else {
+      let files = files.into_iter().filter(|file| is_valid_blob(file));
+      best_icon_file(owner, repo, files, |file| &file.path).map(|file| (false, file))
+    };
 
   Ok(result.map(|(is_package_json, file)| {
     (
@@ -201,6 +188,45 @@ pub async fn get_blob(owner: &str, repo: &str) -> Result<Option<(bool, RepoBlob)
   }))
 }
 
+/// The file most likely to be the repo's icon, going by its path. Equally
+/// weighted files prefer an svg, then a png
+pub(crate) fn best_icon_file<T: Clone>(
+  owner: &str,
+  repo: &str,
+  files: impl Iterator<Item = T>,
+  path: impl Fn(&T) -> &str,
+) -> Option<T> {
+  let mut results = files
+    .map(|file| {
+      let weight = get_weight(owner, repo, path(&file));
+      (file, weight)
+    })
+    .filter(|(_, weight)| *weight > 0)
+    .collect::<Vec<_>>();
+
+  results.sort_by(|(_, a_weight), (_, b_weight)| b_weight.cmp(&a_weight));
+
+  results.get(0).cloned().map(|(file, weight)| {
+    let final_results = results
+      .into_iter()
+      .filter(|(_, other_weight)| weight == *other_weight)
+      .collect::<Vec<_>>();
+
+    let (file, _) = final_results
+      .iter()
+      .cloned()
+      .find(|(file, _)| path(file).ends_with(".svg"))
+      .or_else(|| {
+        final_results
+          .into_iter()
+          .find(|(file, _)| path(file).ends_with(".png"))
+      })
+      .unwrap_or((file, weight));
+
+    file
+  })
+}
+
 fn get_path_and_filename(fullpath: &str) -> (&str, &str) {
   fullpath.rsplit_once('/').unwrap_or(("", &fullpath))
 }
@@ -1,4 +1,9 @@
+use crate::{
+  client::{github_client, send},
+  RepoIconsBuilder,
+};
 use cached::proc_macro::cached;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::error::Error;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,19 +31,26 @@ struct Trees {
   tree: Vec<File>,
 }
 
-#[cached]
+#[cached(
+  key = "(String, String, String)",
+  convert = r#"{ (owner.clone(), repo.clone(), tree_sha.clone()) }"#
+)]
 async fn get_repo_files_cached(
+  options: &RepoIconsBuilder,
   owner: String,
   repo: String,
   tree_sha: String,
 ) -> Result<Vec<File>, String> {
-  let res = gh_api_get!(
-    "repos/{}/{}/git/trees/{}?recursive=1",
-    owner,
-    repo,
-    tree_sha
+  let res = send(
+    options,
+    gh_api_get!(
+      github_client(options),
+      "repos/{}/{}/git/trees/{}?recursive=1",
+      owner,
+      repo,
+      tree_sha
+    ),
   )
-  .send()
   .await
   .map_err(|e| format!("{:?}", e).to_string())?
   .json::<Trees>()
@@ -49,14 +61,26 @@ async fn get_repo_files_cached(
 }
 
 pub async fn get_repo_files(
+  options: &RepoIconsBuilder,
   owner: &str,
   repo: &str,
 ) -> Result<(String, Vec<File>), Box<dyn Error>> {
-  let res = gh_api_get!("repos/{}/{}/commits", owner, repo)
-    .send()
-    .await?
-    .json::<Vec<Commit>>()
-    .await?;
+  let res = send(
+    options,
+    gh_api_get!(
+      github_client(options),
+      "repos/{}/{}/commits{}",
+      owner,
+      repo,
+      match &options.branch {
+        Some(branch) => format!("?sha={}", utf8_percent_encode(branch, NON_ALPHANUMERIC)),
+        None => String::new(),
+      }
+    ),
+  )
+  .await?
+  .json::<Vec<Commit>>()
+  .await?;
 
   let commit_sha = res
     .into_iter()
@@ -65,6 +89,7 @@ pub async fn get_repo_files(
     .sha;
 
   let files = get_repo_files_cached(
+    options,
     owner.to_lowercase(),
     repo.to_lowercase(),
     commit_sha.clone(),
@@ -1,7 +1,13 @@
 mod blob;
+mod owner;
+mod rate_limit;
 pub mod readme;
+mod social_preview;
 mod user_repos;
 
 pub use blob::*;
+pub(crate) use owner::*;
+pub use rate_limit::*;
 pub use readme::*;
+pub use social_preview::*;
 pub use user_repos::*;
@@ -0,0 +1,37 @@
+use crate::{
+  client::{github_client, send},
+  RepoIconsBuilder,
+};
+use std::error::Error;
+use url::Url;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub(crate) enum OwnerType {
+  User,
+  Organization,
+  Bot,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Owner {
+  #[serde(rename = "type")]
+  pub owner_type: OwnerType,
+  pub avatar_url: Url,
+}
+
+/// Get a user / organization
+pub(crate) async fn get_owner(
+  options: &RepoIconsBuilder,
+  login: &str,
+) -> Result<Owner, Box<dyn Error>> {
+  let owner = send(
+    options,
+    gh_api_get!(github_client(options), "users/{}", login),
+  )
+  .await?
+  .error_for_status()?
+  .json::<Owner>()
+  .await?;
+
+  Ok(owner)
+}
@@ -0,0 +1,91 @@
+use crate::{
+  client::{execute, github_client},
+  RepoIconsBuilder,
+};
+use once_cell::sync::Lazy;
+use reqwest::Response;
+use std::{error::Error, sync::RwLock};
+
+/// the core rate limit, from the headers of the latest api response
+static LAST_RATE_LIMIT: Lazy<RwLock<Option<RateLimit>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimit {
+  pub limit: u32,
+  pub remaining: u32,
+  /// unix timestamp of when the limit resets
+  pub reset: u64,
+}
+
+/// Get the core rate limit, this doesn't count against the limit itself
+pub async fn get_rate_limit() -> Result<RateLimit, Box<dyn Error>> {
+  get_rate_limit_with(&RepoIconsBuilder::new()).await
+}
+
+pub(crate) async fn get_rate_limit_with(
+  options: &RepoIconsBuilder,
+) -> Result<RateLimit, Box<dyn Error>> {
+  #[derive(Deserialize)]
+  struct Resources {
+    core: RateLimit,
+  }
+
+  #[derive(Deserialize)]
+  struct Response {
+    resources: Resources,
+  }
+
+  let res = execute(options, gh_api_get!(github_client(options), "rate_limit"))
+    .await?
+    .error_for_status()?
+    .json::<Response>()
+    .await?;
+
+  Ok(res.resources.core)
+}
+
+/// Record the `X-RateLimit-*` headers of an api response
+pub(crate) fn record_rate_limit(res: &Response) {
+  let header = |name| {
+    let value = res.headers().get(name)?.to_str().ok()?;
+    value.parse::<u64>().ok()
+  };
+
+  // search & graphql have their own limits
+  let resource = res.headers().get("x-ratelimit-resource");
+  if resource.is_some_and(|resource| resource != "core") {
+    return;
+  }
+
+  if let (Some(limit), Some(remaining), Some(reset)) = (
+    header("x-ratelimit-limit"),
+    header("x-ratelimit-remaining"),
+    header("x-ratelimit-reset"),
+  ) {
+    *LAST_RATE_LIMIT.write().unwrap() = Some(RateLimit {
+      limit: limit as u32,
+      remaining: remaining as u32,
+      reset,
+    });
+  }
+}
+
+/// The latest recorded rate limit, `None` once it's reset
+pub(crate) fn last_rate_limit() -> Option<RateLimit> {
+  let rate_limit = LAST_RATE_LIMIT.read().unwrap().clone()?;
+
+  #[cfg(not(target_arch = "wasm32"))]
+  {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|now| now.as_secs())
+      .unwrap_or(0);
+    if rate_limit.reset <= now {
+      return None;
+    }
+  }
+
+  Some(rate_limit)
+}
@@ -0,0 +1,96 @@
+use super::{Readme, ReadmeImage, Signal};
+use crate::{blacklist::is_placeholder, Exclusion};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// What an image in the readme is, derived from the same signals as the ranking
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageKind {
+  Badge,
+  Placeholder,
+  Logo,
+  Banner,
+  Screenshot,
+  Avatar,
+  /// one of a row of "built with" technology logos
+  TechStack,
+  Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClassifiedImage {
+  pub src: Url,
+  pub kind: ImageKind,
+  /// `None` for the images that are excluded before they're
+  /// scored, eg. badges, dividers and repeated images
+  pub image: Option<ReadmeImage>,
+}
+
+impl ReadmeImage {
+  pub fn kind(&self) -> ImageKind {
+    let signals = self.signals();
+    let path = self.src.path().to_lowercase();
+    let alt = self.alt.as_deref().unwrap_or_default().to_lowercase();
+    let mentions = |keyword: &&str| path.contains(keyword) || alt.contains(keyword);
+
+    if is_placeholder(&self.src) {
+      ImageKind::Placeholder
+    } else if is_avatar(&self.src) {
+      ImageKind::Avatar
+    } else if self.in_tech_stack_row {
+      ImageKind::TechStack
+    } else if signals.contains(&Signal::BannerMention) {
+      ImageKind::Banner
+    } else if signals.contains(&Signal::LogoMention) || self.is_logo_candidate() {
+      ImageKind::Logo
+    } else if signals.contains(&Signal::UltrawideAspect)
+      || ["screenshot", "demo", "preview"].iter().any(mentions)
+    {
+      ImageKind::Screenshot
+    } else {
+      ImageKind::Other
+    }
+  }
+}
+
+impl Readme {
+  /// Every image in the readme with what kind of image it is, including the
+  /// images that are normally excluded (eg. badges)
+  pub async fn classified_images(&self) -> Vec<ClassifiedImage> {
+    let (images, excluded) = self.images_with_excluded().await;
+
+    let excluded = excluded.into_iter().filter_map(|candidate| {
+      let kind = match candidate.excluded? {
+        Exclusion::Badge => ImageKind::Badge,
+        Exclusion::InvalidUrl => return None,
+        _ => ImageKind::Other,
+      };
+
+      Some(ClassifiedImage {
+        src: self.qualify_url(&candidate.url).ok()?,
+        kind,
+        image: None,
+      })
+    });
+
+    images
+      .into_iter()
+      .map(|image| ClassifiedImage {
+        src: image.src.clone(),
+        kind: image.kind(),
+        image: Some(image),
+      })
+      .chain(excluded)
+      .collect()
+  }
+}
+
+/// A github user's avatar, either directly or through `github.com/USER.png`
+fn is_avatar(url: &Url) -> bool {
+  match url.domain() {
+    Some("avatars.githubusercontent.com") => true,
+    Some("github.com") => regex!(r"^/[^/]+\.png$").is_match(url.path()).unwrap(),
+    _ => false,
+  }
+}
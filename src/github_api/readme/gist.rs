@@ -0,0 +1,91 @@
+use super::{decode_text, parse_icon_directive, render_fallback, render_markdown_api, Readme};
+use crate::{
+  client::{client, github_client, send},
+  RepoIconsBuilder,
+};
+use serde::Deserialize;
+use std::{collections::BTreeMap, error::Error};
+use url::Url;
+
+#[derive(Deserialize)]
+struct GistOwner {
+  login: String,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+  filename: String,
+  language: Option<String>,
+  raw_url: Url,
+  /// only the first megabyte of the file is included
+  truncated: bool,
+  content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Gist {
+  owner: Option<GistOwner>,
+  /// github orders a gist's files by their name
+  files: BTreeMap<String, GistFile>,
+}
+
+impl GistFile {
+  fn is_markdown(&self) -> bool {
+    let filename = self.filename.to_lowercase();
+    self.language.as_deref() == Some("Markdown")
+      || filename.ends_with(".md")
+      || filename.ends_with(".markdown")
+  }
+}
+
+impl Readme {
+  /// Load the first markdown file of a gist as the readme,
+  /// or `None` if the gist doesn't have any markdown files
+  pub async fn load_gist(id: &str) -> Result<Option<Self>, Box<dyn Error>> {
+    Readme::load_gist_with(&RepoIconsBuilder::new(), id).await
+  }
+
+  pub async fn load_gist_with(
+    options: &RepoIconsBuilder,
+    id: &str,
+  ) -> Result<Option<Self>, Box<dyn Error>> {
+    let gist = send(options, gh_api_get!(github_client(options), "gists/{}", id))
+      .await?
+      .error_for_status()?
+      .json::<Gist>()
+      .await?;
+
+    let file = match gist.files.into_values().find(GistFile::is_markdown) {
+      Some(file) => file,
+      None => return Ok(None),
+    };
+
+    let truncated = file.truncated;
+    let source = match file.content.filter(|_| !truncated) {
+      Some(content) => content,
+      None => {
+        let res = send(options, client(options).get(file.raw_url.clone()))
+          .await?
+          .error_for_status()?;
+        decode_text(res).await?
+      }
+    };
+
+    let html = match render_markdown_api(options, None, &source).await {
+      Ok(html) => html,
+      Err(err) => render_fallback(&source, err)?,
+    };
+
+    let owner = gist.owner.map(|owner| owner.login).unwrap_or_default();
+    let mut readme = Readme::new(&owner, id, &html, false, "HEAD", None);
+    readme.apply_options(options);
+    readme.declared_icon = parse_icon_directive(&source);
+
+    // relative urls are relative to the gist's raw files,
+    // as gists don't have directories
+    readme.link_base = file.raw_url.join(".")?;
+    readme.repo_base = readme.link_base.clone();
+
+    Ok(Some(readme))
+  }
+}
@@ -0,0 +1,183 @@
+#[cfg(not(feature = "markdown"))]
+use super::{render_fallback, render_markdown_api};
+use super::{Readme, Repo, RepoOwner};
+use crate::{
+  client::{get_token, github_client, send},
+  RepoIconsBuilder,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::error::Error;
+use url::Url;
+
+/// The repo's metadata, with each of the markdown readme names github
+/// recognizes in order of preference. Readmes in `.github` / `docs` are
+/// only found by the REST api
+const QUERY: &str = r#"
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    owner { login }
+    name
+    defaultBranchRef { name }
+    isPrivate
+    homepageUrl
+    description
+    repositoryTopics(first: 100) { nodes { topic { name } } }
+    primaryLanguage { name }
+    readmeMd: object(expression: "HEAD:README.md") { ...ReadmeBlob }
+    readmeLowerMd: object(expression: "HEAD:readme.md") { ...ReadmeBlob }
+    readmeTitleMd: object(expression: "HEAD:Readme.md") { ...ReadmeBlob }
+    readmeMarkdown: object(expression: "HEAD:README.markdown") { ...ReadmeBlob }
+    readme: object(expression: "HEAD:README") { ...ReadmeBlob }
+  }
+}
+
+fragment ReadmeBlob on Blob {
+  text
+  isTruncated
+}
+"#;
+
+#[derive(Deserialize)]
+struct Name {
+  name: String,
+}
+
+#[derive(Deserialize)]
+struct TopicNode {
+  topic: Name,
+}
+
+#[derive(Deserialize)]
+struct Topics {
+  nodes: Vec<TopicNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Blob {
+  text: Option<String>,
+  is_truncated: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+  owner: RepoOwner,
+  name: String,
+  default_branch_ref: Option<Name>,
+  is_private: bool,
+  #[serde(deserialize_with = "super::deserialize_url")]
+  homepage_url: Option<Url>,
+  description: Option<String>,
+  repository_topics: Topics,
+  primary_language: Option<Name>,
+  readme_md: Option<Blob>,
+  readme_lower_md: Option<Blob>,
+  readme_title_md: Option<Blob>,
+  readme_markdown: Option<Blob>,
+  readme: Option<Blob>,
+}
+
+#[derive(Deserialize)]
+struct Data {
+  repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+  message: String,
+}
+
+#[derive(Deserialize)]
+struct Response {
+  data: Option<Data>,
+  #[serde(default)]
+  errors: Vec<GraphqlError>,
+}
+
+impl Readme {
+  /// Load the repo's metadata & readme source in one query, see
+  /// [`RepoIconsBuilder::use_graphql`]
+  pub(super) async fn load_graphql(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    if get_token().is_none() {
+      return Err("the graphql api needs a token".into());
+    }
+
+    let response = send(
+      options,
+      github_client(options)
+        .post("https://api.github.com/graphql")
+        .json(&json!({
+          "query": QUERY,
+          "variables": { "owner": owner, "name": repo },
+        })),
+    )
+    .await?
+    .error_for_status()?
+    .json::<Response>()
+    .await?;
+
+    if let Some(error) = response.errors.first() {
+      return Err(error.message.clone().into());
+    }
+
+    let repository = response
+      .data
+      .and_then(|data| data.repository)
+      .ok_or("repo not found")?;
+
+    let source = vec![
+      repository.readme_md,
+      repository.readme_lower_md,
+      repository.readme_title_md,
+      repository.readme_markdown,
+      repository.readme,
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|blob| !blob.is_truncated)
+    .find_map(|blob| blob.text)
+    .ok_or("no markdown readme found")?;
+
+    let default_branch = repository
+      .default_branch_ref
+      .ok_or("repo has no default branch")?
+      .name;
+
+    // the query returns the source, so rendering it locally saves a request
+    #[cfg(feature = "markdown")]
+    let html = super::render_markdown(&source);
+
+    #[cfg(not(feature = "markdown"))]
+    let html = {
+      let context = format!("{}/{}", repository.owner.login, repository.name);
+      match render_markdown_api(options, Some(&context), &source).await {
+        Ok(html) => html,
+        Err(err) => render_fallback(&source, err)?,
+      }
+    };
+
+    let repo = Repo {
+      owner: repository.owner,
+      name: repository.name,
+      default_branch,
+      private: repository.is_private,
+      homepage: repository.homepage_url,
+      topics: repository
+        .repository_topics
+        .nodes
+        .into_iter()
+        .map(|node| node.topic.name)
+        .collect(),
+      language: repository.primary_language.map(|language| language.name),
+      description: repository.description,
+    };
+
+    Readme::from_repo(options, None, repo, html, &source, None)
+  }
+}
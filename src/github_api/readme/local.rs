@@ -0,0 +1,45 @@
+use super::{parse_icon_directive, render_markdown, Readme};
+use std::{error::Error, fs, path::Path};
+use url::Url;
+
+/// in order of preference
+const README_NAMES: [&str; 3] = ["readme.md", "readme.markdown", "readme"];
+
+impl Readme {
+  /// Load the readme of a repo checked out on disk, rendered locally. Its
+  /// relative images resolve to `file://` urls inside the directory
+  pub fn load_from_dir(dir: &Path) -> Result<Self, Box<dyn Error>> {
+    let dir = dir.canonicalize()?;
+
+    let files = fs::read_dir(&dir)?
+      .filter_map(|entry| entry.ok())
+      .map(|entry| entry.path())
+      .filter(|path| path.is_file())
+      .collect::<Vec<_>>();
+    let path = README_NAMES
+      .iter()
+      .find_map(|name| {
+        files.iter().find(|path| {
+          let file_name = path.file_name().and_then(|name| name.to_str());
+          file_name.map(str::to_lowercase).as_deref() == Some(name)
+        })
+      })
+      .ok_or_else(|| format!("no readme found in {}", dir.display()))?;
+
+    let source = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+    let html = render_markdown(&source);
+
+    let repo = dir
+      .file_name()
+      .and_then(|name| name.to_str())
+      .unwrap_or_default();
+    let mut readme = Readme::new("", repo, &html, false, "HEAD", None);
+    readme.declared_icon = parse_icon_directive(&source);
+
+    readme.repo_base = Url::from_directory_path(&dir)
+      .map_err(|_| format!("{} isn't an absolute path", dir.display()))?;
+    readme.link_base = readme.repo_base.clone();
+
+    Ok(readme)
+  }
+}
@@ -0,0 +1,75 @@
+use super::Readme;
+use crate::client::{client, send};
+use fancy_regex::{escape, Regex};
+use futures::future::join_all;
+use std::{collections::HashMap, convert::TryInto};
+use url::Url;
+
+/// The metadata files a logo is commonly declared in, in order of preference
+pub(crate) const METADATA_FILES: [&str; 4] = [
+  "package.json",
+  "composer.json",
+  "CITATION.cff",
+  "pyproject.toml",
+];
+pub(crate) const METADATA_FIELDS: [&str; 2] = ["logo", "icon"];
+
+impl Readme {
+  /// The logo declared by a field of the repo's metadata files, see
+  /// [`crate::RepoIconsBuilder::metadata_logo`]. The first file with one of
+  /// the fields wins
+  pub(crate) async fn metadata_logo(&self) -> Option<(Url, HashMap<String, String>)> {
+    let options = &self.options;
+
+    let values = join_all(options.metadata_files.iter().map(async move |file| {
+      let branch = options.branch.as_deref().unwrap_or("HEAD");
+      let (url, headers) = self.raw_file(branch, file);
+      let request = client(options)
+        .get(url)
+        .headers((&headers).try_into().ok()?);
+
+      let res = send(options, request).await.ok()?.error_for_status().ok()?;
+      let text = res.text().await.ok()?;
+      field_value(file, &text, &options.metadata_fields)
+    }))
+    .await;
+
+    let url = values
+      .into_iter()
+      .flatten()
+      .find_map(|value| self.qualify_url(&value).ok())?;
+
+    Some(match self.get_branch_and_path(&url).await {
+      Some((branch, path)) => self.raw_file(&branch, &path),
+      None => (url, HashMap::new()),
+    })
+  }
+}
+
+/// The value of the first of the fields set in a metadata file. Json files
+/// are parsed, otherwise top-level `field: value` (yaml) and
+/// `field = "value"` (toml) lines are matched
+fn field_value(file: &str, text: &str, fields: &[String]) -> Option<String> {
+  if file.to_lowercase().ends_with(".json") {
+    let json = serde_json::from_str::<serde_json::Value>(text).ok()?;
+    let json = json.as_object()?;
+
+    return fields.iter().find_map(|field| {
+      json
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(field))
+        .and_then(|(_, value)| value.as_str())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    });
+  }
+
+  fields.iter().find_map(|field| {
+    let pattern = format!(
+      r#"(?im)^["']?{}["']?\s*[:=]\s*["']?([^"'\s#]+)"#,
+      escape(field)
+    );
+    let captures = Regex::new(&pattern).ok()?.captures(text).ok().flatten()?;
+    Some(captures[1].to_string())
+  })
+}
@@ -1,85 +1,281 @@
+mod classified_image;
+mod gist;
+mod graphql;
+#[cfg(all(feature = "markdown", not(target_arch = "wasm32")))]
+mod local;
+mod metadata_logo;
 mod primary_heading;
 pub mod readme_image;
 mod repo_redirect;
+mod scoring_profile;
+mod secondary_readmes;
+#[cfg(feature = "tokenizer")]
+mod tokenizer;
 
+pub use classified_image::*;
+pub(crate) use metadata_logo::{METADATA_FIELDS, METADATA_FILES};
 pub use readme_image::*;
+pub use scoring_profile::*;
+#[cfg(feature = "tokenizer")]
+pub use tokenizer::parse_simple_readme;
 
-use self::{primary_heading::PrimaryHeading, repo_redirect::is_same_repo};
-use scraper::Html;
+pub use repo_redirect::add_repo_alias;
+#[cfg(feature = "cache")]
+pub(crate) use repo_redirect::forget_repo_redirect;
+
+use self::{
+  primary_heading::PrimaryHeading,
+  repo_redirect::{is_same_repo, resolve_alias},
+};
+use crate::{
+  blacklist::{is_badge, is_shared_host},
+  client::{get_token, github_client, send},
+  Candidate, Exclusion, RepoIconKind, RepoIconsBuilder,
+};
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use futures::{future::join, FutureExt};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::{
+  header::{ETAG, IF_NONE_MATCH},
+  StatusCode,
+};
+use scraper::{node::Element, ElementRef, Html, Node};
 use serde::{de, Deserialize};
-use std::error::Error;
+use std::{collections::HashMap, error::Error, iter, panic::AssertUnwindSafe};
 use url::Url;
 
+#[derive(Clone, Deserialize)]
+struct RepoOwner {
+  login: String,
+}
+
+#[derive(Clone, Deserialize)]
+struct Repo {
+  owner: RepoOwner,
+  name: String,
+  default_branch: String,
+  private: bool,
+  #[serde(deserialize_with = "deserialize_url")]
+  homepage: Option<Url>,
+  #[serde(default)]
+  topics: Vec<String>,
+  language: Option<String>,
+  description: Option<String>,
+}
+
 pub struct Readme {
+  /// lowercased for matching, see `cased_owner` for urls
   pub owner: String,
   pub repo: String,
+  /// the owner & repo as they're cased on github, as raw urls are case-sensitive
+  pub(crate) cased_owner: String,
+  pub(crate) cased_repo: String,
   pub homepage: Option<Url>,
   pub private: bool,
+  /// the repo's topics, lowercased with the separators removed
+  pub topics: Vec<String>,
+  pub profile: ScoringProfile,
+  /// the max number of images that are processed, in document order
+  pub max_images: Option<usize>,
+  /// stop processing the images once a logo candidate weighs at least this much
+  pub stop_on_confident_logo: Option<u8>,
+  /// the ETag of the readme, see [`crate::RepoIconsBuilder::load_if_modified`]
+  pub etag: Option<String>,
+  /// the repo's primary language, as named by github
+  pub language: Option<String>,
+  /// the repo's description on github
+  pub description: Option<String>,
+  /// use the original url of camo proxied images, instead of the camo url
+  pub resolve_camo: bool,
   link_base: Url,
+  repo_base: Url,
+  /// used for the requests made while extracting the images
+  options: RepoIconsBuilder,
+  pub(crate) declared_icon: Option<String>,
+  /// the repo's metadata when it was loaded from github, so other files in
+  /// the repo can be loaded without fetching it again
+  metadata: Option<Repo>,
   document: Html,
 }
 
 impl Readme {
   pub async fn load(owner: &str, repo: &str) -> Result<Self, Box<dyn Error>> {
-    #[derive(Deserialize)]
-    struct RepoOwner {
-      login: String,
+    Readme::load_with(&RepoIconsBuilder::new(), owner, repo).await
+  }
+
+  pub async fn load_with(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    Readme::load_from(options, owner, repo, None).await
+  }
+
+  /// Load a specific markdown file in the repo as the readme,
+  /// eg. `docs/README.md` or `README.zh-CN.md`
+  pub async fn load_path(owner: &str, repo: &str, path: &str) -> Result<Self, Box<dyn Error>> {
+    Readme::load_path_with(&RepoIconsBuilder::new(), owner, repo, path).await
+  }
+
+  pub async fn load_path_with(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+    path: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    Readme::load_from(options, owner, repo, Some(path)).await
+  }
+
+  async fn load_from(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+    path: Option<&str>,
+  ) -> Result<Self, Box<dyn Error>> {
+    if options.use_graphql && path.is_none() && options.branch.is_none() {
+      match Readme::load_graphql(options, owner, repo).await {
+        Ok(readme) => return Ok(readme),
+        Err(err) => warn!("falling back to the rest api: {}", err),
+      }
     }
 
     #[derive(Deserialize)]
-    struct Repo {
-      owner: RepoOwner,
-      name: String,
-      default_branch: String,
-      private: bool,
-      #[serde(deserialize_with = "deserialize_url")]
-      homepage: Option<Url>,
+    struct Message {
+      message: String,
     }
 
+    /// the body of a 301 for a renamed / transferred repo
     #[derive(Deserialize)]
-    struct Message {
+    struct Moved {
       message: String,
+      url: Url,
     }
 
+    // moved has to come before message, as it also has a message
     #[derive(Deserialize)]
     #[serde(untagged)]
     enum Response {
       Repo(Repo),
+      Moved(Moved),
       Message(Message),
     }
 
-    let (response, readme_body) = try_join!(
+    let (response, (readme_body, readme_source, etag), _) = try_join!(
       async {
-        gh_api_get!("repos/{}/{}", owner, repo)
-          .send()
-          .await?
-          .json::<Response>()
-          .await
+        let response = send(
+          options,
+          gh_api_get!(github_client(options), "repos/{}/{}", owner, repo),
+        )
+        .await?
+        .json::<Response>()
+        .await?;
+
+        // redirects are usually followed, but when they aren't the body
+        // points to the canonical repo
+        match response {
+          Response::Moved(moved) => send(options, github_client(options).get(moved.url))
+            .await?
+            .json::<Response>()
+            .await
+            .map_err(Into::into),
+          response => Ok::<_, Box<dyn Error>>(response),
+        }
       },
       async {
-        gh_api_get!("repos/{}/{}/readme", owner, repo)
-          .header("Accept", "application/vnd.github.html")
-          .send()
-          .await?
-          .error_for_status()?
-          .text()
-          .await
-      }
+        if let Some(path) = path {
+          let (source, body) = render_file(options, owner, repo, path).await?;
+          return Ok((body, source, None));
+        }
+
+        if !options.icon_directive {
+          let (html, etag) = get_readme(options, owner, repo, HTML).await?;
+          return Ok((html, String::new(), etag));
+        }
+
+        // the rendered html has comments stripped, so the
+        // source is needed for the icon directive
+        let (html, source) = join(
+          get_readme(options, owner, repo, HTML),
+          get_readme(options, owner, repo, RAW),
+        )
+        .await;
+
+        match (html, source) {
+          (Ok((html, _)), Ok((source, etag))) => Ok((html, source, etag)),
+          (Err(err), Ok((source, etag))) => Ok((render_fallback(&source, err)?, source, etag)),
+          // without the source there's no directive, and no etag to check it against
+          (Ok((html, _)), Err(err)) => {
+            warn!("failed to get the readme's source {}", err);
+            Ok((html, String::new(), None))
+          }
+          (Err(err), Err(_)) => Err(err),
+        }
+      },
+      check_branch(options, owner, repo)
     )?;
 
     match response {
-      Response::Repo(repo) => Ok(Readme::new(
-        &repo.owner.login,
-        &repo.name,
-        &readme_body,
-        repo.private,
-        &repo.default_branch,
-        repo.homepage,
-      )),
+      Response::Repo(repo) => {
+        Readme::from_repo(options, path, repo, readme_body, &readme_source, etag)
+      }
+      Response::Moved(moved) => Err(format!("{} to {}", moved.message, moved.url).into()),
       Response::Message(message) => Err(message.message.into()),
     }
   }
 
+  /// Build the readme from the repo's metadata & the rendered readme,
+  /// however they were fetched
+  fn from_repo(
+    options: &RepoIconsBuilder,
+    path: Option<&str>,
+    repo: Repo,
+    mut readme_body: String,
+    readme_source: &str,
+    etag: Option<String>,
+  ) -> Result<Self, Box<dyn Error>> {
+    if let Some(max_readme_bytes) = options.max_readme_bytes {
+      if readme_body.len() > max_readme_bytes {
+        warn!("truncating readme to {} bytes", max_readme_bytes);
+
+        let mut end = max_readme_bytes;
+        while !readme_body.is_char_boundary(end) {
+          end -= 1;
+        }
+        readme_body.truncate(end);
+      }
+    }
+
+    let metadata = repo.clone();
+    let mut readme = Readme::new(
+      &repo.owner.login,
+      &repo.name,
+      &readme_body,
+      repo.private,
+      options.branch.as_deref().unwrap_or(&repo.default_branch),
+      repo.homepage,
+    );
+    readme.topics = repo
+      .topics
+      .iter()
+      .map(|topic| topic.to_lowercase().replace('-', ""))
+      .collect();
+    readme.apply_options(options);
+    readme.declared_icon = parse_icon_directive(readme_source);
+    readme.etag = etag;
+    readme.language = repo.language;
+    readme.description = repo
+      .description
+      .filter(|description| !description.trim().is_empty());
+    readme.metadata = Some(metadata);
+
+    // relative images are relative to the file, not the repo root
+    if let Some((directory, _)) = path.and_then(|path| path.trim_matches('/').rsplit_once('/')) {
+      readme.link_base = readme.link_base.join(&format!("{}/", directory))?;
+    }
+
+    Ok(readme)
+  }
+
   pub fn new(
     owner: &str,
     repo: &str,
@@ -88,9 +284,25 @@ impl Readme {
     default_branch: &str,
     homepage: Option<Url>,
   ) -> Self {
-    let document = Html::parse_document(&body);
+    let body = body.trim_start_matches('\u{feff}');
+    #[cfg(feature = "tokenizer")]
+    let document = parse_simple_readme(body).unwrap_or_else(|| Html::parse_document(body));
+    #[cfg(not(feature = "tokenizer"))]
+    let document = Html::parse_document(body);
 
-    let link_base = Url::parse(&format!(
+    Readme::from_document(owner, repo, document, private, default_branch, homepage)
+  }
+
+  /// [`Readme::new`] with the readme's already parsed html
+  pub fn from_document(
+    owner: &str,
+    repo: &str,
+    document: Html,
+    private: bool,
+    default_branch: &str,
+    homepage: Option<Url>,
+  ) -> Self {
+    let repo_base = Url::parse(&format!(
       "https://github.com/{}/{}/raw/{}/",
       owner, repo, default_branch
     ))
@@ -99,20 +311,157 @@ impl Readme {
     Self {
       owner: owner.to_lowercase(),
       repo: repo.to_lowercase(),
+      cased_owner: owner.to_string(),
+      cased_repo: repo.to_string(),
       private,
       homepage,
+      topics: Vec::new(),
+      profile: ScoringProfile::default(),
+      max_images: None,
+      stop_on_confident_logo: None,
+      etag: None,
+      language: None,
+      description: None,
+      resolve_camo: false,
+      options: RepoIconsBuilder::new(),
+      declared_icon: None,
+      metadata: None,
       document,
-      link_base,
+      link_base: repo_base.clone(),
+      repo_base,
     }
   }
 
+  /// Copy the load's scoring and extraction options onto the readme
+  pub(crate) fn apply_options(&mut self, options: &RepoIconsBuilder) {
+    self.profile = options.scoring_profile.clone();
+    self.max_images = options.max_images;
+    self.stop_on_confident_logo = options.stop_on_confident_logo;
+    self.resolve_camo = options.resolve_camo;
+    self.options = options.clone();
+  }
+
   pub async fn images(&self) -> Vec<ReadmeImage> {
+    self.extract_images(false).await.0
+  }
+
+  /// The images, along with the images that were excluded and why
+  pub async fn images_with_excluded(&self) -> (Vec<ReadmeImage>, Vec<Candidate>) {
+    self.extract_images(false).await
+  }
+
+  /// Extract the images from the html alone, without any network requests.
+  ///
+  /// Repo redirects aren't followed, so `sourced_from_repo` only matches the
+  /// literal owner/repo, and links aren't checked so `links_to` is always `None`
+  pub async fn images_offline(&self) -> Vec<ReadmeImage> {
+    self.extract_images(true).await.0
+  }
+
+  async fn extract_images(&self, offline: bool) -> (Vec<ReadmeImage>, Vec<Candidate>) {
     let primary_heading = &mut PrimaryHeading::new(&self.document);
 
+    // images repeated throughout the readme are dividers / decorations
+    let mut src_counts = HashMap::new();
+    for img in self.document.select(selector!("img[src]")) {
+      *src_counts.entry(img.value().attr("src")).or_insert(0) += 1;
+    }
+    let is_repeated = |element_ref: &ElementRef| {
+      let elem = element_ref.value();
+      elem.name() == "img" && src_counts.get(&elem.attr("src")).copied().unwrap_or(0) >= 3
+    };
+
+    // how far down the readme each image is, by the blocks that start before it
+    let mut block_count = 0;
+    let mut blocks_before = HashMap::new();
+    for elem in self
+      .document
+      .root_element()
+      .descendants()
+      .filter_map(ElementRef::wrap)
+    {
+      match elem.value().name() {
+        name if is_block(name) => block_count += 1,
+        "img" | "object" | "svg" => {
+          blocks_before.insert(elem.id(), block_count);
+        }
+        _ => {}
+      }
+    }
+
     let mut images = Vec::new();
-    for element_ref in self.document.select(selector!("img[src]")) {
-      if let Some(image) = ReadmeImage::get(self, &element_ref, primary_heading).await {
-        images.push(image);
+    let mut excluded = Vec::new();
+    let mut processed = 0;
+    for element_ref in self
+      .document
+      .select(selector!("img[src]", "object[data]", "svg"))
+      .filter(is_image_element)
+    {
+      if is_repeated(&element_ref) {
+        excluded.push(Candidate::excluded(
+          element_ref.value().attr("src").unwrap_or_default(),
+          RepoIconKind::ReadmeImage,
+          Exclusion::Repeated,
+        ));
+        continue;
+      }
+
+      if let Some(exclusion) = invisible_exclusion(&element_ref) {
+        excluded.push(Candidate::excluded(
+          element_ref.value().attr("src").unwrap_or_default(),
+          RepoIconKind::ReadmeImage,
+          exclusion,
+        ));
+        continue;
+      }
+
+      if processed == self.max_images.unwrap_or(usize::MAX) {
+        break;
+      }
+      let document_index = processed;
+      processed += 1;
+
+      // a bug processing one image shouldn't lose the rest of them
+      let image = AssertUnwindSafe(ReadmeImage::get_or_excluded(
+        self,
+        &element_ref,
+        primary_heading,
+        offline,
+      ))
+      .catch_unwind()
+      .await;
+
+      match image {
+        Ok(Ok(mut image)) => {
+          image.is_first_image = images.is_empty();
+          image.document_index = document_index;
+          // the image's own block doesn't count as before it
+          image.blocks_before = blocks_before
+            .get(&element_ref.id())
+            .map_or(0, |before: &usize| before.saturating_sub(1));
+          image.block_count = block_count;
+
+          // the edge of the primary heading is only known after the next
+          // image, so it isn't counted towards the confidence
+          let is_confident = self.stop_on_confident_logo.is_some_and(|min_weight| {
+            image.is_logo_candidate() && image.weight_with(&self.profile) >= min_weight
+          });
+
+          images.push(image);
+          if is_confident {
+            break;
+          }
+        }
+        Ok(Err(candidate)) => excluded.push(candidate),
+        Err(_) => {
+          let src = element_ref.value().attr("src").unwrap_or_default();
+          warn!("failed to process image {}", src);
+          excluded.push(Candidate::excluded(
+            src,
+            RepoIconKind::ReadmeImage,
+            Exclusion::Failed,
+          ));
+        }
       }
     }
 
@@ -129,17 +478,81 @@ impl Readme {
       };
     }
 
-    images.sort();
+    images.sort_by(|a, b| {
+      b.weight_with(&self.profile)
+        .cmp(&a.weight_with(&self.profile))
+        .then_with(|| a.tie_break(b))
+    });
 
     warn!(
       "{:#?}",
       images
         .iter()
-        .map(|img| (img.src.clone(), img.weight()))
+        .map(|img| (img.src.clone(), img.weight_with(&self.profile)))
         .collect::<Vec<_>>()
     );
 
-    images
+    (images, excluded)
+  }
+
+  /// The project's title from the primary heading, which can differ from the
+  /// repo's name, eg. "Next.js" for `vercel/next.js`. Only the first line of a
+  /// multi-line heading is used, and headings that are only an image use its alt
+  pub fn title(&self) -> Option<String> {
+    let heading = PrimaryHeading::new(&self.document).heading()?;
+
+    let mut text = String::new();
+    'nodes: for node in heading.descendants() {
+      match node.value() {
+        Node::Text(node) => {
+          let mut lines = node.split('\n').peekable();
+          while let Some(line) = lines.next() {
+            text.push_str(line);
+            if lines.peek().is_some() && !text.trim().is_empty() {
+              break 'nodes;
+            }
+          }
+        }
+        Node::Element(elem) if elem.name() == "br" && !text.trim().is_empty() => break,
+        _ => {}
+      }
+    }
+
+    clean_title(&text).or_else(|| {
+      heading
+        .select(selector!("img[alt]"))
+        .filter(|img| {
+          let src = img.value().attr("src").unwrap_or_default();
+          self.qualify_url(src).map_or(true, |url| !is_badge(&url))
+        })
+        .find_map(|img| clean_title(img.value().attr("alt")?))
+    })
+  }
+
+  /// The icon declared in the readme source with a
+  /// `<!-- repo-icon: path -->` comment or `<link rel="icon">`
+  pub async fn declared_icon(&self) -> Option<(Url, HashMap<String, String>)> {
+    let url = self.qualify_url(self.declared_icon.as_ref()?).ok()?;
+
+    Some(match self.get_branch_and_path(&url).await {
+      Some((branch, path)) => self.raw_file(&branch, &path),
+      None => (url, HashMap::new()),
+    })
+  }
+
+  /// The raw url of a file in the repo, with the headers needed if the repo is private
+  pub(crate) fn raw_file(&self, branch: &str, path: &str) -> (Url, HashMap<String, String>) {
+    let mut headers = HashMap::new();
+    if self.private {
+      if let Some(token) = get_token() {
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+      }
+    }
+
+    (
+      raw_file_url(&self.cased_owner, &self.cased_repo, branch, path),
+      headers,
+    )
   }
 
   /// Check if a given url is a project link.
@@ -148,18 +561,18 @@ impl Readme {
 
     // check for github pages
     let re = regex!(r"^([^.])+\.github\.(com|io)$");
-    if let Some(res) = re.captures(&domain).unwrap() {
+    if let Some(res) = re.captures(&domain).ok().flatten() {
       let user = &res[1];
 
       // USERNAME.github.io
-      if let Some(repo_res) = re.captures(&domain).unwrap() {
+      if let Some(repo_res) = re.captures(&domain).ok().flatten() {
         if &repo_res[1] == user {
           return Some(ProjectLink::Website);
         }
       }
 
       // USERNAME.github.io/REPO
-      if let Some(res) = regex!("^/([^/]+)").captures(url.path()).unwrap() {
+      if let Some(res) = regex!("^/([^/]+)").captures(url.path()).ok().flatten() {
         let repo = &res[1];
         if self.is_same_repo_as(user, repo).await {
           return Some(ProjectLink::Website);
@@ -167,11 +580,13 @@ impl Readme {
       }
     }
 
+    // a homepage of the repo's own github page (or github.com generally) would
+    // make every github link a website link, so those are checked as repo links
     if self
       .homepage
       .as_ref()
-      .and_then(|u| u.domain().map(|d| d.to_lowercase()))
-      .map(|d| domain == d)
+      .filter(|homepage| !is_github_url(homepage))
+      .map(|homepage| is_homepage_link(homepage, url))
       .unwrap_or(false)
     {
       return Some(ProjectLink::Website);
@@ -186,53 +601,477 @@ impl Readme {
 
   /// Check if a given url points to a file located inside the repo.
   pub async fn get_branch_and_path(&self, url: &Url) -> Option<(String, String)> {
-    let domain = if let Some(domain) = url.domain() {
-      domain.to_lowercase()
+    self.branch_and_path(url, false).await
+  }
+
+  pub(crate) async fn branch_and_path(&self, url: &Url, offline: bool) -> Option<(String, String)> {
+    let file = RepoFileUrl::parse(url)?;
+
+    let is_same_repo = if offline {
+      resolve_alias((&file.owner, &file.repo)) == resolve_alias((&self.owner, &self.repo))
     } else {
-      return None;
+      self.is_same_repo_as(&file.owner, &file.repo).await
     };
 
-    match &domain[..] {
-      "raw.githubusercontent.com" | "raw.github.com" | "github.com" => {
-        let re = if domain == "github.com" {
-          regex!("^/([^/]+)/([^/]+)/[^/]+/([^/]+)/(.+)")
-        } else {
-          regex!("^/([^/]+)/([^/]+)/([^/]+)/(.+)")
-        };
-
-        if let Some(res) = re.captures(url.path()).unwrap() {
-          let user = &res[1];
-          let repo = &res[2];
-
-          if self.is_same_repo_as(user, repo).await {
-            let branch = &res[3];
-            let path = &res[4];
-            return Some((branch.into(), path.into()));
-          };
-        }
-      }
-      _ => {}
+    is_same_repo.then_some((file.branch, file.path))
+  }
+
+  /// Resolve a src / href in the readme to an absolute url,
+  /// erroring for fragments and srcs that aren't web or data urls, or files of a repo on disk
+  pub fn qualify_url(&self, path: &str) -> Result<Url, Box<dyn Error>> {
+    let path = path.trim();
+    if path.is_empty() || path.starts_with('#') {
+      return Err(format!("{:?} isn't a link to a resource", path).into());
     }
 
-    None
+    let url = if path.starts_with("//") {
+      // protocol relative urls use the scheme of the link base
+      Url::parse(&format!("{}:{}", self.link_base.scheme(), path))?
+    } else if self.is_github_file_path(path) {
+      // github renders links to the repo's files as paths on github.com
+      Url::parse("https://github.com")?.join(path)?
+    } else if path.starts_with('/') {
+      // absolute paths are relative to the repo root
+      self.repo_base.join(&format!(".{}", path))?
+    } else {
+      self.join_relative(path)?
+    };
+
+    match url.scheme() {
+      "http" | "https" | "data" => Ok(url),
+      // only readmes on disk link to files, which have to be inside the repo
+      "file" if url.as_str().starts_with(self.repo_base.as_str()) => Ok(url),
+      scheme => Err(format!("unsupported url scheme {}", scheme).into()),
+    }
   }
 
-  pub fn qualify_url(&self, path: &str) -> Result<Url, Box<dyn Error>> {
-    let mut path = path.to_string();
-    if path.starts_with("/") {
-      path = format!(".{}", path);
+  /// Whether the path is a file of the repo on github.com,
+  /// eg. `/owner/repo/blob/main/logo.png?raw=true`
+  fn is_github_file_path(&self, path: &str) -> bool {
+    let mut segments = path.split('/');
+    segments.next() == Some("")
+      && segments.next().map(str::to_lowercase).as_ref() == Some(&self.owner)
+      && segments.next().map(str::to_lowercase).as_ref() == Some(&self.repo)
+      && matches!(segments.next(), Some("blob" | "raw" | "tree"))
+  }
+
+  /// Join a path relative to the readme's directory, with `../` clamped at the
+  /// repo root rather than escaping into the owner's other repos
+  fn join_relative(&self, path: &str) -> Result<Url, url::ParseError> {
+    let directory = self
+      .link_base
+      .path()
+      .strip_prefix(self.repo_base.path())
+      .unwrap_or_default();
+
+    // the url parser already clamps `..` at the root, so the path is resolved
+    // against a placeholder root before being joined to the repo root
+    let root = Url::parse("https://repo.invalid/")?;
+    let url = root.join(directory)?.join(path)?;
+    if url.origin() != root.origin() {
+      return Ok(url);
     }
 
-    Ok(self.link_base.join(&path)?)
+    self
+      .repo_base
+      .join(&format!(".{}", &url[url::Position::BeforePath..]))
   }
 
   async fn is_same_repo_as(&self, owner: &str, repo: &str) -> bool {
     let user = owner.to_lowercase();
     let repo = repo.to_lowercase();
-    is_same_repo((&self.owner, &self.repo), (&user, &repo)).await
+    is_same_repo(&self.options, (&self.owner, &self.repo), (&user, &repo)).await
   }
 }
 
+/// The raw.githubusercontent.com url of a file, with each segment percent-encoded
+pub(crate) fn raw_file_url(owner: &str, repo: &str, branch: &str, path: &str) -> Url {
+  let mut url = Url::parse("https://raw.githubusercontent.com/").unwrap();
+  url
+    .path_segments_mut()
+    .unwrap()
+    .pop_if_empty()
+    .extend(&[owner, repo, branch])
+    .extend(path.split('/'));
+  url
+}
+
+/// Check whether the readme changed since it had the `etag`, without downloading it
+pub(crate) async fn is_readme_modified(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+  etag: &str,
+) -> Result<bool, Box<dyn Error>> {
+  let res = send(
+    options,
+    gh_api_get!(
+      github_client(options),
+      "repos/{}/{}/readme{}",
+      owner,
+      repo,
+      ref_query(options)
+    )
+    // the etag is of the source, or the html when the source isn't fetched
+    .header("Accept", if options.icon_directive { RAW } else { HTML })
+    .header(IF_NONE_MATCH, etag),
+  )
+  .await?;
+
+  Ok(res.status() != StatusCode::NOT_MODIFIED)
+}
+
+const HTML: &str = "application/vnd.github.html";
+const RAW: &str = "application/vnd.github.raw";
+
+/// Fetch the readme in the given format and its ETag, a missing readme is empty
+async fn get_readme(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+  accept: &str,
+) -> Result<(String, Option<String>), Box<dyn Error>> {
+  // request headers take precedence over the client's default Accept
+  let res = send(
+    options,
+    gh_api_get!(
+      github_client(options),
+      "repos/{}/{}/readme{}",
+      owner,
+      repo,
+      ref_query(options)
+    )
+    .header("Accept", accept),
+  )
+  .await?;
+
+  // repos without a readme still have a homepage / avatar to use,
+  // if the repo itself doesn't exist the metadata request errors
+  if res.status() == StatusCode::NOT_FOUND {
+    return Ok((String::new(), None));
+  }
+
+  let etag = res
+    .headers()
+    .get(ETAG)
+    .and_then(|etag| etag.to_str().ok())
+    .map(|etag| etag.to_string());
+
+  Ok((decode_text(res.error_for_status()?).await?, etag))
+}
+
+/// Decode a readme, which github serves as utf-8 regardless of its actual encoding.
+/// A BOM takes precedence, then the `Content-Type` charset, and bodies
+/// that aren't valid utf-8 fall back to windows-1252 (a superset of latin-1)
+async fn decode_text(res: reqwest::Response) -> Result<String, reqwest::Error> {
+  let charset = res
+    .headers()
+    .get(reqwest::header::CONTENT_TYPE)
+    .and_then(|content_type| content_type.to_str().ok())
+    .and_then(|content_type| {
+      content_type.split(';').find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        (name.trim().eq_ignore_ascii_case("charset")).then(|| value.trim().trim_matches('"'))
+      })
+    })
+    .and_then(|charset| Encoding::for_label(charset.as_bytes()));
+  let bytes = res.bytes().await?;
+
+  let encoding = match Encoding::for_bom(&bytes) {
+    Some((encoding, _)) => encoding,
+    None => match charset {
+      Some(charset) if charset != UTF_8 => charset,
+      _ if std::str::from_utf8(&bytes).is_ok() => UTF_8,
+      _ => WINDOWS_1252,
+    },
+  };
+
+  // decoding strips the BOM
+  let (text, _, _) = encoding.decode(&bytes);
+  Ok(text.into_owned())
+}
+
+/// The `?ref=` of [`crate::RepoIconsBuilder::branch`] for the contents api,
+/// empty for the default branch
+pub(crate) fn ref_query(options: &RepoIconsBuilder) -> String {
+  match &options.branch {
+    Some(branch) => format!("?ref={}", utf8_percent_encode(branch, NON_ALPHANUMERIC)),
+    None => String::new(),
+  }
+}
+
+/// Error if the repo doesn't have the [`crate::RepoIconsBuilder::branch`],
+/// as the contents api treats a missing ref like a missing readme
+async fn check_branch(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+) -> Result<(), Box<dyn Error>> {
+  let branch = match &options.branch {
+    Some(branch) => branch,
+    None => return Ok(()),
+  };
+
+  let res = send(
+    options,
+    gh_api_get!(
+      github_client(options),
+      "repos/{}/{}/branches/{}",
+      owner,
+      repo,
+      branch
+    ),
+  )
+  .await?;
+
+  if res.status() == StatusCode::NOT_FOUND {
+    return Err(format!("branch {} doesn't exist in {}/{}", branch, owner, repo).into());
+  }
+  res.error_for_status()?;
+
+  Ok(())
+}
+
+/// Fetch a markup file in the repo, and render it the same way github renders readmes.
+/// Returns the source and the rendered html
+async fn render_file(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+  path: &str,
+) -> Result<(String, String), Box<dyn Error>> {
+  let get_file = |accept| async move {
+    let res = send(
+      options,
+      gh_api_get!(
+        github_client(options),
+        "repos/{}/{}/contents/{}{}",
+        owner,
+        repo,
+        path.trim_start_matches('/'),
+        ref_query(options)
+      )
+      .header("Accept", accept),
+    )
+    .await?
+    .error_for_status()?;
+    Ok::<_, Box<dyn Error>>(decode_text(res).await?)
+  };
+
+  // the markdown api only renders markdown, github renders other markup
+  // (eg. `.rst` / `.adoc`) with the file's html media type
+  if !is_markdown_path(path) {
+    let (source, html) = join(get_file(RAW), get_file(HTML)).await;
+    return Ok((source?, html?));
+  }
+
+  let markdown = get_file(RAW).await?;
+
+  let context = format!("{}/{}", owner, repo);
+  let html = render_markdown_api(options, Some(&context), &markdown).await;
+  let html = match html {
+    Ok(html) => html,
+    Err(err) => render_fallback(&markdown, err)?,
+  };
+
+  Ok((markdown, html))
+}
+
+/// Whether github renders a file as markdown. Files without an extension
+/// (eg. `README`) are plain text, which renders the same as markdown
+fn is_markdown_path(path: &str) -> bool {
+  let file_name = path.rsplit('/').next().unwrap_or(path).to_lowercase();
+  match file_name.rsplit_once('.') {
+    Some((_, extension)) => matches!(
+      extension,
+      "md" | "markdown" | "mdown" | "mkdn" | "mkd" | "mdwn" | "txt"
+    ),
+    None => true,
+  }
+}
+
+/// Render markdown with github, `context` is the repo that issue references
+/// (eg. `#123`) link to
+async fn render_markdown_api(
+  options: &RepoIconsBuilder,
+  context: Option<&str>,
+  markdown: &str,
+) -> Result<String, Box<dyn Error>> {
+  let mut body = serde_json::json!({
+    "text": markdown,
+    "mode": "gfm",
+  });
+  if let Some(context) = context {
+    body["context"] = context.into();
+  }
+
+  send(
+    options,
+    github_client(options)
+      .post("https://api.github.com/markdown")
+      .json(&body),
+  )
+  .await?
+  .error_for_status()?
+  .text()
+  .await
+  .map_err(Into::into)
+}
+
+/// When github can't render the html (eg. it's rate limited / erroring),
+/// render the markdown locally with the `markdown` feature
+fn render_fallback(markdown: &str, err: Box<dyn Error>) -> Result<String, Box<dyn Error>> {
+  #[cfg(feature = "markdown")]
+  {
+    warn!("rendering readme locally: {}", err);
+    Ok(render_markdown(markdown))
+  }
+
+  #[cfg(not(feature = "markdown"))]
+  {
+    let _ = markdown;
+    Err(err)
+  }
+}
+
+/// Render markdown the closest to github-flavoured markdown. Raw html is passed
+/// through as-is, so `align` attributes and `<picture>` elements are kept
+#[cfg(feature = "markdown")]
+pub(crate) fn render_markdown(markdown: &str) -> String {
+  use pulldown_cmark::{html, Options, Parser};
+
+  let mut options = Options::empty();
+  options.insert(Options::ENABLE_TABLES);
+  options.insert(Options::ENABLE_STRIKETHROUGH);
+  options.insert(Options::ENABLE_TASKLISTS);
+  options.insert(Options::ENABLE_FOOTNOTES);
+
+  let mut output = String::with_capacity(markdown.len() * 3 / 2);
+  html::push_html(&mut output, Parser::new_ext(markdown, options));
+  output
+}
+
+/// Find the icon a maintainer declared in the readme source, with either
+/// `<!-- repo-icon: assets/logo.svg -->` or `<link rel="icon" href="assets/logo.svg">`,
+/// or in the comments of other markup, `.. repo-icon: assets/logo.svg` (rST) and
+/// `// repo-icon: assets/logo.svg` (AsciiDoc)
+fn parse_icon_directive(source: &str) -> Option<String> {
+  let document = Html::parse_fragment(source);
+
+  let comment = document.tree.values().find_map(|node| {
+    let comment = node.as_comment()?;
+    let res = regex!(r"^\s*repo-icon:\s*(\S+)\s*$")
+      .captures(comment)
+      .ok()
+      .flatten()?;
+    Some(res[1].to_string())
+  });
+
+  comment
+    .or_else(|| {
+      document
+        .select(selector!("link[rel~='icon'][href]"))
+        .find_map(|link| link.value().attr("href"))
+        .map(|href| href.to_string())
+    })
+    .or_else(|| {
+      let res = regex!(r"(?m)^\s*(?:\.\.|//)\s*repo-icon:\s*(\S+)\s*$")
+        .captures(source)
+        .ok()
+        .flatten()?;
+      Some(res[1].to_string())
+    })
+}
+
+/// Collapse the whitespace of a title, and trim the separators left
+/// from badges & taglines, eg. "Next.js -"
+fn clean_title(text: &str) -> Option<String> {
+  let title = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  let title = title.trim_matches(|c: char| c.is_whitespace() || "-–—|:·".contains(c));
+  (!title.is_empty()).then(|| title.to_string())
+}
+
+fn is_github_url(url: &Url) -> bool {
+  matches!(
+    url.domain().map(|domain| domain.to_lowercase()).as_deref(),
+    Some("github.com" | "www.github.com")
+  )
+}
+
+/// Check if a url points to the homepage (or a page beneath it).
+/// When the homepage is the root of a subdomain, links to the root of its
+/// site also count, eg. `example.com` for a homepage of `docs.example.com`.
+/// Shared hosts aren't the site of their subdomains (eg. `github.io`)
+fn is_homepage_link(homepage: &Url, url: &Url) -> bool {
+  let normalize_host = |url: &Url| {
+    url.domain().map(|domain| {
+      let domain = domain.to_lowercase();
+      domain
+        .strip_prefix("www.")
+        .map(|domain| domain.to_string())
+        .unwrap_or(domain)
+    })
+  };
+
+  let (homepage_host, host) = match (normalize_host(homepage), normalize_host(url)) {
+    (Some(homepage_host), Some(host)) => (homepage_host, host),
+    _ => return false,
+  };
+
+  let homepage_path = homepage.path().trim_end_matches('/').to_lowercase();
+  let path = url.path().trim_end_matches('/').to_lowercase();
+
+  let is_root_site = homepage_host == host
+    || (homepage_host.ends_with(&format!(".{}", host)) && !is_shared_host(&host));
+  if path.is_empty() && homepage_path.is_empty() && is_root_site {
+    return true;
+  }
+
+  homepage_host == host
+    && (path == homepage_path || path.starts_with(&format!("{}/", homepage_path)))
+}
+
+fn is_image_element(element_ref: &ElementRef) -> bool {
+  let elem = element_ref.value();
+
+  match elem.name() {
+    // objects can embed any document
+    "object" => elem
+      .attr("type")
+      .map(|mime| mime.starts_with("image/"))
+      .unwrap_or(true),
+    // only the outermost svg is used
+    "svg" => !element_ref
+      .ancestors()
+      .filter_map(ElementRef::wrap)
+      .any(|ancestor| ancestor.value().name() == "svg"),
+    _ => true,
+  }
+}
+
+/// Why an image isn't a visible logo, if it's hidden (eg. a fallback or
+/// print-only image) or marked decorative with `aria-hidden`
+fn invisible_exclusion(element_ref: &ElementRef) -> Option<Exclusion> {
+  let is_hidden = |elem: &Element| {
+    matches!(elem.name(), "template" | "noscript")
+      || elem.attr("hidden").is_some()
+      || elem.attr("style").is_some_and(|style| {
+        regex!(r"(?i)display\s*:\s*none")
+          .is_match(style)
+          .unwrap_or(false)
+      })
+  };
+
+  if iter::once(*element_ref)
+    .chain(element_ref.ancestors().filter_map(ElementRef::wrap))
+    .any(|elem| is_hidden(elem.value()))
+  {
+    return Some(Exclusion::Hidden);
+  }
+
+  (element_ref.value().attr("aria-hidden") == Some("true")).then_some(Exclusion::Decorative)
+}
+
 fn deserialize_url<'de, D: de::Deserializer<'de>>(d: D) -> Result<Option<Url>, D::Error> {
   Deserialize::deserialize(d).map(|url: Option<&str>| {
     url.and_then(|url| {
@@ -245,3 +1084,52 @@ fn deserialize_url<'de, D: de::Deserializer<'de>>(d: D) -> Result<Option<Url>, D
     })
   })
 }
+
+/// A file of a github repo, from any of the urls github serves it at
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct RepoFileUrl {
+  /// lowercased, as github's owner & repo names are case insensitive
+  pub owner: String,
+  pub repo: String,
+  pub branch: String,
+  pub path: String,
+}
+
+impl RepoFileUrl {
+  /// Parse `github.com/OWNER/REPO/(blob|tree|raw)/BRANCH/PATH` (with or without
+  /// `?raw=true`) and `raw.githubusercontent.com/OWNER/REPO/BRANCH/PATH` urls,
+  /// so the same file is equal regardless of how it's linked
+  pub fn parse(url: &Url) -> Option<Self> {
+    let domain = url.domain()?.to_lowercase();
+
+    let re = match &domain[..] {
+      // the query (eg. ?raw=true) isn't part of the path
+      "github.com" => regex!("^/([^/]+)/([^/]+)/(?:blob|tree|raw)/([^/]+)/(.+)"),
+      "raw.githubusercontent.com" | "raw.github.com" => regex!("^/([^/]+)/([^/]+)/([^/]+)/(.+)"),
+      _ => return None,
+    };
+
+    let res = re.captures(url.path()).ok().flatten()?;
+    let decode = |text| percent_decode_str(text).decode_utf8_lossy().to_string();
+
+    let mut branch = decode(&res[3]);
+    let mut path = decode(&res[4]);
+    // raw.githubusercontent.com/OWNER/REPO/refs/heads/BRANCH/PATH
+    if branch == "refs" {
+      if let Some((kind, rest)) = path.split_once('/') {
+        if let (true, Some((name, rest))) = (matches!(kind, "heads" | "tags"), rest.split_once('/'))
+        {
+          branch = name.to_string();
+          path = rest.to_string();
+        }
+      }
+    }
+
+    Some(RepoFileUrl {
+      owner: res[1].to_lowercase(),
+      repo: res[2].to_lowercase(),
+      branch,
+      path: path.trim_end_matches('/').to_string(),
+    })
+  }
+}
@@ -26,6 +26,13 @@ impl<'a> PrimaryHeading<'a> {
     }
   }
 
+  /// The primary heading, unless the readme's first heading is a `<hr>`
+  pub fn heading(&self) -> Option<ElementRef<'a>> {
+    self
+      .primary_heading
+      .filter(|heading| heading.value().name() != "hr")
+  }
+
   pub fn contains(&mut self, element: &ElementRef) -> bool {
     // ugly hack to extract id https://github.com/causal-agent/ego-tree/pull/22
     let get_id = |node: &ElementRef| {
@@ -1,11 +1,22 @@
-use super::{primary_heading::PrimaryHeading, Readme};
-use crate::blacklist::is_badge;
-use gh_api::get_token;
-use scraper::ElementRef;
+use super::{primary_heading::PrimaryHeading, Readme, RepoFileUrl, ScoringProfile, Signal};
+use crate::{
+  blacklist::{is_badge, is_decorative},
+  client::{client, send},
+  Aspect, Candidate, ColorScheme, Exclusion, IconFormat, RepoIconKind, RepoIconsBuilder,
+};
+use futures::{stream, StreamExt};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::{
+  header::{HeaderMap, RANGE},
+  StatusCode,
+};
+use scraper::{node::Element, ElementRef};
 use serde::{Deserialize, Serialize};
 use std::{
   cmp::Ordering,
   collections::{HashMap, HashSet},
+  convert::TryInto,
+  iter,
 };
 use url::Url;
 
@@ -21,11 +32,39 @@ pub enum KeywordMention {
   Logo,
   Banner,
   RepoName,
+  Topic,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PictureSource {
+  pub src: Url,
+  pub color_scheme: ColorScheme,
+  /// the format declared by the `type`, eg. `image/webp`
+  pub format: Option<IconFormat>,
+}
+
+/// An image paired with its dark variant from the surrounding `<picture>`
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ThemedIcon {
+  /// the light variant, or the only variant if the image isn't themed
+  pub light: Url,
+  pub dark: Option<Url>,
+}
+
+impl ThemedIcon {
+  /// All the variants, for consumers that don't care about the scheme
+  pub fn urls(&self) -> Vec<&Url> {
+    let mut urls = vec![&self.light];
+    urls.extend(&self.dark);
+    urls
+  }
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct ReadmeImage {
   pub src: Url,
+  /// the original url of images proxied through github's camo
+  pub canonical_src: Option<Url>,
   pub headers: HashMap<String, String>,
   /// whether the image was in the primary markdown heading
   pub in_primary_heading: bool,
@@ -37,10 +76,38 @@ pub struct ReadmeImage {
   pub sourced_from_repo: bool,
   /// whether the image has links to the projects
   pub links_to: Option<ProjectLink>,
+  /// the resolved href of the link to the project
+  pub link_href: Option<Url>,
+  /// the alt text of the image
+  pub alt: Option<String>,
+  /// whether the image is the first one in the readme
+  pub is_first_image: bool,
+  /// the index of the image among the readme's images, in document order
+  pub document_index: usize,
   /// whether the image has the CSS "align: center"
   pub is_align_center: bool,
   /// whether the image has height or width attributes
   pub has_size_attrs: bool,
+  /// the aspect ratio of the height and width attributes
+  pub aspect: Option<Aspect>,
+  /// the color scheme declared by the surrounding `<picture>`
+  pub color_scheme: ColorScheme,
+  /// the `<source>` variants of the surrounding `<picture>`
+  pub picture_sources: Vec<PictureSource>,
+  /// whether the image is in a `<details>` that's collapsed by default
+  pub in_collapsed_details: bool,
+  /// whether the text next to the image resembles the repo's description
+  pub near_description: bool,
+  /// the number of block elements (paragraphs, headings, lists...) before the image
+  pub blocks_before: usize,
+  /// the number of block elements in the readme
+  pub block_count: usize,
+  /// whether the image is one of a row of similarly sized images, eg. the
+  /// logos of the technologies the project is "built with"
+  pub in_tech_stack_row: bool,
+  /// whether the image is from a readme in `docs/` or `.github/`, see
+  /// [`crate::RepoIconsBuilder::scan_all_readmes`]
+  pub in_secondary_readme: bool,
 }
 
 impl ReadmeImage {
@@ -48,45 +115,155 @@ impl ReadmeImage {
     readme: &Readme,
     elem_ref: &ElementRef<'_>,
     primary_heading: &mut PrimaryHeading<'_>,
+    offline: bool,
   ) -> Option<Self> {
+    Self::get_or_excluded(readme, elem_ref, primary_heading, offline)
+      .await
+      .ok()
+  }
+
+  /// Like [`ReadmeImage::get`], but with the reason the image was excluded
+  pub(crate) async fn get_or_excluded(
+    readme: &Readme,
+    elem_ref: &ElementRef<'_>,
+    primary_heading: &mut PrimaryHeading<'_>,
+    offline: bool,
+  ) -> Result<Self, Candidate> {
     let elem = elem_ref.value();
 
-    let src = elem
+    let src = match elem.name() {
+      // inline svgs are turned into a data uri
+      "svg" => encode_svg(&elem_ref.html()),
+      "object" => elem.attr("data").unwrap_or_default().to_string(),
+      _ => elem
+        .attr("data-canonical-src")
+        .or(elem.attr("src"))
+        .unwrap_or_default()
+        .to_string(),
+    };
+    let src = match readme.qualify_url(&src) {
+      Ok(src) => src,
+      Err(err) => {
+        warn!("skipping image {}", err);
+        return Err(Candidate::excluded(
+          src,
+          RepoIconKind::ReadmeImage,
+          Exclusion::InvalidUrl,
+        ));
+      }
+    };
+
+    let mut cdn_src = elem
       .attr("data-canonical-src")
-      .or(elem.attr("src"))
-      .and_then(|src| readme.qualify_url(src).ok())
-      .unwrap();
+      .and(elem.attr("src"))
+      .and_then(|src| readme.qualify_url(src).ok());
+
+    // camo urls without a data-canonical-src still contain the original url
+    let src = match cdn_src.is_none().then(|| decode_camo(&src)).flatten() {
+      Some(canonical_src) => {
+        cdn_src = Some(src);
+        canonical_src
+      }
+      None => src,
+    };
 
     if is_badge(&src) {
-      return None;
+      return Err(Candidate::excluded(
+        src,
+        RepoIconKind::ReadmeImage,
+        Exclusion::Badge,
+      ));
+    }
+    if is_decorative(&src) {
+      return Err(Candidate::excluded(
+        src,
+        RepoIconKind::ReadmeImage,
+        Exclusion::Decorative,
+      ));
     }
 
-    let cdn_src = elem
-      .attr("data-canonical-src")
-      .and(elem.attr("src"))
-      .and_then(|src| readme.qualify_url(src).ok());
+    let canonical_src = cdn_src.as_ref().map(|_| src.clone());
+    if readme.resolve_camo {
+      cdn_src = None;
+    }
+
+    let picture_sources = elem_ref
+      .parent()
+      .and_then(ElementRef::wrap)
+      .filter(|parent| parent.value().name() == "picture")
+      .map(|picture| {
+        picture
+          .children()
+          .filter_map(ElementRef::wrap)
+          .filter(|child| child.value().name() == "source")
+          .filter_map(|source| {
+            let source = source.value();
+            let srcset = source.attr("srcset")?;
+            // only the first candidate of the srcset is used. The url ends at
+            // whitespace rather than a comma, as data uris contain commas
+            let src = srcset.split_whitespace().next()?.trim_end_matches(',');
+
+            Some(PictureSource {
+              src: readme.qualify_url(src).ok()?,
+              color_scheme: ColorScheme::from_media(source.attr("media").unwrap_or("")),
+              format: source.attr("type").and_then(IconFormat::from_mime),
+            })
+          })
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    let color_scheme = picture_sources
+      .iter()
+      .find(|source| source.src == src)
+      .map(|source| source.color_scheme)
+      .unwrap_or_else(|| {
+        let has_scheme = |scheme| {
+          picture_sources
+            .iter()
+            .any(|source| source.color_scheme == scheme)
+        };
 
-    let mut is_align_center = false;
+        // the <img> fallback is the light variant when only a dark <source> is given
+        if has_scheme(ColorScheme::Dark) && !has_scheme(ColorScheme::Light) {
+          ColorScheme::Light
+        } else {
+          ColorScheme::Any
+        }
+      });
+
+    let branch_and_path = readme.branch_and_path(&src, offline).await;
+
+    let mut is_align_center = has_center_class(elem);
     let mut links_to = None;
+    let mut link_href = None;
     for elem_ref in elem_ref.ancestors().map(ElementRef::wrap).flatten() {
       let element = elem_ref.value();
 
-      if element.attr("align") == Some("center") {
+      if element.attr("align") == Some("center") || has_center_class(element) {
         is_align_center = true;
       }
 
-      if element.name() == "a" && links_to.is_none() {
+      if element.name() == "a" && links_to.is_none() && !offline {
         links_to = match element
           .attr("href")
+          .filter(|href| !is_anchor_link(href))
           .and_then(|href| readme.qualify_url(href).ok())
         {
           Some(href) => {
-            // if the img points to the same url as the link
-            // then its a default url generated by github
-            let mut img_blob_url = src.clone();
-            img_blob_url.set_path(&src.path().replacen("/raw/", "/blob/", 1));
-            if href != img_blob_url {
-              readme.is_link_to_project(&href).await
+            // if the link points to the same file as the img then its a default
+            // link generated by github, eg. a raw src linked to its blob page
+            let src_file = RepoFileUrl::parse(&src);
+            let is_image_link = href == src
+              || (src_file.is_some() && RepoFileUrl::parse(&href) == src_file)
+              || (branch_and_path.is_some()
+                && readme.branch_and_path(&href, offline).await == branch_and_path);
+            if !is_image_link {
+              let links_to = readme.is_link_to_project(&href).await;
+              if links_to.is_some() {
+                link_href = Some(href);
+              }
+              links_to
             } else {
               None
             }
@@ -96,7 +273,6 @@ impl ReadmeImage {
       }
     }
 
-    let branch_and_path = readme.get_branch_and_path(&src).await;
     let keyword_mentions = {
       let mut mentions = HashSet::new();
 
@@ -121,95 +297,512 @@ impl ReadmeImage {
       if path.contains(&readme.repo) || alt.contains(&readme.repo) {
         mentions.insert(KeywordMention::RepoName);
       };
+
+      // topics are often the project name without separators, eg. nextjs
+      let strip_separators = |text: &str| text.replace(&['-', '_', '.', ' '][..], "");
+      let (stripped_path, stripped_alt) = (strip_separators(path), strip_separators(&alt));
+      if readme.topics.iter().any(|topic| {
+        topic != &readme.repo
+          && (stripped_path.contains(topic.as_str()) || stripped_alt.contains(topic.as_str()))
+      }) {
+        mentions.insert(KeywordMention::Topic);
+      }
       mentions
     };
 
-    let mut headers = HashMap::new();
-
-    let src = cdn_src.unwrap_or({
-      if let Some((branch, path)) = &branch_and_path {
-        if readme.private {
-          headers.insert(
-            "Authorization".to_string(),
-            format!("Bearer {}", get_token().unwrap()).to_string(),
-          );
-        }
-
-        Url::parse(&format!(
-          "https://raw.githubusercontent.com/{}/{}/{}/{}",
-          readme.owner, readme.repo, branch, path
-        ))
-        .unwrap()
-      } else {
-        src
-      }
-    });
+    let (src, headers) = match (cdn_src, &branch_and_path) {
+      (Some(cdn_src), _) => (cdn_src, HashMap::new()),
+      (None, Some((branch, path))) => readme.raw_file(branch, path),
+      (None, None) => (src, HashMap::new()),
+    };
 
-    Some(ReadmeImage {
+    Ok(ReadmeImage {
       src,
+      canonical_src,
       headers,
       in_primary_heading: primary_heading.contains(elem_ref),
       edge_of_primary_heading: false,
       keyword_mentions,
       sourced_from_repo: branch_and_path.is_some(),
       links_to,
+      link_href,
+      alt: elem.attr("alt").map(|alt| alt.to_string()),
+      is_first_image: false,
+      document_index: 0,
+      blocks_before: 0,
+      block_count: 0,
+      in_secondary_readme: false,
       is_align_center,
       has_size_attrs: elem.attr("width").or(elem.attr("height")).is_some(),
+      aspect: {
+        let size_attr = |name| {
+          elem
+            .attr(name)
+            .and_then(|value: &str| value.trim_end_matches("px").parse::<u32>().ok())
+        };
+
+        size_attr("width")
+          .zip(size_attr("height"))
+          .and_then(|(width, height)| Aspect::from_size(width, height))
+      },
+      color_scheme,
+      picture_sources,
+      in_collapsed_details: in_collapsed_details(elem_ref),
+      in_tech_stack_row: in_tech_stack_row(readme, elem_ref),
+      near_description: readme
+        .description
+        .as_deref()
+        .is_some_and(|description| is_near_description(elem_ref, description)),
     })
   }
 
+  /// Check the image resolves, using a HEAD request with the image's headers.
+  /// Hosts that refuse HEAD requests are checked with a GET of the first byte
+  pub async fn is_live(&self) -> bool {
+    self.is_live_with(&RepoIconsBuilder::new()).await
+  }
+
+  pub async fn is_live_with(&self, options: &RepoIconsBuilder) -> bool {
+    if self.src.scheme() == "data" {
+      return true;
+    }
+
+    let headers: HeaderMap = match (&self.headers).try_into() {
+      Ok(headers) => headers,
+      Err(_) => return false,
+    };
+
+    let res = match send(
+      options,
+      client(options)
+        .head(self.src.clone())
+        .headers(headers.clone()),
+    )
+    .await
+    {
+      Ok(res) => res,
+      Err(_) => return false,
+    };
+
+    // some hosts (eg. S3 presigned urls) only allow GET
+    let res = match res.status() {
+      StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED | StatusCode::FORBIDDEN => {
+        match send(
+          options,
+          client(options)
+            .get(self.src.clone())
+            .headers(headers)
+            .header(RANGE, "bytes=0-0"),
+        )
+        .await
+        {
+          Ok(res) => res,
+          Err(_) => return false,
+        }
+      }
+      _ => res,
+    };
+
+    !res.status().is_client_error() && !res.status().is_server_error()
+  }
+
+  /// Drop the images that don't resolve, checking `concurrency` at a time
+  pub async fn retain_live(
+    options: &RepoIconsBuilder,
+    images: Vec<ReadmeImage>,
+    concurrency: usize,
+  ) -> Vec<ReadmeImage> {
+    stream::iter(images)
+      .map(async move |image| {
+        let is_live = image.is_live_with(options).await;
+        if !is_live {
+          warn!("dropping unresolvable image {}", image.src);
+        }
+        (image, is_live)
+      })
+      .buffered(concurrency)
+      .filter_map(async move |(image, is_live)| if is_live { Some(image) } else { None })
+      .collect()
+      .await
+  }
+
+  /// Pair the image with its light / dark `<source>` variants
+  pub fn themed(&self) -> ThemedIcon {
+    let variant = |scheme| {
+      if self.color_scheme == scheme {
+        return Some(&self.src);
+      }
+      self
+        .picture_sources
+        .iter()
+        .find(|source| source.color_scheme == scheme)
+        .map(|source| &source.src)
+    };
+
+    let dark = variant(ColorScheme::Dark);
+    let light = variant(ColorScheme::Light)
+      .or_else(|| variant(ColorScheme::Any))
+      .filter(|light| Some(*light) != dark)
+      .unwrap_or(&self.src);
+
+    ThemedIcon {
+      light: light.clone(),
+      dark: dark.filter(|dark| *dark != light).cloned(),
+    }
+  }
+
+  /// The src in the first of the `formats` the surrounding `<picture>` has a
+  /// `<source type>` for, eg. `[IconFormat::Avif, IconFormat::Webp]` for a
+  /// client that supports modern formats. Only the sources for the image's
+  /// color scheme are considered, and without a match it's the `<img>` src
+  pub fn preferred_src(&self, formats: &[IconFormat]) -> &Url {
+    formats
+      .iter()
+      .find_map(|format| {
+        self.picture_sources.iter().find(|source| {
+          source.format == Some(*format)
+            && (source.color_scheme == self.color_scheme || source.color_scheme == ColorScheme::Any)
+        })
+      })
+      .map_or(&self.src, |source| &source.src)
+  }
+
+  /// Whether the image is positioned like a logo, only these are used as icons
+  pub fn is_logo_candidate(&self) -> bool {
+    self.in_primary_heading || self.signals().contains(&Signal::CenteredFirstImage)
+  }
+
+  /// The order of images with the same weight, so rankings are reproducible
+  pub(crate) fn tie_break(&self, other: &Self) -> Ordering {
+    self
+      .document_index
+      .cmp(&other.document_index)
+      .then_with(|| self.src.as_str().cmp(other.src.as_str()))
+  }
+
   pub fn weight(&self) -> u8 {
-    let mut weight = 0;
+    self.weight_with(&ScoringProfile::default())
+  }
+
+  pub fn weight_with(&self, profile: &ScoringProfile) -> u8 {
+    let weight: i16 = self
+      .score_breakdown_with(profile)
+      .into_iter()
+      .map(|(_, contribution)| contribution)
+      .sum();
+
+    weight.max(0).min(u8::MAX as i16) as u8
+  }
+
+  pub fn score_breakdown(&self) -> Vec<(Signal, i16)> {
+    self.score_breakdown_with(&ScoringProfile::default())
+  }
+
+  /// Each signal with the points it adds to (or subtracts from) the weight,
+  /// before the weight is clamped to `0..=255`
+  pub fn score_breakdown_with(&self, profile: &ScoringProfile) -> Vec<(Signal, i16)> {
+    self
+      .signals()
+      .into_iter()
+      .map(|signal| match signal {
+        Signal::DocumentPosition => (
+          signal,
+          profile.document_position_bonus(self.document_position()),
+        ),
+        _ => (signal, profile.contribution(signal)),
+      })
+      .collect()
+  }
+
+  /// The signals that contribute to the weight of the image
+  pub fn signals(&self) -> Vec<Signal> {
+    let mut signals = Vec::new();
 
     if self.in_primary_heading {
-      weight += 2;
+      signals.push(Signal::InPrimaryHeading);
 
       if self.is_align_center {
-        weight += 2;
+        signals.push(Signal::AlignCenter);
       }
 
       if self.has_size_attrs {
-        weight += 2;
+        signals.push(Signal::SizeAttrs);
       }
 
       if self.sourced_from_repo {
-        weight += 4;
+        signals.push(Signal::SourcedFromRepo);
       }
     };
 
     if self.edge_of_primary_heading {
-      weight += 4;
+      signals.push(Signal::EdgeOfPrimaryHeading);
+    }
+
+    // centered logos often precede the heading, which heading detection can miss
+    if self.is_first_image
+      && self.is_align_center
+      && (self.has_size_attrs || self.sourced_from_repo)
+    {
+      signals.push(Signal::CenteredFirstImage);
     }
 
     match self.links_to {
-      Some(ProjectLink::Website) => {
-        weight += 8;
-      }
-      Some(ProjectLink::Repo) => {
-        weight += 4;
-      }
+      Some(ProjectLink::Website) => signals.push(Signal::LinksToWebsite),
+      Some(ProjectLink::Repo) => signals.push(Signal::LinksToRepo),
       None => {}
     }
 
     if self.keyword_mentions.contains(&KeywordMention::Logo) {
-      weight += 16
+      signals.push(Signal::LogoMention);
     }
 
     if self.keyword_mentions.contains(&KeywordMention::Banner) {
-      weight += 8
+      signals.push(Signal::BannerMention);
     }
 
     if self.keyword_mentions.contains(&KeywordMention::RepoName) {
-      weight += 4
+      signals.push(Signal::RepoNameMention);
+    }
+
+    if self.keyword_mentions.contains(&KeywordMention::Topic) {
+      signals.push(Signal::TopicMention);
+    }
+
+    match self.aspect {
+      Some(Aspect::Square) => signals.push(Signal::SquareAspect),
+      Some(Aspect::Ultrawide) => signals.push(Signal::UltrawideAspect),
+      _ => {}
+    }
+
+    if self.near_description {
+      signals.push(Signal::NearDescription);
+    }
+
+    if self.in_collapsed_details {
+      signals.push(Signal::InCollapsedDetails);
+    }
+
+    if self.in_tech_stack_row {
+      signals.push(Signal::TechStackRow);
+    }
+
+    if self.in_secondary_readme {
+      signals.push(Signal::InSecondaryReadme);
+    }
+
+    // its bonus decays further down, see `ScoringProfile::document_position_bonus`
+    if self.document_position() <= 0.25 {
+      signals.push(Signal::DocumentPosition);
+    }
+
+    signals
+  }
+
+  /// How far down the readme the image is, by the blocks before it,
+  /// from 0 at the very top to 1 at the bottom
+  pub fn document_position(&self) -> f32 {
+    if self.block_count == 0 {
+      return 0.0;
     }
 
-    weight
+    self.blocks_before.min(self.block_count) as f32 / self.block_count as f32
   }
 }
 
+/// The classes rST (`:align: center`) & AsciiDoc (`[.text-center]`) center
+/// images with, as their readmes can't use `align="center"`
+fn has_center_class(element: &Element) -> bool {
+  element
+    .classes()
+    .any(|class| matches!(class, "align-center" | "text-center"))
+}
+
+/// Whether the element is in a `<details>` without `open`. The `<summary>`
+/// is still shown when it's collapsed
+fn in_collapsed_details(elem_ref: &ElementRef) -> bool {
+  let mut child = elem_ref.value().name();
+
+  for ancestor in elem_ref.ancestors().filter_map(ElementRef::wrap) {
+    let elem = ancestor.value();
+    if elem.name() == "details" && elem.attr("open").is_none() && child != "summary" {
+      return true;
+    }
+    child = elem.name();
+  }
+
+  false
+}
+
+/// Whether the image is in a block with at least 2 other (non-badge) images
+/// of a similar size, or with all of them linked when none are sized, like a
+/// row of "built with" logos linking to each technology's site
+fn in_tech_stack_row(readme: &Readme, elem_ref: &ElementRef) -> bool {
+  let block = match elem_ref
+    .ancestors()
+    .filter_map(ElementRef::wrap)
+    .find(|elem| is_block(elem.value().name()))
+  {
+    Some(block) => block,
+    None => return false,
+  };
+
+  let images = block
+    .select(selector!("img[src]"))
+    .filter(|img| {
+      readme
+        .qualify_url(img.value().attr("src").unwrap())
+        .is_ok_and(|src| !is_badge(&src))
+    })
+    .collect::<Vec<_>>();
+  if images.len() < 3 || !images.contains(elem_ref) {
+    return false;
+  }
+
+  let sizes = images
+    .iter()
+    .map(|img| {
+      let img = img.value();
+      img
+        .attr("height")
+        .or(img.attr("width"))
+        .and_then(|value| value.trim_end_matches("px").parse::<u32>().ok())
+    })
+    .collect::<Option<Vec<_>>>();
+
+  match sizes {
+    // within a quarter of each other
+    Some(sizes) => {
+      let min = sizes.iter().copied().min().unwrap_or(0);
+      let max = sizes.iter().copied().max().unwrap_or(0);
+      min > 0 && max * 4 <= min * 5
+    }
+    None => images.iter().all(|img| {
+      img
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .take_while(|elem| *elem != block)
+        .any(|elem| elem.value().name() == "a")
+    }),
+  }
+}
+
+pub(crate) fn is_block(name: &str) -> bool {
+  matches!(
+    name,
+    "p"
+      | "div"
+      | "h1"
+      | "h2"
+      | "h3"
+      | "h4"
+      | "h5"
+      | "h6"
+      | "ul"
+      | "ol"
+      | "table"
+      | "blockquote"
+      | "pre"
+      | "details"
+      | "hr"
+  )
+}
+
+/// Whether the text of the image's block, or the blocks either side of it,
+/// reads like the repo's description, eg. a tagline under a centered logo
+fn is_near_description(elem_ref: &ElementRef, description: &str) -> bool {
+  let description = words(description);
+  if description.is_empty() {
+    return false;
+  }
+
+  let block = match elem_ref
+    .ancestors()
+    .filter_map(ElementRef::wrap)
+    .find(|elem| {
+      matches!(
+        elem.value().name(),
+        "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "td" | "li" | "blockquote"
+      )
+    }) {
+    Some(block) => block,
+    None => return false,
+  };
+
+  let mut nearby = iter::once(block)
+    .chain(block.prev_siblings().find_map(ElementRef::wrap))
+    .chain(block.next_siblings().find_map(ElementRef::wrap));
+
+  nearby.any(|elem| {
+    let text = words(&elem.text().collect::<Vec<_>>().join(" "));
+    // most of the words, as taglines are often a shortened description
+    let shared = description
+      .iter()
+      .filter(|word| text.contains(*word))
+      .count();
+    shared * 3 >= description.len() * 2
+  })
+}
+
+/// The lowercased words of a text, without short words like "a" / "of"
+fn words(text: &str) -> HashSet<String> {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|word| word.chars().count() > 2)
+    .map(str::to_lowercase)
+    .collect()
+}
+
+/// Whether a href jumps within the readme, rather than navigating anywhere,
+/// eg. github's generated `#user-content-` heading anchors
+fn is_anchor_link(href: &str) -> bool {
+  let href = href.trim();
+  href.starts_with('#')
+    || href
+      .split_once('#')
+      .map(|(_, fragment)| fragment.starts_with("user-content-"))
+      .unwrap_or(false)
+}
+
+/// Decode the original url from a camo url,
+/// `camo.githubusercontent.com/<digest>/<hex encoded url>`
+fn decode_camo(url: &Url) -> Option<Url> {
+  if url.domain()? != "camo.githubusercontent.com" {
+    return None;
+  }
+
+  if let Some((_, url)) = url.query_pairs().find(|(key, _)| key == "url") {
+    return Url::parse(&url).ok();
+  }
+
+  let hex = url.path_segments()?.nth(1)?;
+  let bytes = (0..hex.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+    .collect::<Option<Vec<_>>>()?;
+
+  Url::parse(&String::from_utf8(bytes).ok()?).ok()
+}
+
+pub(crate) fn encode_svg(svg: &str) -> String {
+  let svg = if svg.contains("http://www.w3.org/2000/svg") {
+    svg.to_string()
+  } else {
+    svg.replacen("<svg", "<svg xmlns=\"http://www.w3.org/2000/svg\"", 1)
+  };
+
+  format!(
+    "data:image/svg+xml,{}",
+    utf8_percent_encode(&svg, NON_ALPHANUMERIC)
+  )
+}
+
+/// Heaviest first, ties are broken by document order then the src
 impl Ord for ReadmeImage {
   fn cmp(&self, other: &Self) -> Ordering {
-    other.weight().cmp(&self.weight())
+    other
+      .weight()
+      .cmp(&self.weight())
+      .then_with(|| self.tie_break(other))
   }
 }
 
@@ -1,5 +1,45 @@
+use crate::{
+  client::{github_client_builder, send},
+  RepoIconsBuilder,
+};
 use cached::proc_macro::cached;
+#[cfg(feature = "cache")]
+use cached::Cached;
+use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::{collections::HashMap, sync::RwLock};
+
+type OwnerRepo = (String, String);
+
+/// lowercased aliases, with the repo they're an alias of
+static REPO_ALIASES: Lazy<RwLock<HashMap<OwnerRepo, OwnerRepo>>> =
+  Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Treat a repo as the same repo as another, eg. a mirror & its upstream, so
+/// the readme's links to either repo are links to the project & files of either
+/// are sourced from the repo. Checked before following github's redirects
+///
+/// ```
+/// # use repo_icons::add_repo_alias;
+/// add_repo_alias(("acme", "widget"), ("widget-org", "widget"));
+/// ```
+pub fn add_repo_alias(alias: (&str, &str), repo: (&str, &str)) {
+  let lowercase = |(owner, repo): (&str, &str)| (owner.to_lowercase(), repo.to_lowercase());
+  REPO_ALIASES
+    .write()
+    .unwrap()
+    .insert(lowercase(alias), lowercase(repo));
+}
+
+/// The repo an alias is of, or the repo itself
+/// user/repo pairs should be transformed to lowercase!
+pub(crate) fn resolve_alias(repo: (&str, &str)) -> OwnerRepo {
+  let repo = (repo.0.to_string(), repo.1.to_string());
+  match REPO_ALIASES.read().unwrap().get(&repo) {
+    Some(aliased) => aliased.clone(),
+    None => repo,
+  }
+}
 
 #[derive(Deserialize)]
 struct RepoOwner {
@@ -15,8 +55,12 @@ struct Repo {
 /// check if two repos are the same, following
 /// redirects (in case the user/repo was renamed)
 /// user/repo pairs should be transformed to lowercase!
-pub async fn is_same_repo(repo: (&str, &str), other_repo: (&str, &str)) -> bool {
-  if repo == other_repo {
+pub async fn is_same_repo(
+  options: &RepoIconsBuilder,
+  repo: (&str, &str),
+  other_repo: (&str, &str),
+) -> bool {
+  if repo == other_repo || resolve_alias(repo) == resolve_alias(other_repo) {
     return true;
   }
 
@@ -26,7 +70,7 @@ pub async fn is_same_repo(repo: (&str, &str), other_repo: (&str, &str)) -> bool
     return false;
   }
 
-  let other_repo_res = get_repo_redirect(other_repo.0.into(), other_repo.1.into())
+  let other_repo_res = get_repo_redirect(options, other_repo.0.into(), other_repo.1.into())
     .await
     .map(|(user, repo)| (user.to_lowercase(), repo.to_lowercase()));
   let other_repo = other_repo_res
@@ -38,7 +82,7 @@ pub async fn is_same_repo(repo: (&str, &str), other_repo: (&str, &str)) -> bool
     return true;
   }
 
-  let repo_res = get_repo_redirect(repo.0.into(), repo.1.into())
+  let repo_res = get_repo_redirect(options, repo.0.into(), repo.1.into())
     .await
     .map(|(user, repo)| (user.to_lowercase(), repo.to_lowercase()));
   let repo = repo_res
@@ -53,19 +97,42 @@ pub async fn is_same_repo(repo: (&str, &str), other_repo: (&str, &str)) -> bool
   false
 }
 
-#[cached]
-async fn get_repo_redirect(owner: String, repo: String) -> Option<(String, String)> {
+/// Forget where the repo redirects to, in case it was renamed since.
+/// user/repo pairs should be transformed to lowercase!
+#[cfg(feature = "cache")]
+pub(crate) async fn forget_repo_redirect(owner: &str, repo: &str) {
+  GET_REPO_REDIRECT
+    .lock()
+    .await
+    .cache_remove(&(owner.to_string(), repo.to_string()));
+}
+
+#[cached(
+  key = "(String, String)",
+  convert = r#"{ (owner.clone(), repo.clone()) }"#
+)]
+async fn get_repo_redirect(
+  options: &RepoIconsBuilder,
+  owner: String,
+  repo: String,
+) -> Option<(String, String)> {
   #[cfg(target_arch = "wasm32")]
-  let req = gh_api_get!("repos/{}/{}", owner, repo);
+  let req = gh_api_get!(
+    github_client_builder(options).build().ok()?,
+    "repos/{}/{}",
+    owner,
+    repo
+  );
 
   #[cfg(not(target_arch = "wasm32"))]
   let req = {
-    use gh_api::gh_client;
     use reqwest::{header::LOCATION, redirect::Policy};
 
-    let client = gh_client(None).redirect(Policy::none()).build().ok()?;
-    let res = gh_api_get!(client, "repos/{}/{}", owner, repo)
-      .send()
+    let client = github_client_builder(options)
+      .redirect(Policy::none())
+      .build()
+      .ok()?;
+    let res = send(options, gh_api_get!(client, "repos/{}/{}", owner, repo))
       .await
       .ok()?;
 
@@ -77,7 +144,7 @@ async fn get_repo_redirect(owner: String, repo: String) -> Option<(String, Strin
     client.get(location)
   };
 
-  let repo = req.send().await.ok()?.json::<Repo>().await.ok()?;
+  let repo = send(options, req).await.ok()?.json::<Repo>().await.ok()?;
 
   Some((repo.owner.login, repo.name))
 }
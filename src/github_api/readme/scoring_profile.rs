@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+  InPrimaryHeading,
+  AlignCenter,
+  SizeAttrs,
+  SourcedFromRepo,
+  EdgeOfPrimaryHeading,
+  CenteredFirstImage,
+  LinksToWebsite,
+  LinksToRepo,
+  LogoMention,
+  BannerMention,
+  RepoNameMention,
+  TopicMention,
+  SquareAspect,
+  UltrawideAspect,
+  InCollapsedDetails,
+  NearDescription,
+  DocumentPosition,
+  InSecondaryReadme,
+  TechStackRow,
+}
+
+/// The weight each signal contributes when ranking readme images
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScoringProfile {
+  pub in_primary_heading: u8,
+  /// only applies to images in the primary heading
+  pub align_center: u8,
+  /// only applies to images in the primary heading
+  pub size_attrs: u8,
+  /// only applies to images in the primary heading
+  pub sourced_from_repo: u8,
+  pub edge_of_primary_heading: u8,
+  /// the first image in the readme, when centered and sized or sourced from the repo
+  pub centered_first_image: u8,
+  pub links_to_website: u8,
+  pub links_to_repo: u8,
+  pub logo_mention: u8,
+  pub banner_mention: u8,
+  pub repo_name_mention: u8,
+  /// the image mentions one of the repo's topics
+  pub topic_mention: u8,
+  /// square images are almost always a logo
+  pub square_aspect: u8,
+  /// subtracted from ultrawide images, which tend to be screenshots
+  pub ultrawide_aspect_penalty: u8,
+  /// the text next to the image resembles the repo's description,
+  /// eg. a tagline under the logo
+  pub near_description: u8,
+  /// subtracted from images in a `<details>` that's collapsed by default,
+  /// which rarely holds the logo
+  pub collapsed_details_penalty: u8,
+  /// the bonus for an image at the very top of the readme, as logos are almost
+  /// always in the first screenful. It decays further down, see `document_position_half_life`
+  pub document_position: u8,
+  /// the percentage of the readme after which the `document_position` bonus halves
+  pub document_position_half_life: u8,
+  /// subtracted from images in a row of similarly sized images, eg. the logos
+  /// of the technologies a project is "built with"
+  pub tech_stack_row_penalty: u8,
+  /// subtracted from images in the readmes of `docs/` & `.github/`, with
+  /// [`crate::RepoIconsBuilder::scan_all_readmes`]
+  pub secondary_readme_penalty: u8,
+}
+
+impl ScoringProfile {
+  /// The weight a signal adds to (or subtracts from) an image
+  pub fn contribution(&self, signal: Signal) -> i16 {
+    let weight = match signal {
+      Signal::InPrimaryHeading => self.in_primary_heading,
+      Signal::AlignCenter => self.align_center,
+      Signal::SizeAttrs => self.size_attrs,
+      Signal::SourcedFromRepo => self.sourced_from_repo,
+      Signal::EdgeOfPrimaryHeading => self.edge_of_primary_heading,
+      Signal::CenteredFirstImage => self.centered_first_image,
+      Signal::LinksToWebsite => self.links_to_website,
+      Signal::LinksToRepo => self.links_to_repo,
+      Signal::LogoMention => self.logo_mention,
+      Signal::BannerMention => self.banner_mention,
+      Signal::RepoNameMention => self.repo_name_mention,
+      Signal::TopicMention => self.topic_mention,
+      Signal::SquareAspect => self.square_aspect,
+      Signal::UltrawideAspect => return -(self.ultrawide_aspect_penalty as i16),
+      Signal::NearDescription => self.near_description,
+      Signal::InCollapsedDetails => return -(self.collapsed_details_penalty as i16),
+      // the most it can add, see `document_position_bonus`
+      Signal::DocumentPosition => self.document_position,
+      Signal::InSecondaryReadme => return -(self.secondary_readme_penalty as i16),
+      Signal::TechStackRow => return -(self.tech_stack_row_penalty as i16),
+    };
+
+    weight as i16
+  }
+
+  /// The bonus for an image `position` of the way down the readme, from 0 at
+  /// the very top to 1 at the bottom, halving every `document_position_half_life`
+  /// percent of the readme
+  pub fn document_position_bonus(&self, position: f32) -> i16 {
+    if self.document_position_half_life == 0 {
+      return if position <= 0.0 {
+        self.document_position as i16
+      } else {
+        0
+      };
+    }
+
+    let half_lives = position.max(0.0) * 100.0 / self.document_position_half_life as f32;
+    (self.document_position as f32 * 0.5_f32.powf(half_lives)).round() as i16
+  }
+}
+
+impl Default for ScoringProfile {
+  fn default() -> Self {
+    Self {
+      in_primary_heading: 2,
+      align_center: 2,
+      size_attrs: 2,
+      sourced_from_repo: 4,
+      edge_of_primary_heading: 4,
+      centered_first_image: 8,
+      links_to_website: 8,
+      links_to_repo: 4,
+      logo_mention: 16,
+      banner_mention: 8,
+      repo_name_mention: 4,
+      topic_mention: 4,
+      square_aspect: 4,
+      ultrawide_aspect_penalty: 4,
+      near_description: 8,
+      collapsed_details_penalty: 8,
+      document_position: 4,
+      document_position_half_life: 10,
+      tech_stack_row_penalty: 8,
+      secondary_readme_penalty: 4,
+    }
+  }
+}
@@ -0,0 +1,96 @@
+use super::{render_file, Readme};
+use crate::client::{github_client, send};
+use futures::future::join_all;
+use serde::Deserialize;
+use std::error::Error;
+
+/// the directories besides the root that hold readmes
+const README_DIRS: [&str; 2] = ["docs", ".github"];
+
+#[derive(Deserialize)]
+struct ContentsEntry {
+  name: String,
+  path: String,
+  #[serde(rename = "type")]
+  kind: String,
+}
+
+impl Readme {
+  /// The readmes in the repo's `docs/` & `.github/` directories, see
+  /// [`crate::RepoIconsBuilder::scan_all_readmes`]. Directories & readmes
+  /// that fail to load are skipped
+  pub(crate) async fn secondary_readmes(&self) -> Vec<Readme> {
+    let paths = join_all(README_DIRS.iter().map(|dir| self.readme_paths(dir))).await;
+
+    let readmes = join_all(
+      paths
+        .into_iter()
+        .filter_map(|paths| match paths {
+          Ok(paths) => Some(paths),
+          Err(err) => {
+            warn!("failed to list readmes: {}", err);
+            None
+          }
+        })
+        .flatten()
+        .map(async move |path| {
+          self
+            .load_secondary(&path)
+            .await
+            .map_err(|err| warn!("failed to load {}: {}", path, err))
+            .ok()
+        }),
+    )
+    .await;
+
+    readmes.into_iter().flatten().collect()
+  }
+
+  /// Load another readme in the repo, reusing this readme's metadata so only
+  /// the file itself is fetched
+  async fn load_secondary(&self, path: &str) -> Result<Readme, Box<dyn Error>> {
+    let (options, owner, repo) = (&self.options, &self.cased_owner, &self.cased_repo);
+    let metadata = match &self.metadata {
+      Some(metadata) => metadata.clone(),
+      None => return Readme::load_path_with(options, owner, repo, path).await,
+    };
+
+    let (source, body) = render_file(options, owner, repo, path).await?;
+    Readme::from_repo(options, Some(path), metadata, body, &source, None)
+  }
+
+  /// The readme files in a directory of the repo, none if it doesn't exist
+  async fn readme_paths(&self, dir: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let options = &self.options;
+    let res = send(
+      options,
+      gh_api_get!(
+        github_client(options),
+        "repos/{}/{}/contents/{}",
+        self.cased_owner,
+        self.cased_repo,
+        dir
+      ),
+    )
+    .await?;
+
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+      return Ok(Vec::new());
+    }
+
+    // a file at the path is listed as a single entry rather than an array
+    let entries = match res.error_for_status()?.json::<serde_json::Value>().await? {
+      serde_json::Value::Array(entries) => entries,
+      _ => return Ok(Vec::new()),
+    };
+
+    Ok(
+      entries
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value::<ContentsEntry>(entry).ok())
+        .filter(|entry| entry.kind == "file" && entry.name.to_lowercase().starts_with("readme."))
+        .map(|entry| entry.path)
+        .collect(),
+    )
+  }
+}
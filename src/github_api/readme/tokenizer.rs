@@ -0,0 +1,732 @@
+use ego_tree::NodeId;
+use html5ever::{
+  namespace_url, ns,
+  tendril::StrTendril,
+  tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+  },
+  tree_builder::QuirksMode,
+  Attribute, LocalName, Namespace, Prefix, QualName,
+};
+use scraper::{
+  node::{Comment, Element, Text},
+  Html, Node,
+};
+
+/// Build a readme's document straight from html5ever's tokens, without the
+/// tree builder. The spans of syntax highlighted code are left out, as
+/// nothing reads them, which for code heavy readmes is most of the document.
+///
+/// Only well-formed html is handled: `None` is returned for anything the
+/// tree builder would repair (eg. a misnested end tag, a `<div>` in a `<p>`
+/// or text in a `<table>`) or that it tokenizes differently (raw text
+/// elements like `<script>` & `<textarea>`, `<math>`, doctypes...), which
+/// [`crate::Readme::new`] parses with scraper instead. The images found in
+/// either document are the same
+pub fn parse_simple_readme(html: &str) -> Option<Html> {
+  let mut tokenizer = Tokenizer::new(DocumentSink::default(), TokenizerOpts::default());
+  let mut input = BufferQueue::new();
+  input.push_back(StrTendril::from_slice(html));
+
+  let _ = tokenizer.feed(&mut input);
+  if tokenizer.sink.bailed {
+    return None;
+  }
+  tokenizer.end();
+
+  let mut sink = tokenizer.sink;
+  if sink.bailed {
+    return None;
+  }
+  sink.start();
+  Some(sink.html)
+}
+
+/// The tree builder's `<html>` & `<body>`, which are never closed
+const SENTINELS: usize = 2;
+
+struct OpenElement {
+  id: NodeId,
+  name: QualName,
+  /// a `<tbody>` / `<tr>` the tree builder adds around bare table rows & cells
+  implied: bool,
+}
+
+struct DocumentSink {
+  html: Html,
+  open: Vec<OpenElement>,
+  bailed: bool,
+  /// the tree builder drops the newline right after a `<pre>`
+  skip_newline: bool,
+  /// the `<span>`s left out of the open `<pre>`s, that are still unclosed
+  dropped_spans: usize,
+  /// whether any spans were left out of the open `<pre>`s
+  dropped_any: bool,
+  /// text after a left out span would've been a separate text node
+  split_text: bool,
+}
+
+impl Default for DocumentSink {
+  fn default() -> Self {
+    let mut html = Html::new_document();
+    // there's no doctype
+    html.quirks_mode = QuirksMode::Quirks;
+
+    Self {
+      html,
+      open: Vec::new(),
+      bailed: false,
+      skip_newline: false,
+      dropped_spans: 0,
+      dropped_any: false,
+      split_text: false,
+    }
+  }
+}
+
+impl TokenSink for DocumentSink {
+  type Handle = ();
+
+  fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+    let is_tag = matches!(token, Token::TagToken(_));
+    if !self.bailed && self.process(token).is_none() {
+      self.bailed = true;
+    }
+
+    // the tokenizer can only be stopped at a tag, by asking it to run a script
+    if self.bailed && is_tag {
+      TokenSinkResult::Script(())
+    } else {
+      TokenSinkResult::Continue
+    }
+  }
+
+  /// CDATA sections are text in svgs, and comments elsewhere
+  fn adjusted_current_node_present_but_not_in_html_namespace(&self) -> bool {
+    self
+      .open
+      .last()
+      .is_some_and(|elem| elem.name.ns != ns!(html))
+  }
+}
+
+impl DocumentSink {
+  /// Add the token to the document, or `None` to bail
+  fn process(&mut self, token: Token) -> Option<()> {
+    match token {
+      Token::ParseError(_) => Some(()),
+      Token::EOFToken => Some(()),
+      Token::DoctypeToken(_) | Token::NullCharacterToken => None,
+      Token::CommentToken(comment) => {
+        // comments before the body go in the document itself
+        if self.open.is_empty() {
+          return None;
+        }
+        self.skip_newline = false;
+        self.append(Node::Comment(Comment { comment }));
+        Some(())
+      }
+      Token::CharacterTokens(mut text) => {
+        if self.open.is_empty() {
+          // whitespace before the body is dropped
+          let start = text.len() - text.trim_start_matches(is_whitespace).len();
+          text.pop_front(start as u32);
+          if text.is_empty() {
+            return Some(());
+          }
+          self.start();
+        }
+        if std::mem::take(&mut self.skip_newline) && text.starts_with('\n') {
+          text.pop_front(1);
+        }
+        if in_table(self.current()) && !text.chars().all(is_whitespace) {
+          return None;
+        }
+        self.append_text(text);
+        Some(())
+      }
+      Token::TagToken(tag) => {
+        self.start();
+        self.skip_newline = false;
+        match tag.kind {
+          TagKind::StartTag => self.start_tag(tag),
+          TagKind::EndTag => self.end_tag(tag),
+        }
+      }
+    }
+  }
+
+  /// Add the `<html>` with an empty `<head>`, and the `<body>`
+  fn start(&mut self) {
+    if !self.open.is_empty() {
+      return;
+    }
+
+    let root = self.html.tree.root().id();
+    let html = self.insert(root, html_name("html"), Vec::new());
+    self.insert(html, html_name("head"), Vec::new());
+    let body = self.insert(html, html_name("body"), Vec::new());
+
+    for (id, name) in [(html, "html"), (body, "body")] {
+      self.open.push(OpenElement {
+        id,
+        name: html_name(name),
+        implied: false,
+      });
+    }
+  }
+
+  fn start_tag(&mut self, tag: Tag) -> Option<()> {
+    let Tag {
+      name,
+      self_closing,
+      attrs,
+      ..
+    } = tag;
+
+    if self.current().ns == ns!(svg) {
+      return self.svg_start_tag(name, self_closing, attrs);
+    }
+
+    if self.in_pre() {
+      if &*name == "span" {
+        self.dropped_spans += 1;
+        self.dropped_any = true;
+        self.split_text = true;
+        return Some(());
+      }
+      // the left out spans could've held it
+      if self.dropped_any {
+        return None;
+      }
+    }
+
+    if in_table(self.current()) {
+      return self.table_start_tag(name, attrs);
+    }
+
+    match &*name {
+      "html" | "head" | "body" | "frameset" | "frame" | "base" | "basefont" | "bgsound"
+      | "link" | "meta" | "title" | "style" | "script" | "noscript" | "noframes" | "noembed"
+      | "iframe" | "template" | "textarea" | "xmp" | "plaintext" | "listing" | "select"
+      | "option" | "optgroup" | "math" | "image" | "form" | "button" | "nobr" | "rb" | "rtc"
+      | "rp" | "rt" | "caption" | "col" | "colgroup" | "tbody" | "thead" | "tfoot" | "tr"
+      | "td" | "th" => return None,
+      // an open link would be closed by the adoption agency
+      "a" if self.is_open("a") => return None,
+      _ => {}
+    }
+
+    if closes_p(&name) && self.is_open("p") {
+      return None;
+    }
+    if is_heading(&name) && is_heading(&self.current().local) {
+      return None;
+    }
+    if matches!(&*name, "li" | "dd" | "dt") && self.closes_list_item(&name) {
+      return None;
+    }
+
+    match &*name {
+      "svg" => {
+        let attrs = attrs.into_iter().map(adjust_svg_attr).collect();
+        let id = self.insert_current(svg_name(name.clone()), attrs);
+        if !self_closing {
+          self.push(id, svg_name(name), false);
+        }
+      }
+      name if is_void(name) => {
+        self.insert_current(html_name(name), attrs);
+      }
+      _ => {
+        let id = self.insert_current(html_name(&name), attrs);
+        self.push(id, html_name(&name), false);
+        self.skip_newline = &*name == "pre";
+      }
+    }
+
+    Some(())
+  }
+
+  /// The rows & cells of a table, adding the `<tbody>` / `<tr>` the tree
+  /// builder adds around bare ones. Anything else would be moved out of the
+  /// table
+  fn table_start_tag(&mut self, name: LocalName, attrs: Vec<Attribute>) -> Option<()> {
+    let current = self.current().local.clone();
+    let implied: &[&str] = match (&*current, &*name) {
+      ("table", "tbody" | "thead" | "tfoot") => &[],
+      ("table", "tr") => &["tbody"],
+      ("table", "td" | "th") => &["tbody", "tr"],
+      ("tbody" | "thead" | "tfoot", "tr") => &[],
+      ("tbody" | "thead" | "tfoot", "td" | "th") => &["tr"],
+      ("tr", "td" | "th") => &[],
+      _ => return None,
+    };
+
+    for implied in implied {
+      let id = self.insert_current(html_name(implied), Vec::new());
+      self.push(id, html_name(implied), true);
+    }
+    let id = self.insert_current(html_name(&name), attrs);
+    self.push(id, html_name(&name), false);
+
+    Some(())
+  }
+
+  fn svg_start_tag(
+    &mut self,
+    name: LocalName,
+    self_closing: bool,
+    attrs: Vec<Attribute>,
+  ) -> Option<()> {
+    // the html in a <foreignObject> / <desc> / <title>
+    if matches!(&*self.current().local, "foreignObject" | "desc" | "title") {
+      return None;
+    }
+
+    let breaks_out = match &*name {
+      "b" | "big" | "blockquote" | "body" | "br" | "center" | "code" | "dd" | "div" | "dl"
+      | "dt" | "em" | "embed" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "head" | "hr" | "i"
+      | "img" | "li" | "listing" | "menu" | "meta" | "nobr" | "ol" | "p" | "pre" | "ruby" | "s"
+      | "small" | "span" | "strong" | "strike" | "sub" | "sup" | "table" | "tt" | "u" | "ul"
+      | "var" | "script" => true,
+      "font" => attrs
+        .iter()
+        .any(|attr| matches!(&*attr.name.local, "color" | "face" | "size")),
+      _ => false,
+    };
+    if breaks_out {
+      return None;
+    }
+
+    let name = svg_name(adjust_svg_tag(name));
+    let attrs = attrs.into_iter().map(adjust_svg_attr).collect();
+    let id = self.insert_current(name.clone(), attrs);
+    if !self_closing {
+      self.push(id, name, false);
+    }
+
+    Some(())
+  }
+
+  fn end_tag(&mut self, tag: Tag) -> Option<()> {
+    let name = tag.name;
+
+    if self.in_pre() && &*name == "span" && self.dropped_spans > 0 {
+      self.dropped_spans -= 1;
+      self.split_text = true;
+      return Some(());
+    }
+
+    // closing a table closes the rows & bodies it implied
+    if matches!(&*name, "table" | "tbody" | "thead" | "tfoot") {
+      while self.open.len() > SENTINELS {
+        let current = self.open.last().unwrap();
+        if !current.implied || current.name.local == name {
+          break;
+        }
+        self.open.pop();
+      }
+    }
+
+    if self.open.len() <= SENTINELS {
+      return None;
+    }
+    let current = self.open.last().unwrap();
+    let matches = match current.name.ns {
+      ns!(html) => current.name.local == name,
+      _ => current.name.local.eq_ignore_ascii_case(&name),
+    };
+    if !matches {
+      return None;
+    }
+
+    if &*name == "pre" && current.name.ns == ns!(html) {
+      // the tree builder would close the unclosed spans
+      if self.dropped_spans > 0 {
+        return None;
+      }
+      self.open.pop();
+      if !self.in_pre() {
+        self.dropped_any = false;
+      }
+    } else {
+      self.open.pop();
+    }
+
+    Some(())
+  }
+
+  fn current(&self) -> &QualName {
+    &self.open.last().unwrap().name
+  }
+
+  fn is_open(&self, name: &str) -> bool {
+    self
+      .open
+      .iter()
+      .any(|elem| elem.name.ns == ns!(html) && &*elem.name.local == name)
+  }
+
+  fn in_pre(&self) -> bool {
+    self.is_open("pre")
+  }
+
+  /// Whether a new `<li>` / `<dd>` / `<dt>` would close an open one, which
+  /// the tree builder checks up to the nearest list
+  fn closes_list_item(&self, name: &str) -> bool {
+    for elem in self.open.iter().rev() {
+      let local = &*elem.name.local;
+      let closes = match name {
+        "li" => local == "li",
+        _ => matches!(local, "dd" | "dt"),
+      };
+      if closes {
+        return true;
+      }
+      if is_special(local) && !matches!(local, "address" | "div" | "p") {
+        return false;
+      }
+    }
+    false
+  }
+
+  fn push(&mut self, id: NodeId, name: QualName, implied: bool) {
+    self.open.push(OpenElement { id, name, implied });
+  }
+
+  fn insert(&mut self, parent: NodeId, name: QualName, attrs: Vec<Attribute>) -> NodeId {
+    self.split_text = false;
+    self
+      .html
+      .tree
+      .get_mut(parent)
+      .unwrap()
+      .append(Node::Element(Element::new(name, attrs)))
+      .id()
+  }
+
+  fn insert_current(&mut self, name: QualName, attrs: Vec<Attribute>) -> NodeId {
+    let parent = self.open.last().unwrap().id;
+    self.insert(parent, name, attrs)
+  }
+
+  fn append(&mut self, node: Node) {
+    let parent = self.open.last().unwrap().id;
+    self.split_text = false;
+    self.html.tree.get_mut(parent).unwrap().append(node);
+  }
+
+  /// Add text to the current element, joined onto its last text like the
+  /// tree builder does
+  fn append_text(&mut self, text: StrTendril) {
+    let parent = self.open.last().unwrap().id;
+    let mut parent = self.html.tree.get_mut(parent).unwrap();
+
+    if !std::mem::take(&mut self.split_text) {
+      if let Some(mut last) = parent.last_child() {
+        if let Node::Text(last) = last.value() {
+          last.text.push_tendril(&text);
+          return;
+        }
+      }
+    }
+    parent.append(Node::Text(Text { text }));
+  }
+}
+
+fn html_name(name: &str) -> QualName {
+  QualName::new(None, ns!(html), LocalName::from(name))
+}
+
+fn svg_name(name: LocalName) -> QualName {
+  QualName::new(None, ns!(svg), name)
+}
+
+fn is_whitespace(c: char) -> bool {
+  matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' ')
+}
+
+fn in_table(name: &QualName) -> bool {
+  name.ns == ns!(html) && matches!(&*name.local, "table" | "tbody" | "thead" | "tfoot" | "tr")
+}
+
+fn is_heading(name: &str) -> bool {
+  matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6")
+}
+
+fn is_void(name: &str) -> bool {
+  matches!(
+    name,
+    "area"
+      | "br"
+      | "embed"
+      | "img"
+      | "keygen"
+      | "wbr"
+      | "input"
+      | "param"
+      | "source"
+      | "track"
+      | "hr"
+  )
+}
+
+/// The elements that close an open `<p>`
+fn closes_p(name: &str) -> bool {
+  is_heading(name)
+    || matches!(
+      name,
+      "address"
+        | "article"
+        | "aside"
+        | "blockquote"
+        | "center"
+        | "details"
+        | "dialog"
+        | "dir"
+        | "div"
+        | "dl"
+        | "fieldset"
+        | "figcaption"
+        | "figure"
+        | "footer"
+        | "header"
+        | "hgroup"
+        | "main"
+        | "menu"
+        | "nav"
+        | "ol"
+        | "p"
+        | "section"
+        | "summary"
+        | "ul"
+        | "pre"
+        | "li"
+        | "dd"
+        | "dt"
+        | "hr"
+    )
+}
+
+/// html5ever's special elements, that a new list item doesn't look past
+fn is_special(name: &str) -> bool {
+  is_heading(name)
+    || matches!(
+      name,
+      "address"
+        | "applet"
+        | "area"
+        | "article"
+        | "aside"
+        | "base"
+        | "basefont"
+        | "bgsound"
+        | "blockquote"
+        | "body"
+        | "br"
+        | "button"
+        | "caption"
+        | "center"
+        | "col"
+        | "colgroup"
+        | "dd"
+        | "details"
+        | "dir"
+        | "div"
+        | "dl"
+        | "dt"
+        | "embed"
+        | "fieldset"
+        | "figcaption"
+        | "figure"
+        | "footer"
+        | "form"
+        | "frame"
+        | "frameset"
+        | "head"
+        | "header"
+        | "hgroup"
+        | "hr"
+        | "html"
+        | "iframe"
+        | "img"
+        | "input"
+        | "isindex"
+        | "li"
+        | "link"
+        | "listing"
+        | "main"
+        | "marquee"
+        | "menu"
+        | "meta"
+        | "nav"
+        | "noembed"
+        | "noframes"
+        | "noscript"
+        | "object"
+        | "ol"
+        | "p"
+        | "param"
+        | "plaintext"
+        | "pre"
+        | "script"
+        | "section"
+        | "select"
+        | "source"
+        | "style"
+        | "summary"
+        | "table"
+        | "tbody"
+        | "td"
+        | "template"
+        | "textarea"
+        | "tfoot"
+        | "th"
+        | "thead"
+        | "title"
+        | "tr"
+        | "track"
+        | "ul"
+        | "wbr"
+        | "xmp"
+    )
+}
+
+/// The camel cased svg elements, which the tokenizer lowercases
+const SVG_TAGS: [&str; 37] = [
+  "altGlyph",
+  "altGlyphDef",
+  "altGlyphItem",
+  "animateColor",
+  "animateMotion",
+  "animateTransform",
+  "clipPath",
+  "feBlend",
+  "feColorMatrix",
+  "feComponentTransfer",
+  "feComposite",
+  "feConvolveMatrix",
+  "feDiffuseLighting",
+  "feDisplacementMap",
+  "feDistantLight",
+  "feDropShadow",
+  "feFlood",
+  "feFuncA",
+  "feFuncB",
+  "feFuncG",
+  "feFuncR",
+  "feGaussianBlur",
+  "feImage",
+  "feMerge",
+  "feMergeNode",
+  "feMorphology",
+  "feOffset",
+  "fePointLight",
+  "feSpecularLighting",
+  "feSpotLight",
+  "feTile",
+  "feTurbulence",
+  "foreignObject",
+  "glyphRef",
+  "linearGradient",
+  "radialGradient",
+  "textPath",
+];
+
+/// The camel cased svg attributes, which the tokenizer lowercases
+const SVG_ATTRS: [&str; 58] = [
+  "attributeName",
+  "attributeType",
+  "baseFrequency",
+  "baseProfile",
+  "calcMode",
+  "clipPathUnits",
+  "diffuseConstant",
+  "edgeMode",
+  "filterUnits",
+  "glyphRef",
+  "gradientTransform",
+  "gradientUnits",
+  "kernelMatrix",
+  "kernelUnitLength",
+  "keyPoints",
+  "keySplines",
+  "keyTimes",
+  "lengthAdjust",
+  "limitingConeAngle",
+  "markerHeight",
+  "markerUnits",
+  "markerWidth",
+  "maskContentUnits",
+  "maskUnits",
+  "numOctaves",
+  "pathLength",
+  "patternContentUnits",
+  "patternTransform",
+  "patternUnits",
+  "pointsAtX",
+  "pointsAtY",
+  "pointsAtZ",
+  "preserveAlpha",
+  "preserveAspectRatio",
+  "primitiveUnits",
+  "refX",
+  "refY",
+  "repeatCount",
+  "repeatDur",
+  "requiredExtensions",
+  "requiredFeatures",
+  "specularConstant",
+  "specularExponent",
+  "spreadMethod",
+  "startOffset",
+  "stdDeviation",
+  "stitchTiles",
+  "surfaceScale",
+  "systemLanguage",
+  "tableValues",
+  "targetX",
+  "targetY",
+  "textLength",
+  "viewBox",
+  "viewTarget",
+  "xChannelSelector",
+  "yChannelSelector",
+  "zoomAndPan",
+];
+
+fn camel_case(name: &str, names: &[&str]) -> Option<LocalName> {
+  names
+    .iter()
+    .find(|camel| camel.eq_ignore_ascii_case(name))
+    .map(|camel| LocalName::from(*camel))
+}
+
+fn adjust_svg_tag(name: LocalName) -> LocalName {
+  camel_case(&name, &SVG_TAGS).unwrap_or(name)
+}
+
+/// Camel case the attribute, or put `xlink:` / `xml:` / `xmlns` ones in
+/// their namespace, as the tree builder does
+fn adjust_svg_attr(mut attr: Attribute) -> Attribute {
+  let local = &*attr.name.local;
+  let prefixed = |prefix: &str, ns: Namespace, local: &str| {
+    QualName::new(Some(Prefix::from(prefix)), ns, LocalName::from(local))
+  };
+
+  attr.name = match local.split_once(':') {
+    Some((
+      "xlink",
+      local @ ("actuate" | "arcrole" | "href" | "role" | "show" | "title" | "type"),
+    )) => prefixed("xlink", ns!(xlink), local),
+    Some(("xml", local @ ("base" | "lang" | "space"))) => prefixed("xml", ns!(xml), local),
+    Some(("xmlns", "xlink")) => prefixed("xmlns", ns!(xmlns), "xlink"),
+    _ if local == "xmlns" => prefixed("", ns!(xmlns), "xmlns"),
+    _ => match camel_case(local, &SVG_ATTRS) {
+      Some(local) => QualName::new(None, ns!(), local),
+      None => return attr,
+    },
+  };
+  attr
+}
@@ -0,0 +1,41 @@
+use crate::{
+  blacklist::is_placeholder,
+  client::{client, send},
+  RepoIconsBuilder,
+};
+use scraper::Html;
+use std::error::Error;
+use url::Url;
+
+/// Get the custom social preview uploaded for the repo with its `og:image:alt`,
+/// ignoring the preview github generates by default
+pub async fn get_social_preview(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+) -> Result<Option<(Url, Option<String>)>, Box<dyn Error>> {
+  let body = send(
+    options,
+    client(options).get(format!("https://github.com/{}/{}", owner, repo)),
+  )
+  .await?
+  .error_for_status()?
+  .text()
+  .await?;
+
+  let document = Html::parse_document(&body);
+
+  let url = document
+    .select(selector!("meta[property='og:image']"))
+    .find_map(|elem_ref| elem_ref.value().attr("content"))
+    .and_then(|content| Url::parse(content).ok())
+    .filter(|url| !is_placeholder(url));
+
+  let alt = document
+    .select(selector!("meta[property='og:image:alt']"))
+    .find_map(|elem_ref| elem_ref.value().attr("content"))
+    .map(|alt| alt.trim().to_string())
+    .filter(|alt| !alt.is_empty());
+
+  Ok(url.map(|url| (url, alt)))
+}
@@ -1,4 +1,10 @@
+use crate::{
+  client::{github_client, send},
+  RepoIconsBuilder,
+};
 use cached::proc_macro::cached;
+#[cfg(feature = "cache")]
+use cached::Cached;
 use std::error::Error;
 
 #[derive(Deserialize)]
@@ -6,21 +12,38 @@ struct Repo {
   name: String,
 }
 
-#[cached]
-async fn get_user_repos_cached(user: String) -> Result<Vec<String>, String> {
-  let res = gh_api_get!("users/{}/repos?per_page=100", user)
-    .send()
-    .await
-    .map_err(|e| format!("{:?}", e).to_string())?
-    .json::<Vec<Repo>>()
-    .await
-    .map_err(|e| format!("{:?}", e).to_string())?;
+#[cached(key = "String", convert = r#"{ user.clone() }"#)]
+async fn get_user_repos_cached(
+  options: &RepoIconsBuilder,
+  user: String,
+) -> Result<Vec<String>, String> {
+  let res = send(
+    options,
+    gh_api_get!(github_client(options), "users/{}/repos?per_page=100", user),
+  )
+  .await
+  .map_err(|e| format!("{:?}", e).to_string())?
+  .json::<Vec<Repo>>()
+  .await
+  .map_err(|e| format!("{:?}", e).to_string())?;
 
   Ok(res.into_iter().map(|r| r.name.to_lowercase()).collect())
 }
 
-pub async fn get_user_repos(user: &str) -> Result<Vec<String>, Box<dyn Error>> {
-  get_user_repos_cached(user.to_lowercase())
+/// Forget the user's cached repos, so they're fetched again
+#[cfg(feature = "cache")]
+pub(crate) async fn forget_user_repos(user: &str) {
+  GET_USER_REPOS_CACHED
+    .lock()
+    .await
+    .cache_remove(&user.to_lowercase());
+}
+
+pub async fn get_user_repos(
+  options: &RepoIconsBuilder,
+  user: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+  get_user_repos_cached(options, user.to_lowercase())
     .await
     .map_err(|e| e.into())
 }
@@ -0,0 +1,359 @@
+use crate::{
+  client::{client, send, with_permit},
+  github_api::readme::readme_image::encode_svg,
+  ColorScheme, RepoIcon, RepoIconKind, RepoIconsBuilder,
+};
+use itertools::Itertools;
+use reqwest::header::ACCEPT;
+use scraper::{ElementRef, Html};
+use site_icons::{IconKind, Icons};
+use std::{cmp::Reverse, collections::HashMap, error::Error, mem};
+use url::Url;
+
+/// What's found in a homepage's html that `site_icons` doesn't report
+pub(crate) struct HomepageScan {
+  pub background_logos: Vec<Url>,
+  /// the scheme of the `<link rel="icon">`s with a color scheme `media` query
+  pub icon_color_schemes: HashMap<Url, ColorScheme>,
+  /// the `<title>`, used to label the favicons
+  pub title: Option<String>,
+  /// the `og:image:alt` of the `og:image`s
+  pub image_alts: HashMap<Url, String>,
+  /// the `<link rel="mask-icon">`s, with their `color`
+  pub mask_icons: HashMap<Url, Option<String>>,
+}
+
+impl HomepageScan {
+  /// Apply what the scan found about an icon: mask icons get their kind &
+  /// color, favicons their color scheme and the alt or page title as a label
+  pub fn apply(&self, icon: &mut RepoIcon) {
+    if let Some(color) = self.mask_icons.get(&icon.url) {
+      if matches!(icon.kind, RepoIconKind::Site(_)) {
+        icon.kind = RepoIconKind::MaskIcon;
+      }
+      icon.mask_color = color.clone();
+    }
+
+    if icon.color_scheme == ColorScheme::Any {
+      if let Some(color_scheme) = self.icon_color_schemes.get(&icon.url) {
+        icon.color_scheme = *color_scheme;
+      }
+    }
+
+    if let Some(alt) = self.image_alts.get(&icon.url) {
+      icon.label = Some(alt.clone());
+    } else if icon.label.is_none()
+      && matches!(icon.kind, RepoIconKind::Site(_) | RepoIconKind::MaskIcon)
+    {
+      icon.label = self.title.clone();
+    }
+  }
+}
+
+/// Load a homepage's icons into `icons`: the favicons, logo & manifest icons
+/// `site_icons` would find, along with the background logos & mask icons the
+/// scan finds. The homepage is only downloaded once, for both
+pub(crate) async fn load_homepage(
+  options: &RepoIconsBuilder,
+  icons: &mut Icons,
+  homepage: &Url,
+) -> Result<HomepageScan, Box<dyn Error>> {
+  let res = send(
+    options,
+    client(options)
+      .get(homepage.clone())
+      .header(ACCEPT, "text/html"),
+  )
+  .await?
+  .error_for_status()?;
+  let url = res.url().clone();
+  let html = res.text().await?;
+
+  let (mut scan, manifests) = {
+    let document = Html::parse_document(&html);
+    let base = document
+      .select(selector!("base[href]"))
+      .next()
+      .and_then(|base| url.join(base.value().attr("href")?).ok())
+      .unwrap_or(url);
+
+    (
+      scan_homepage(&base, &document),
+      site_icons(icons, &base, &document),
+    )
+  };
+
+  for manifest in manifests {
+    if let Err(err) = with_permit(options, icons.load_manifest(manifest.clone())).await {
+      warn!("failed to fetch manifest {} {}", manifest, err);
+    }
+  }
+
+  for url in mem::take(&mut scan.background_logos) {
+    icons.add_icon(url, IconKind::SiteLogo, None);
+  }
+  for url in scan.mask_icons.keys() {
+    icons.add_icon(url.clone(), IconKind::SiteLogo, None);
+  }
+
+  Ok(scan)
+}
+
+fn scan_homepage(base: &Url, document: &Html) -> HomepageScan {
+  HomepageScan {
+    background_logos: background_logos(base, document),
+    icon_color_schemes: icon_color_schemes(base, document),
+    title: document
+      .select(selector!("title"))
+      .next()
+      .map(|title| title.text().collect::<Vec<_>>().join(" "))
+      .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+      .filter(|title| !title.is_empty()),
+    image_alts: image_alts(base, document),
+    mask_icons: mask_icons(base, document),
+  }
+}
+
+/// Add the icons `site_icons`' `load_website` finds in the page: the favicons
+/// (or `/favicon.ico` without any) and the most logo-like image. Returns the
+/// manifests to load, as `load_website` would download the page again
+fn site_icons(icons: &mut Icons, base: &Url, document: &Html) -> Vec<Url> {
+  let mut found_favicon = false;
+  for link in document.select(selector!(
+    "link[rel='icon']",
+    "link[rel='shortcut icon']",
+    "link[rel='apple-touch-icon']",
+    "link[rel='apple-touch-icon-precomposed']"
+  )) {
+    let link = link.value();
+    if let Some(href) = link.attr("href").and_then(|href| base.join(href).ok()) {
+      icons.add_icon(
+        href,
+        IconKind::SiteFavicon,
+        link.attr("sizes").map(|sizes| sizes.into()),
+      );
+      found_favicon = true;
+    }
+  }
+  if !found_favicon {
+    icons.add_icon(
+      base.join("/favicon.ico").unwrap(),
+      IconKind::SiteFavicon,
+      None,
+    );
+  }
+
+  let mentions_logo = |elem: &ElementRef, attr| {
+    elem
+      .value()
+      .attr(attr)
+      .map(|attr| {
+        regex!("logo([^s]|$)")
+          .is_match(&attr.to_lowercase())
+          .unwrap_or(false)
+      })
+      .unwrap_or(false)
+  };
+
+  // the heaviest, preferring an `<img>` over an equally heavy svg, then the
+  // first
+  let logo = document
+    .select(selector!(
+      "header img, header svg",
+      "img[src*=logo]",
+      "img[alt*=logo], svg[alt*=logo]",
+      "img[class*=logo], svg[class*=logo]",
+    ))
+    .enumerate()
+    .map(|(i, elem)| {
+      let mut weight = 0;
+      if elem
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| ancestor.value().name() == "header")
+      {
+        weight += 2;
+      }
+      if mentions_logo(&elem, "class") || mentions_logo(&elem, "id") {
+        weight += 3;
+      }
+      if mentions_logo(&elem, "alt") {
+        weight += 2;
+      }
+      if mentions_logo(&elem, "src") {
+        weight += 1;
+      }
+      ((weight, elem.value().name() == "img", Reverse(i)), elem)
+    })
+    .max_by_key(|(key, _)| *key)
+    .and_then(|(_, elem)| match elem.value().name() {
+      "svg" => Url::parse(&encode_svg(&elem.html())).ok(),
+      _ => base.join(elem.value().attr("src")?).ok(),
+    });
+  if let Some(logo) = logo {
+    icons.add_icon(logo, IconKind::SiteLogo, None);
+  }
+
+  document
+    .select(selector!("link[rel='manifest']"))
+    .filter_map(|link| base.join(link.value().attr("href")?).ok())
+    .collect()
+}
+
+/// Safari's pinned tab icons, which `site_icons` doesn't find
+fn mask_icons(base: &Url, document: &Html) -> HashMap<Url, Option<String>> {
+  document
+    .select(selector!("link[rel][href]"))
+    .map(|link| link.value())
+    .filter(|link| {
+      link
+        .attr("rel")
+        .unwrap()
+        .split_whitespace()
+        .any(|rel| rel.eq_ignore_ascii_case("mask-icon"))
+    })
+    .filter_map(|link| {
+      let url = base.join(link.attr("href").unwrap()).ok()?;
+      let color = link
+        .attr("color")
+        .map(|color| color.trim().to_string())
+        .filter(|color| !color.is_empty());
+      Some((url, color))
+    })
+    .collect()
+}
+
+/// Each `og:image` paired with the `og:image:alt` that follows it
+fn image_alts(base: &Url, document: &Html) -> HashMap<Url, String> {
+  let mut alts = HashMap::new();
+  let mut image = None;
+
+  for meta in document.select(selector!("meta[property][content]")) {
+    let meta = meta.value();
+    let content = meta.attr("content").unwrap().trim();
+
+    match meta.attr("property").unwrap() {
+      "og:image" | "og:image:url" => image = base.join(content).ok(),
+      "og:image:alt" if !content.is_empty() => {
+        if let Some(image) = image.take() {
+          alts.insert(image, content.to_string());
+        }
+      }
+      _ => {}
+    }
+  }
+
+  alts
+}
+
+/// The favicon variants for dark & light mode, declared with eg.
+/// `<link rel="icon" media="(prefers-color-scheme: dark)">`
+fn icon_color_schemes(base: &Url, document: &Html) -> HashMap<Url, ColorScheme> {
+  document
+    .select(selector!("link[rel][href][media]"))
+    .map(|link| link.value())
+    .filter(|link| link.attr("rel").unwrap().to_lowercase().contains("icon"))
+    .filter_map(|link| {
+      let color_scheme = ColorScheme::from_media(link.attr("media").unwrap());
+      let url = base.join(link.attr("href").unwrap()).ok()?;
+      (color_scheme != ColorScheme::Any).then_some((url, color_scheme))
+    })
+    .collect()
+}
+
+/// Find the logos a homepage sets as css backgrounds, in inline `style`
+/// attributes & `<style>` blocks, or references from an svg sprite sheet with
+/// `<use href="sprite.svg#logo">`. Hero logos are often one of these, which
+/// `site_icons` doesn't find as they aren't `<img>`s
+fn background_logos(base: &Url, document: &Html) -> Vec<Url> {
+  let mut srcs = Vec::new();
+
+  for elem in document.select(selector!("[style]")) {
+    let elem = elem.value();
+    let context = format!(
+      "{} {}",
+      elem.attr("id").unwrap_or_default(),
+      elem.attr("class").unwrap_or_default()
+    );
+    srcs.extend(background_srcs(elem.attr("style").unwrap(), &context));
+  }
+
+  for style in document.select(selector!("style")) {
+    let css = style.text().collect::<String>();
+
+    // only the innermost rules, so the selectors of @media blocks are skipped
+    for rule in regex!(r"([^{}]+)\{([^{}]*)\}")
+      .captures_iter(&css)
+      .flatten()
+    {
+      srcs.extend(background_srcs(&rule[2], &rule[1]));
+    }
+  }
+
+  srcs.extend(sprite_srcs(document));
+
+  srcs
+    .into_iter()
+    .filter_map(|src| base.join(&src).ok())
+    .filter(|url| matches!(url.scheme(), "http" | "https" | "data"))
+    .unique()
+    .collect()
+}
+
+/// The background image urls in css declarations, when either the url or the
+/// element / selector they apply to looks like a logo
+fn background_srcs(declarations: &str, context: &str) -> Vec<String> {
+  let context_is_logo = is_logo(context);
+
+  regex!(r#"(?i)background(?:-image)?\s*:[^;]*?url\(\s*(['"]?)([^'")]+)\1\s*\)"#)
+    .captures_iter(declarations)
+    .flatten()
+    .map(|captures| captures[2].trim().to_string())
+    .filter(|src| context_is_logo || is_logo(src))
+    .collect()
+}
+
+/// The external sprites referenced by `<use>`, keeping the symbol's fragment.
+/// `#logo` style references to symbols inline in the page can't be loaded
+/// on their own, so they're skipped
+fn sprite_srcs(document: &Html) -> Vec<String> {
+  let mut srcs = Vec::new();
+
+  for elem in document.select(selector!("use")) {
+    // `xlink:href` is parsed into the xlink namespace, so it can't be selected
+    let href = elem
+      .value()
+      .attrs()
+      .find(|(name, _)| *name == "href")
+      .map(|(_, href)| href.trim());
+
+    let href = match href {
+      Some(href) if !href.is_empty() && !href.starts_with('#') => href,
+      _ => continue,
+    };
+
+    // the svg & its wrapper, eg. `<a class="logo"><svg><use>`
+    let context = elem
+      .ancestors()
+      .take(3)
+      .filter_map(|node| node.value().as_element())
+      .map(|elem| {
+        format!(
+          "{} {}",
+          elem.attr("id").unwrap_or_default(),
+          elem.attr("class").unwrap_or_default()
+        )
+      })
+      .join(" ");
+
+    if is_logo(&context) || is_logo(href) {
+      srcs.push(href.to_string());
+    }
+  }
+
+  srcs
+}
+
+fn is_logo(text: &str) -> bool {
+  regex!(r"(?i)logo|brand").is_match(text).unwrap_or(false)
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum IconFormat {
+  Png,
+  Jpeg,
+  Ico,
+  Gif,
+  Webp,
+  Avif,
+  Svg,
+}
+
+impl IconFormat {
+  /// Detect the format from the magic bytes of the data,
+  /// rather than trusting the url / content type
+  pub fn detect(data: &[u8]) -> Option<Self> {
+    Some(match data {
+      [0x89, b'P', b'N', b'G', ..] => IconFormat::Png,
+      [0xFF, 0xD8, 0xFF, ..] => IconFormat::Jpeg,
+      [0x00, 0x00, 0x01, 0x00, ..] => IconFormat::Ico,
+      [b'G', b'I', b'F', b'8', ..] => IconFormat::Gif,
+      [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => IconFormat::Webp,
+      [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f' | b's', ..] => IconFormat::Avif,
+      _ => {
+        // svgs can start with an xml declaration, comments or a doctype
+        let head = String::from_utf8_lossy(&data[..data.len().min(1024)]).to_lowercase();
+        if head.trim_start().starts_with('<') && head.contains("<svg") {
+          IconFormat::Svg
+        } else {
+          return None;
+        }
+      }
+    })
+  }
+
+  /// The format of a mime type, eg. a `<source type="image/webp">`
+  pub fn from_mime(mime: &str) -> Option<Self> {
+    let mime = mime.split(';').next()?.trim().to_lowercase();
+
+    Some(match mime.as_str() {
+      "image/png" => IconFormat::Png,
+      "image/jpeg" | "image/jpg" => IconFormat::Jpeg,
+      "image/x-icon" | "image/vnd.microsoft.icon" => IconFormat::Ico,
+      "image/gif" => IconFormat::Gif,
+      "image/webp" => IconFormat::Webp,
+      "image/avif" => IconFormat::Avif,
+      "image/svg+xml" => IconFormat::Svg,
+      _ => return None,
+    })
+  }
+
+  pub fn mime(&self) -> &'static str {
+    match self {
+      IconFormat::Png => "image/png",
+      IconFormat::Jpeg => "image/jpeg",
+      IconFormat::Ico => "image/x-icon",
+      IconFormat::Gif => "image/gif",
+      IconFormat::Webp => "image/webp",
+      IconFormat::Avif => "image/avif",
+      IconFormat::Svg => "image/svg+xml",
+    }
+  }
+
+  /// The format of a file extension, without the dot
+  pub fn from_extension(extension: &str) -> Option<Self> {
+    Some(match &extension.to_lowercase()[..] {
+      "png" => IconFormat::Png,
+      "jpg" | "jpeg" => IconFormat::Jpeg,
+      "ico" => IconFormat::Ico,
+      "gif" => IconFormat::Gif,
+      "webp" => IconFormat::Webp,
+      "avif" => IconFormat::Avif,
+      "svg" => IconFormat::Svg,
+      _ => return None,
+    })
+  }
+
+  /// The file extension, without the dot
+  pub fn extension(&self) -> &'static str {
+    match self {
+      IconFormat::Png => "png",
+      IconFormat::Jpeg => "jpg",
+      IconFormat::Ico => "ico",
+      IconFormat::Gif => "gif",
+      IconFormat::Webp => "webp",
+      IconFormat::Avif => "avif",
+      IconFormat::Svg => "svg",
+    }
+  }
+}
@@ -0,0 +1,68 @@
+use crate::RepoIconsBuilder;
+use url::Url;
+
+/// The devicon names of github's languages, where they differ from the lowercased language
+static DEVICON_NAMES: [(&str, &str); 7] = [
+  ("c++", "cplusplus"),
+  ("c#", "csharp"),
+  ("shell", "bash"),
+  ("vue", "vuejs"),
+  ("objective-c", "objectivec"),
+  ("html", "html5"),
+  ("css", "css3"),
+];
+
+/// The languages with a devicon logo
+static DEVICON_LANGUAGES: [&str; 27] = [
+  "bash",
+  "c",
+  "clojure",
+  "cplusplus",
+  "crystal",
+  "csharp",
+  "css3",
+  "dart",
+  "elixir",
+  "elm",
+  "erlang",
+  "go",
+  "haskell",
+  "html5",
+  "java",
+  "javascript",
+  "julia",
+  "kotlin",
+  "lua",
+  "objectivec",
+  "php",
+  "python",
+  "ruby",
+  "rust",
+  "scala",
+  "swift",
+  "typescript",
+];
+
+/// The logo of the repo's primary language, from the caller's overrides or devicon
+pub(crate) fn language_logo_url(options: &RepoIconsBuilder, language: &str) -> Option<Url> {
+  let language = language.to_lowercase();
+  if let Some(url) = options.language_logos.get(&language) {
+    return Some(url.clone());
+  }
+
+  let name = DEVICON_NAMES
+    .iter()
+    .find(|(github_name, _)| *github_name == language)
+    .map(|(_, devicon_name)| *devicon_name)
+    .unwrap_or(&language);
+
+  if !DEVICON_LANGUAGES.contains(&name) {
+    return None;
+  }
+
+  Url::parse(&format!(
+    "https://cdn.jsdelivr.net/gh/devicons/devicon/icons/{0}/{0}-original.svg",
+    name
+  ))
+  .ok()
+}
@@ -27,13 +27,66 @@ extern crate futures;
 
 #[macro_use]
 mod macros;
+mod aspect;
 mod blacklist;
+mod candidate_source;
+mod client;
+mod color_scheme;
+#[cfg(feature = "compact")]
+mod compact;
+#[cfg(all(feature = "download", not(target_arch = "wasm32")))]
+mod download;
+mod explain;
+mod favicons;
+mod feed;
 mod github_api;
+mod homepage_backgrounds;
+mod icon_format;
+mod language_logo;
+#[cfg(all(feature = "markdown", not(target_arch = "wasm32")))]
+mod local_repo;
+mod observer;
+mod output_format;
+mod primary_icon;
+mod ranked_icon;
+mod ranking_snapshot;
 mod repo_icon;
 mod repo_icons;
+mod repo_icons_builder;
+#[cfg(feature = "cache")]
+mod repo_icons_cache;
+mod request_budget;
+mod request_estimate;
+mod sort_strategy;
+mod svg_size;
 
+pub use aspect::*;
+pub use candidate_source::*;
+pub use client::{
+  get_token, get_user_agent, set_token, set_token_provider, set_user_agent, HttpClient,
+  TokenProvider,
+};
+pub use color_scheme::*;
+#[cfg(all(feature = "download", not(target_arch = "wasm32")))]
+pub use download::DownloadedIcon;
+pub use explain::*;
+pub use favicons::*;
+pub use feed::*;
 pub use gh_api::*;
 pub use github_api::readme::*;
+pub use github_api::{get_rate_limit, RateLimit};
+pub use icon_format::*;
+pub use observer::{Phase, RepoIconsObserver};
+pub use output_format::OutputFormat;
+pub use primary_icon::{PrimaryIcon, PrimaryPreference};
+pub use ranked_icon::RankedIcon;
+pub use ranking_snapshot::*;
 pub use repo_icon::*;
 pub use repo_icons::*;
+pub use repo_icons_builder::*;
+#[cfg(feature = "cache")]
+pub use repo_icons_cache::*;
+pub use request_estimate::RequestEstimate;
 pub use site_icons::*;
+pub use sort_strategy::*;
+pub use svg_size::VectorSize;
@@ -0,0 +1,152 @@
+use crate::{
+  github_api::{best_icon_file, is_icon_path, Readme},
+  ColorScheme, IconFormat, RepoIcon, RepoIconKind, RepoIcons,
+};
+use itertools::Itertools;
+use percent_encoding::{percent_encode, NON_ALPHANUMERIC};
+use reqwest::header::HeaderMap;
+use site_icons::IconInfo;
+use std::{error::Error, fs, path::Path};
+use url::Url;
+
+/// how deep the conventional logo files are searched for
+const MAX_DEPTH: usize = 4;
+const SKIPPED_DIRS: [&str; 3] = ["node_modules", "target", "vendor"];
+
+impl RepoIcons {
+  /// Load the icons of a repo checked out on disk without any requests, eg. in
+  /// CI or air-gapped pipelines. The readme is rendered locally, and its logo &
+  /// the repo's conventional logo files are read from the directory. Only
+  /// files inside the directory (as `file://` urls) and data uris in the
+  /// readme are loaded, as no requests are made
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIcons;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIcons::load_from_path("./my-repo").await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_from_path<P: AsRef<Path>>(dir: P) -> Result<Self, Box<dyn Error>> {
+    let readme = Readme::load_from_dir(dir.as_ref())?;
+    let dir = dir.as_ref().canonicalize()?;
+
+    let mut candidates = Vec::new();
+
+    if let Some(url) = readme
+      .declared_icon
+      .as_ref()
+      .and_then(|declared_icon| readme.qualify_url(declared_icon).ok())
+    {
+      candidates.push((url, RepoIconKind::Declared, ColorScheme::Any, None));
+    }
+
+    if let Some(image) = readme
+      .images_offline()
+      .await
+      .into_iter()
+      .find(|image| image.is_logo_candidate())
+    {
+      for source in &image.picture_sources {
+        let kind = RepoIconKind::ReadmeImage;
+        candidates.push((
+          source.src.clone(),
+          kind,
+          source.color_scheme,
+          image.alt.clone(),
+        ));
+      }
+      candidates.push((
+        image.src,
+        RepoIconKind::ReadmeImage,
+        image.color_scheme,
+        image.alt,
+      ));
+    }
+
+    let files = icon_files(&dir, "", 0);
+    if let Some(path) = best_icon_file("", &readme.repo, files.into_iter(), |path| path) {
+      if let Ok(url) = readme.qualify_url(&path) {
+        candidates.push((url, RepoIconKind::Blob(None), ColorScheme::Any, None));
+      }
+    }
+
+    let mut icons = Vec::new();
+    for (url, kind, color_scheme, label) in
+      candidates.into_iter().unique_by(|(url, ..)| url.clone())
+    {
+      match local_icon(&url).await {
+        Ok((info, data)) => {
+          let mut icon = RepoIcon::new(url, kind, info);
+          icon.color_scheme = color_scheme;
+          icon.label = label;
+          icon.sourced_from_repo = icon.url.scheme() == "file";
+          if icon.is_svg() {
+            icon.read_svg(&data).await;
+          }
+          icons.push(icon);
+        }
+        Err(err) => warn!("skipping local icon {} {}", url, err),
+      }
+    }
+
+    icons.sort_by(|a, b| a.info.cmp(&b.info));
+    icons.sort_by(|a, b| a.kind.cmp(&b.kind));
+
+    RepoIcons::from_icons(icons).ok_or_else(|| "no icons found for repo".into())
+  }
+}
+
+/// The info of a file or data uri, with its contents. Files are read through
+/// a data uri, as `site_icons` only fetches over http
+async fn local_icon(url: &Url) -> Result<(IconInfo, Vec<u8>), Box<dyn Error>> {
+  let data = match url.scheme() {
+    "file" => {
+      let path = url.to_file_path().map_err(|_| "invalid file url")?;
+      fs::read(path)?
+    }
+    "data" => {
+      let info = IconInfo::load(url.clone(), HeaderMap::new(), None).await?;
+      return Ok((info, Vec::new()));
+    }
+    _ => return Err("only files in the repo are loaded".into()),
+  };
+
+  let mime = IconFormat::detect(&data)
+    .ok_or("unrecognized icon format")?
+    .mime();
+  let data_url = format!("data:{},{}", mime, percent_encode(&data, NON_ALPHANUMERIC));
+  let info = IconInfo::load(Url::parse(&data_url)?, HeaderMap::new(), None).await?;
+
+  Ok((info, data))
+}
+
+/// The paths of the image files in the repo, relative to its root
+fn icon_files(dir: &Path, prefix: &str, depth: usize) -> Vec<String> {
+  let entries = match fs::read_dir(dir) {
+    Ok(entries) => entries,
+    Err(_) => return Vec::new(),
+  };
+
+  let mut files = Vec::new();
+  for entry in entries.filter_map(|entry| entry.ok()) {
+    let name = entry.file_name().to_string_lossy().into_owned();
+    let path = format!("{}{}", prefix, name);
+    let file_type = match entry.file_type() {
+      Ok(file_type) => file_type,
+      Err(_) => continue,
+    };
+
+    if file_type.is_dir() {
+      if depth < MAX_DEPTH && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+        files.extend(icon_files(&entry.path(), &format!("{}/", path), depth + 1));
+      }
+    } else if file_type.is_file() && is_icon_path(&path.to_lowercase()) {
+      files.push(path);
+    }
+  }
+
+  // read_dir's order depends on the filesystem
+  files.sort();
+  files
+}
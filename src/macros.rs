@@ -23,11 +23,3 @@ macro_rules! regexes {
       [$(fancy_regex::Regex::new($x).unwrap()),+]
     );
   }
-
-macro_rules! warn_err {
-  ($result:expr, $($arg:tt)*) => {{
-    if let Err(err) = $result {
-      warn!("{} {}", format!($($arg)*), err);
-    }
-  }};
-}
@@ -0,0 +1,125 @@
+use crate::{client::execute, RepoIconsBuilder};
+use futures::Future;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, time::Duration};
+use url::Url;
+
+/// The stages of loading the icons, reported to [`RepoIconsObserver::on_phase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+  /// the repo metadata & readme
+  Readme,
+  /// the icons on the repo's homepage
+  Website,
+  /// the icons in the repo's files
+  Blob,
+  SocialPreview,
+  /// the icons of repos the repo name is prefixed with
+  PrefixedRepos,
+  /// probing the sizes of the icons found
+  Sizes,
+}
+
+/// Hooks into the requests made while loading the icons, eg. for metrics.
+///
+/// Requests made by `site_icons` (favicons & sizes) are only reported as
+/// part of a [`Phase`]. Durations are always zero on wasm
+///
+/// ```no_run
+/// # use reqwest::{StatusCode, Url};
+/// # use repo_icons::{RepoIconsBuilder, RepoIconsObserver};
+/// # use std::{sync::{atomic::{AtomicUsize, Ordering}, Arc}, time::Duration};
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// struct RequestCounter(AtomicUsize);
+///
+/// impl RepoIconsObserver for RequestCounter {
+///   fn on_request(&self, _url: &Url, _duration: Duration, _status: Option<StatusCode>) {
+///     self.0.fetch_add(1, Ordering::Relaxed);
+///   }
+/// }
+///
+/// let counter = Arc::new(RequestCounter(AtomicUsize::new(0)));
+/// let icons = RepoIconsBuilder::new()
+///   .observer(counter.clone())
+///   .load("facebook", "react")
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait RepoIconsObserver: Send + Sync {
+  /// `status` is `None` when the request failed before a response
+  fn on_request(&self, _url: &Url, _duration: Duration, _status: Option<StatusCode>) {}
+
+  fn on_phase(&self, _phase: Phase, _duration: Duration) {}
+}
+
+/// Send a request, reporting it to the observer
+pub(crate) async fn observe_request(
+  options: &RepoIconsBuilder,
+  request: RequestBuilder,
+) -> Result<Response, Box<dyn Error>> {
+  let observer = match &options.observer {
+    Some(observer) => observer,
+    None => return execute(options, request).await,
+  };
+
+  let timer = Timer::start();
+  let res = execute(options, request).await;
+
+  match &res {
+    Ok(res) => observer.on_request(res.url(), timer.elapsed(), Some(res.status())),
+    Err(err) => {
+      if let Some(url) = err
+        .downcast_ref::<reqwest::Error>()
+        .and_then(|err| err.url())
+      {
+        observer.on_request(url, timer.elapsed(), None)
+      }
+    }
+  }
+
+  res
+}
+
+/// Run a phase of loading the icons, reporting how long it took to the observer
+pub(crate) async fn observe_phase<F: Future>(
+  options: &RepoIconsBuilder,
+  phase: Phase,
+  future: F,
+) -> F::Output {
+  let observer = match &options.observer {
+    Some(observer) => observer,
+    None => return future.await,
+  };
+
+  let timer = Timer::start();
+  let output = future.await;
+  observer.on_phase(phase, timer.elapsed());
+
+  output
+}
+
+/// `Instant` panics on wasm, so nothing is timed there
+struct Timer {
+  #[cfg(not(target_arch = "wasm32"))]
+  start: std::time::Instant,
+}
+
+impl Timer {
+  fn start() -> Self {
+    Self {
+      #[cfg(not(target_arch = "wasm32"))]
+      start: std::time::Instant::now(),
+    }
+  }
+
+  fn elapsed(&self) -> Duration {
+    #[cfg(not(target_arch = "wasm32"))]
+    return self.start.elapsed();
+
+    #[cfg(target_arch = "wasm32")]
+    return Duration::ZERO;
+  }
+}
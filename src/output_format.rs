@@ -0,0 +1,116 @@
+use crate::{RepoIcon, RepoIcons};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How [`RepoIcons::format`] prints the icons, eg. for a CLI
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+  /// only the url of the highest ranked icon
+  Url,
+  /// the icons as serialized by serde
+  Json,
+  /// aligned columns of the source, weight, size & url of each icon
+  #[default]
+  Table,
+  /// the table's columns, with a header row
+  Csv,
+}
+
+impl FromStr for OutputFormat {
+  type Err = String;
+
+  fn from_str(format: &str) -> Result<Self, Self::Err> {
+    Ok(match format {
+      "url" => OutputFormat::Url,
+      "json" => OutputFormat::Json,
+      "table" => OutputFormat::Table,
+      "csv" => OutputFormat::Csv,
+      format => return Err(format!("unknown output format {}", format)),
+    })
+  }
+}
+
+const COLUMNS: [&str; 4] = ["source", "weight", "size", "url"];
+
+impl RepoIcons {
+  /// Print the icons in one of the [`OutputFormat`]s
+  ///
+  /// ```no_run
+  /// # use repo_icons::{OutputFormat, RepoIcons};
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIcons::load("facebook", "react").await?;
+  /// println!("{}", icons.format(OutputFormat::Table));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn format(&self, format: OutputFormat) -> String {
+    match format {
+      OutputFormat::Url => self.closest_match().url.to_string(),
+      OutputFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+      OutputFormat::Table => {
+        let rows = self.iter().map(row).collect::<Vec<_>>();
+        let widths = (0..COLUMNS.len() - 1)
+          .map(|i| {
+            rows
+              .iter()
+              .map(|row| row[i].len())
+              .chain([COLUMNS[i].len()])
+              .max()
+              .unwrap_or_default()
+          })
+          .collect::<Vec<_>>();
+
+        let format_row = |row: &[String]| {
+          let mut line = widths
+            .iter()
+            .zip(row)
+            .map(|(width, cell)| format!("{:<width$}  ", cell, width = width))
+            .collect::<String>();
+          line.push_str(&row[COLUMNS.len() - 1]);
+          line
+        };
+
+        let header = COLUMNS.map(String::from);
+        std::iter::once(format_row(&header))
+          .chain(rows.iter().map(|row| format_row(row)))
+          .collect::<Vec<_>>()
+          .join("\n")
+      }
+      OutputFormat::Csv => std::iter::once(COLUMNS.join(","))
+        .chain(self.iter().map(|icon| {
+          row(icon)
+            .iter()
+            .map(|cell| csv_escape(cell))
+            .collect::<Vec<_>>()
+            .join(",")
+        }))
+        .collect::<Vec<_>>()
+        .join("\n"),
+    }
+  }
+}
+
+/// The table's cells for an icon. The weight is only known for readme images
+/// loaded with [`crate::RepoIconsBuilder::debug`]
+fn row(icon: &RepoIcon) -> [String; 4] {
+  let weight = match &icon.debug {
+    Some(debug) => debug.weight.to_string(),
+    None => "-".to_string(),
+  };
+  let size = match icon.dimensions() {
+    Some((width, height)) => format!("{}x{}", width, height),
+    None if icon.is_vector() => "svg".to_string(),
+    None => "-".to_string(),
+  };
+
+  [icon.kind.to_string(), weight, size, icon.url.to_string()]
+}
+
+fn csv_escape(cell: &str) -> String {
+  if cell.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", cell.replace('"', "\"\""))
+  } else {
+    cell.to_string()
+  }
+}
@@ -0,0 +1,100 @@
+use crate::{Aspect, RepoIcon, RepoIconKind, Signal};
+use serde::{Deserialize, Serialize};
+use site_icons::IconKind;
+
+/// The icon for a repo, with how confident the ranking is, see [`crate::RepoIcons::primary`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrimaryIcon {
+  pub icon: RepoIcon,
+  /// from 0 to 1, low for fallbacks like the owner's avatar
+  pub confidence: f32,
+}
+
+/// Whether [`crate::RepoIcons::primary`] picks a square logo or a wide banner,
+/// when the repo has one. Otherwise the highest ranked icon is picked
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PrimaryPreference {
+  /// the highest ranked square icon, other than an avatar or the social preview
+  Logo,
+  /// the highest ranked wide icon, or the social preview
+  Banner,
+  /// the highest ranked icon, by weight
+  #[default]
+  Auto,
+}
+
+impl PrimaryPreference {
+  pub(crate) fn matches(&self, icon: &RepoIcon) -> bool {
+    match self {
+      PrimaryPreference::Logo => {
+        icon.aspect() == Some(Aspect::Square)
+          && !matches!(
+            icon.kind,
+            RepoIconKind::UserAvatar
+              | RepoIconKind::OrgAvatar
+              | RepoIconKind::SocialPreview
+              | RepoIconKind::LanguageLogo
+          )
+      }
+      PrimaryPreference::Banner => {
+        icon.kind == RepoIconKind::SocialPreview
+          || matches!(icon.aspect(), Some(Aspect::Wide | Aspect::Ultrawide))
+      }
+      PrimaryPreference::Auto => true,
+    }
+  }
+}
+
+/// How the chosen readme logo scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LogoScore {
+  pub weight: u8,
+  /// the weight of the next best logo candidate
+  pub runner_up: Option<u8>,
+  /// whether the logo mentions "logo" and is in the primary heading
+  pub strong: bool,
+}
+
+impl LogoScore {
+  pub fn new(weight: u8, runner_up: Option<u8>, signals: &[Signal]) -> Self {
+    LogoScore {
+      weight,
+      runner_up,
+      strong: signals.contains(&Signal::LogoMention) && signals.contains(&Signal::InPrimaryHeading),
+    }
+  }
+
+  /// see [`crate::RepoIcons::primary`] for the formula
+  fn confidence(&self) -> f32 {
+    let weight = self.weight as f32;
+    let strength = weight / (weight + 16.0);
+    let margin = match self.runner_up {
+      Some(runner_up) if self.weight > 0 => self.weight.saturating_sub(runner_up) as f32 / weight,
+      Some(_) => 0.0,
+      None => 1.0,
+    };
+    let strong = if self.strong { 0.1 } else { 0.0 };
+
+    0.4 + 0.3 * strength + 0.2 * margin + strong
+  }
+}
+
+/// The confidence of an icon of each kind being the repo's icon. Readme logos are
+/// scored from their [`LogoScore`] when known, declared icons are always trusted
+/// and github's generated default avatars hardly at all
+pub(crate) fn confidence(icon: &RepoIcon, logo_score: Option<LogoScore>) -> f32 {
+  match &icon.kind {
+    RepoIconKind::Declared => 1.0,
+    RepoIconKind::IconField(_) => 0.95,
+    RepoIconKind::ReadmeImage => logo_score.map_or(0.6, |score| score.confidence()),
+    RepoIconKind::Blob(_) => 0.7,
+    RepoIconKind::Site(IconKind::SiteLogo | IconKind::AppIcon) => 0.6,
+    RepoIconKind::Site(IconKind::SiteFavicon) | RepoIconKind::MaskIcon => 0.5,
+    // a generated identicon says nothing about the repo
+    RepoIconKind::UserAvatar | RepoIconKind::OrgAvatar if icon.default_avatar => 0.05,
+    RepoIconKind::UserAvatar | RepoIconKind::OrgAvatar => 0.3,
+    RepoIconKind::SocialPreview => 0.2,
+    RepoIconKind::LanguageLogo => 0.1,
+  }
+}
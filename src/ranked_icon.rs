@@ -0,0 +1,117 @@
+use crate::{IconDebug, RepoIcon, RepoIconKind, ScoringProfile, Signal};
+use serde::{Deserialize, Serialize};
+use site_icons::IconKind;
+use std::cmp::Reverse;
+use url::Url;
+
+/// An icon with why it was ranked where it was, see [`crate::RepoIcons::top`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RankedIcon {
+  pub url: Url,
+  #[serde(with = "serde_with::rust::display_fromstr")]
+  pub kind: RepoIconKind,
+  /// only known for readme images loaded with [`crate::RepoIconsBuilder::debug`]
+  pub weight: Option<u8>,
+  /// the strongest signals, eg. "logo keyword in primary heading, links to website"
+  pub reason: String,
+}
+
+impl RankedIcon {
+  pub(crate) fn new(icon: &RepoIcon) -> Self {
+    let reason = match &icon.debug {
+      Some(debug) if !debug.signals.is_empty() => signals_reason(debug),
+      _ => kind_reason(&icon.kind).to_string(),
+    };
+
+    RankedIcon {
+      url: icon.url.clone(),
+      kind: icon.kind.clone(),
+      weight: icon.debug.as_ref().map(|debug| debug.weight),
+      reason,
+    }
+  }
+}
+
+/// The signals that added the most weight under the load's scoring profile,
+/// strongest first
+fn signals_reason(debug: &IconDebug) -> String {
+  let contributions = if debug.score_breakdown.is_empty() {
+    let profile = ScoringProfile::default();
+    debug
+      .signals
+      .iter()
+      .map(|signal| (*signal, profile.contribution(*signal)))
+      .collect()
+  } else {
+    debug.score_breakdown.clone()
+  };
+
+  let mut contributions = contributions
+    .into_iter()
+    .filter(|(_, contribution)| *contribution > 0)
+    .collect::<Vec<_>>();
+  contributions.sort_by_key(|(_, contribution)| Reverse(*contribution));
+  let mut signals = contributions
+    .iter()
+    .map(|(signal, _)| signal)
+    .collect::<Vec<_>>();
+
+  // a logo mention in the heading reads better as one reason
+  let logo_in_heading =
+    signals.contains(&&Signal::LogoMention) && signals.contains(&&Signal::InPrimaryHeading);
+  if logo_in_heading {
+    signals.retain(|signal| **signal != Signal::InPrimaryHeading);
+  }
+
+  let reasons = signals
+    .into_iter()
+    .take(3)
+    .map(|signal| match signal {
+      Signal::LogoMention if logo_in_heading => "logo keyword in primary heading",
+      signal => describe(*signal),
+    })
+    .collect::<Vec<_>>();
+
+  reasons.join(", ")
+}
+
+fn describe(signal: Signal) -> &'static str {
+  match signal {
+    Signal::InPrimaryHeading => "in primary heading",
+    Signal::AlignCenter => "centered",
+    Signal::SizeAttrs => "has a set size",
+    Signal::SourcedFromRepo => "file in the repo",
+    Signal::EdgeOfPrimaryHeading => "at the edge of the primary heading",
+    Signal::CenteredFirstImage => "centered first image",
+    Signal::LinksToWebsite => "links to website",
+    Signal::LinksToRepo => "links to repo",
+    Signal::LogoMention => "logo keyword",
+    Signal::BannerMention => "banner keyword",
+    Signal::RepoNameMention => "mentions the repo name",
+    Signal::TopicMention => "mentions a topic",
+    Signal::SquareAspect => "square",
+    Signal::UltrawideAspect => "ultrawide",
+    Signal::InCollapsedDetails => "in a collapsed section",
+    Signal::NearDescription => "next to the description",
+    Signal::DocumentPosition => "near the top of the readme",
+    Signal::InSecondaryReadme => "in a secondary readme",
+    Signal::TechStackRow => "in a row of tech stack logos",
+  }
+}
+
+fn kind_reason(kind: &RepoIconKind) -> &'static str {
+  match kind {
+    RepoIconKind::Declared => "declared by the maintainers",
+    RepoIconKind::IconField(_) => "the icon field of the repo's manifest",
+    RepoIconKind::UserAvatar => "the owner's avatar",
+    RepoIconKind::OrgAvatar => "the organization's avatar",
+    RepoIconKind::Blob(_) => "an icon file in the repo",
+    RepoIconKind::ReadmeImage => "an image in the readme",
+    RepoIconKind::Site(IconKind::SiteLogo) => "the website's logo",
+    RepoIconKind::Site(IconKind::AppIcon) => "the website's app icon",
+    RepoIconKind::Site(IconKind::SiteFavicon) => "the website's favicon",
+    RepoIconKind::MaskIcon => "the website's pinned tab icon",
+    RepoIconKind::SocialPreview => "the repo's social preview",
+    RepoIconKind::LanguageLogo => "the logo of the repo's language",
+  }
+}
@@ -0,0 +1,15 @@
+use crate::{ReadmeImage, RepoIcons};
+use site_icons::Icon;
+
+/// The ranking at a point of [`crate::RepoIconsBuilder::load_progressive`],
+/// in the order they're emitted
+#[derive(Debug)]
+pub enum RankingSnapshot {
+  /// the readme images ordered by weight alone, before any sizes are known
+  Readme(Vec<ReadmeImage>),
+  /// the readme's logo & the homepage's icons once their sizes are known, by
+  /// size. The repo's files & prefixed repos are still loading
+  Sizes(Vec<Icon>),
+  /// the final icons, the same as [`crate::RepoIconsBuilder::load`]
+  Complete(RepoIcons),
+}
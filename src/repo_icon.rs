@@ -1,10 +1,23 @@
-use bytes::Bytes;
+use crate::{
+  client::{client, get_token, send},
+  github_api::raw_file_url,
+  svg_size::{embedded_raster, parse_svg_size},
+  Aspect, ColorScheme, IconFormat, RepoIconsBuilder, Signal, VectorSize,
+};
+use bytes::{Bytes, BytesMut};
 use data_url::DataUrl;
-use gh_api::get_token;
+use futures::StreamExt;
 #[cfg(feature = "image")]
-use image::{io::Reader as ImageReader, DynamicImage, ImageFormat};
+use image::{
+  imageops::{self, FilterType},
+  io::Reader as ImageReader,
+  DynamicImage, ImageOutputFormat, RgbaImage,
+};
 use maplit::hashmap;
+use reqwest::header::HeaderMap;
 use site_icons::{IconInfo, IconKind};
+#[cfg(feature = "image")]
+use std::{cell::RefCell, io::Cursor, rc::Rc};
 use std::{
   cmp::Ordering,
   collections::HashMap,
@@ -15,7 +28,7 @@ use std::{
 };
 use url::Url;
 
-#[derive(Debug, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct RepoBlob {
   pub owner: String,
   pub repo: String,
@@ -43,23 +56,37 @@ impl PartialEq for RepoBlob {
   }
 }
 
-#[derive(Debug, PartialOrd, PartialEq, Ord, Eq)]
+#[derive(Debug, Clone, PartialOrd, PartialEq, Ord, Eq)]
 pub enum RepoIconKind {
+  /// declared by the maintainers in the readme
+  Declared,
   IconField(Option<RepoBlob>),
   UserAvatar,
+  OrgAvatar,
   Blob(Option<RepoBlob>),
   ReadmeImage,
   Site(IconKind),
+  /// safari's pinned tab icon, a monochrome svg declared by the homepage with
+  /// `<link rel="mask-icon">`
+  MaskIcon,
+  SocialPreview,
+  /// the logo of the repo's primary language, when there's nothing better
+  LanguageLogo,
 }
 
 impl Display for RepoIconKind {
   fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
     match self {
+      RepoIconKind::Declared => write!(f, "declared"),
       RepoIconKind::IconField(_) => write!(f, "icon_field"),
       RepoIconKind::ReadmeImage => write!(f, "readme_image"),
       RepoIconKind::UserAvatar => write!(f, "user_avatar"),
+      RepoIconKind::OrgAvatar => write!(f, "org_avatar"),
       RepoIconKind::Blob(_) => write!(f, "blob"),
       RepoIconKind::Site(kind) => write!(f, "{}", kind),
+      RepoIconKind::MaskIcon => write!(f, "mask_icon"),
+      RepoIconKind::SocialPreview => write!(f, "social_preview"),
+      RepoIconKind::LanguageLogo => write!(f, "language_logo"),
     }
   }
 }
@@ -69,17 +96,33 @@ impl FromStr for RepoIconKind {
 
   fn from_str(kind: &str) -> Result<Self, Self::Err> {
     Ok(match kind {
+      "declared" => RepoIconKind::Declared,
       "icon_field" => RepoIconKind::IconField(None),
       "readme_image" => RepoIconKind::ReadmeImage,
       "user_avatar" => RepoIconKind::UserAvatar,
+      "org_avatar" => RepoIconKind::OrgAvatar,
       "blob" => RepoIconKind::Blob(None),
+      "social_preview" => RepoIconKind::SocialPreview,
+      "language_logo" => RepoIconKind::LanguageLogo,
+      "mask_icon" => RepoIconKind::MaskIcon,
       kind => RepoIconKind::Site(IconKind::from_str(kind)?),
     })
   }
 }
 
+/// How a readme image was scored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IconDebug {
+  pub weight: u8,
+  pub signals: Vec<Signal>,
+  /// the points each signal added under the load's scoring profile, empty
+  /// for icons debugged by older versions
+  #[serde(default)]
+  pub score_breakdown: Vec<(Signal, i16)>,
+}
+
 #[derive(Derivative, Serialize, Deserialize)]
-#[derivative(Debug, PartialEq, Eq)]
+#[derivative(Debug, Clone, PartialEq, Eq)]
 pub struct RepoIcon {
   pub url: Url,
   pub headers: HashMap<String, String>,
@@ -88,6 +131,35 @@ pub struct RepoIcon {
   pub kind: RepoIconKind,
   #[serde(flatten)]
   pub info: IconInfo,
+  #[serde(default)]
+  pub color_scheme: ColorScheme,
+  /// the size declared by an svg's dimensions / viewBox, or the resolution
+  /// of the raster a pseudo vector embeds
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub vector_size: Option<VectorSize>,
+  /// whether the icon is an svg that only wraps an embedded raster, so it's
+  /// ranked as the raster rather than as a vector
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub pseudo_vector: bool,
+  /// whether the icon is the identicon github generates for owners that
+  /// haven't uploaded an avatar. Only detected with the `image` feature
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub default_avatar: bool,
+  /// the `color` of a [`RepoIconKind::MaskIcon`], that safari fills it with
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub mask_color: Option<String>,
+  /// whether the icon is a file in the repo, rather than eg. a cdn link,
+  /// favicon or avatar
+  #[serde(default)]
+  pub sourced_from_repo: bool,
+  /// a description of the icon for its `alt`, the first of the homepage's
+  /// `og:image:alt`, the readme image's alt, the homepage's `<title>` for
+  /// favicons and the owner's login for avatars
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+  /// only included when loaded with [`crate::RepoIconsBuilder::debug`]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub debug: Option<IconDebug>,
 
   #[cfg(feature = "image")]
   #[serde(skip)]
@@ -103,11 +175,7 @@ impl RepoIcon {
     if let Blob(Some(blob)) | IconField(Some(blob)) = &mut self.kind {
       if !is_private {
         self.headers.clear();
-        self.url = Url::parse(&format!(
-          "https://raw.githubusercontent.com/{}/{}/{}/{}",
-          blob.owner, blob.repo, blob.commit_sha, blob.path
-        ))
-        .unwrap();
+        self.url = raw_file_url(&blob.owner, &blob.repo, &blob.commit_sha, &blob.path);
       }
     }
   }
@@ -138,6 +206,152 @@ impl RepoIcon {
     ))
   }
 
+  pub fn aspect(&self) -> Option<Aspect> {
+    let (width, height) = self.dimensions()?;
+    Aspect::from_size(width, height)
+  }
+
+  /// Whether the icon needs the token in its headers to load
+  pub fn is_auth_gated(&self) -> bool {
+    self
+      .headers
+      .keys()
+      .any(|header| header.eq_ignore_ascii_case("authorization"))
+  }
+
+  /// A key for the icon that's stable across loads, eg. to store it in a
+  /// database & notice when a repo's icon changes. Urls that only differ by
+  /// the order of their query or their fragment share a fingerprint, and repo
+  /// files are keyed by their content's sha rather than the commit
+  pub fn fingerprint(&self) -> String {
+    let key = match &self.kind {
+      RepoIconKind::Blob(Some(blob)) | RepoIconKind::IconField(Some(blob)) => format!(
+        "blob:{}/{}/{}",
+        blob.owner.to_lowercase(),
+        blob.repo.to_lowercase(),
+        blob.sha
+      ),
+      _ => {
+        let mut url = self.url.clone();
+        url.set_fragment(None);
+
+        let mut query = self.url.query_pairs().into_owned().collect::<Vec<_>>();
+        query.sort();
+        if query.is_empty() {
+          url.set_query(None);
+        } else {
+          url.query_pairs_mut().clear().extend_pairs(query);
+        }
+
+        url.to_string()
+      }
+    };
+
+    // fnv-1a, as std's hasher isn't guaranteed to be stable between releases
+    let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+      (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+  }
+
+  /// The sha of the icon's content, for repo files. Git shas only depend on
+  /// the bytes, so they're shared by the same file in forks & other branches
+  pub fn content_hash(&self) -> Option<&str> {
+    match &self.kind {
+      RepoIconKind::Blob(Some(blob)) | RepoIconKind::IconField(Some(blob)) => Some(&blob.sha),
+      _ => None,
+    }
+  }
+
+  /// Whether two icons are the same image, by their [`RepoIcon::content_hash`]
+  /// when both are known, otherwise by their [`RepoIcon::fingerprint`]
+  pub fn is_same_image(&self, other: &RepoIcon) -> bool {
+    match (self.content_hash(), other.content_hash()) {
+      (Some(a), Some(b)) => a == b,
+      _ => self.fingerprint() == other.fingerprint(),
+    }
+  }
+
+  /// The avatar at `size`x`size` pixels, so it doesn't need to be scaled down,
+  /// or `None` if the icon isn't an avatar
+  pub fn avatar_url(&self, size: u32) -> Option<Url> {
+    match self.kind {
+      RepoIconKind::UserAvatar | RepoIconKind::OrgAvatar => Some(sized_avatar_url(&self.url, size)),
+      _ => None,
+    }
+  }
+
+  /// Flag github's generated identicons, so they can be ranked as generic
+  pub(crate) async fn detect_default_avatar(&mut self, options: &RepoIconsBuilder) {
+    #[cfg(not(feature = "image"))]
+    let _ = options;
+
+    #[cfg(feature = "image")]
+    if matches!(
+      self.kind,
+      RepoIconKind::UserAvatar | RepoIconKind::OrgAvatar
+    ) {
+      match self.image_with(options).await {
+        Ok(image) => self.default_avatar = is_identicon(&image),
+        Err(err) => warn!("failed to check the avatar {}: {}", self.url, err),
+      }
+    }
+  }
+
+  /// Whether the icon is an svg, which scales losslessly.
+  /// Pseudo vectors that only embed a raster aren't
+  pub fn is_vector(&self) -> bool {
+    self.is_svg() && !self.pseudo_vector
+  }
+
+  pub(crate) fn is_svg(&self) -> bool {
+    matches!(self.info, IconInfo::SVG)
+  }
+
+  /// The `(width, height)` of the icon, or the notional size of a vector icon
+  pub fn dimensions(&self) -> Option<(u32, u32)> {
+    match self.info.size() {
+      Some(size) => Some((size.width, size.height)),
+      None => self.vector_size.map(|size| (size.width, size.height)),
+    }
+  }
+
+  /// Fetch a vector icon to read its notional size, and whether it's a
+  /// pseudo vector
+  pub async fn load_vector_size(&mut self) -> Result<(), Box<dyn Error>> {
+    self.load_vector_size_with(&RepoIconsBuilder::new()).await
+  }
+
+  pub async fn load_vector_size_with(
+    &mut self,
+    options: &RepoIconsBuilder,
+  ) -> Result<(), Box<dyn Error>> {
+    if !self.is_svg() {
+      return Ok(());
+    }
+
+    let data = self.data_with(options).await?;
+    self.read_svg(&data).await;
+    Ok(())
+  }
+
+  pub(crate) async fn read_svg(&mut self, data: &[u8]) {
+    let svg = String::from_utf8_lossy(data);
+    self.vector_size = parse_svg_size(&svg);
+
+    if let Some(raster) = embedded_raster(&svg) {
+      self.pseudo_vector = true;
+      if let Ok(info) = IconInfo::load(raster, HeaderMap::new(), None).await {
+        if let Some(size) = info.size() {
+          self.vector_size = Some(VectorSize {
+            width: size.width,
+            height: size.height,
+          });
+        }
+      }
+    }
+  }
+
   pub fn new(url: Url, kind: RepoIconKind, info: IconInfo) -> Self {
     Self::new_with_headers(url, HashMap::new(), kind, info)
   }
@@ -153,12 +367,55 @@ impl RepoIcon {
       headers,
       kind,
       info,
+      color_scheme: ColorScheme::Any,
+      vector_size: None,
+      pseudo_vector: false,
+      default_avatar: false,
+      mask_color: None,
+      sourced_from_repo: false,
+      label: None,
+      debug: None,
       #[cfg(feature = "image")]
       image: RefCell::new(None),
     }
   }
 
   pub async fn data(&self) -> Result<Bytes, Box<dyn Error>> {
+    self.data_with(&RepoIconsBuilder::new()).await
+  }
+
+  /// Download the icon with the load's client, user agent & request budget
+  pub async fn data_with(&self, options: &RepoIconsBuilder) -> Result<Bytes, Box<dyn Error>> {
+    self.download(options, None).await
+  }
+
+  /// Download the icon with its headers, erroring if it's larger than `max_bytes`.
+  /// The format is detected from the data itself
+  pub async fn fetch_bytes(&self, max_bytes: usize) -> Result<(Bytes, IconFormat), Box<dyn Error>> {
+    self
+      .fetch_bytes_with(&RepoIconsBuilder::new(), max_bytes)
+      .await
+  }
+
+  pub async fn fetch_bytes_with(
+    &self,
+    options: &RepoIconsBuilder,
+    max_bytes: usize,
+  ) -> Result<(Bytes, IconFormat), Box<dyn Error>> {
+    let data = self.download(options, Some(max_bytes)).await?;
+    let format = IconFormat::detect(&data).ok_or("unrecognized icon format")?;
+
+    Ok((data, format))
+  }
+
+  async fn download(
+    &self,
+    options: &RepoIconsBuilder,
+    max_bytes: Option<usize>,
+  ) -> Result<Bytes, Box<dyn Error>> {
+    let max_bytes = max_bytes.unwrap_or(usize::MAX);
+    let too_large = || format!("icon is larger than {} bytes", max_bytes);
+
     if self.url.scheme() == "data" {
       let url = self.url.to_string();
       let data = DataUrl::process(&url).map_err(|_| "failed to parse data uri")?;
@@ -166,36 +423,174 @@ impl RepoIcon {
         .decode_to_vec()
         .map_err(|_| "invalid base64 in data uri")?;
 
+      if body.len() > max_bytes {
+        return Err(too_large().into());
+      }
+
       return Ok(body.into());
     }
 
-    let res = reqwest::Client::new()
-      .get(self.url.clone())
-      .headers((&self.headers).try_into()?)
-      .send()
-      .await?;
+    let res = send(
+      options,
+      client(options)
+        .get(self.url.clone())
+        .headers((&self.headers).try_into()?),
+    )
+    .await?;
+
+    if res.content_length().unwrap_or(0) > max_bytes as u64 {
+      return Err(too_large().into());
+    }
+
+    // the content length can be missing, so the limit is also checked while streaming
+    let mut data = BytesMut::new();
+    let mut stream = res.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+      let chunk = chunk?;
+      if data.len() + chunk.len() > max_bytes {
+        return Err(too_large().into());
+      }
+      data.extend_from_slice(&chunk);
+    }
 
-    Ok(res.bytes().await?)
+    Ok(data.freeze())
   }
 
   #[cfg(feature = "image")]
   pub async fn image(&self) -> Result<Rc<DynamicImage>, Box<dyn Error>> {
+    self.image_with(&RepoIconsBuilder::new()).await
+  }
+
+  #[cfg(feature = "image")]
+  pub async fn image_with(
+    &self,
+    options: &RepoIconsBuilder,
+  ) -> Result<Rc<DynamicImage>, Box<dyn Error>> {
     if let Some(image) = self.image.borrow().clone() {
       return Ok(image);
     }
 
-    let data = self.data().await?;
-    let mut reader = ImageReader::new(Cursor::new(data));
+    let data = self.data_with(options).await?;
+    if IconFormat::detect(&data) == Some(IconFormat::Svg) {
+      return Err("svgs can't be decoded as an image, use render_png".into());
+    }
 
-    reader.set_format(match self.info {
-      IconInfo::PNG { .. } => ImageFormat::Png,
-      IconInfo::JPEG { .. } => ImageFormat::Jpeg,
-      IconInfo::ICO { .. } => ImageFormat::Ico,
-      IconInfo::SVG { .. } => return Err("not supported!".into()),
-    });
+    // the format is sniffed from the data, as the url / info can be wrong
+    let reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
 
     let image = Rc::new(reader.decode()?);
     *self.image.borrow_mut() = Some(image.clone());
     Ok(image)
   }
+
+  /// Render the icon as a `size`x`size` png, scaled to fit with transparent
+  /// padding to keep the aspect ratio. Svgs need the `svg` feature
+  #[cfg(feature = "image")]
+  pub async fn render_png(&self, size: u32) -> Result<Vec<u8>, Box<dyn Error>> {
+    self.render_png_with(&RepoIconsBuilder::new(), size).await
+  }
+
+  #[cfg(feature = "image")]
+  pub async fn render_png_with(
+    &self,
+    options: &RepoIconsBuilder,
+    size: u32,
+  ) -> Result<Vec<u8>, Box<dyn Error>> {
+    if size == 0 {
+      return Err("size must be at least 1".into());
+    }
+
+    if self.is_svg() {
+      #[cfg(feature = "svg")]
+      return render_svg(&self.data_with(options).await?, size);
+
+      #[cfg(not(feature = "svg"))]
+      return Err("rendering svgs needs the svg feature".into());
+    }
+
+    let image = self.image_with(options).await?;
+    let scaled = image.resize(size, size, FilterType::Lanczos3).to_rgba8();
+
+    let mut canvas = RgbaImage::new(size, size);
+    imageops::overlay(
+      &mut canvas,
+      &scaled,
+      ((size - scaled.width()) / 2) as i64,
+      ((size - scaled.height()) / 2) as i64,
+    );
+
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(canvas)
+      .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)?;
+    Ok(png)
+  }
+}
+
+#[cfg(feature = "svg")]
+fn render_svg(data: &[u8], size: u32) -> Result<Vec<u8>, Box<dyn Error>> {
+  use resvg::{tiny_skia, usvg};
+
+  let tree = usvg::Tree::from_data(data, &usvg::Options::default())?;
+  let (width, height) = (tree.size().width(), tree.size().height());
+  let scale = size as f32 / width.max(height);
+
+  let mut pixmap = tiny_skia::Pixmap::new(size, size).ok_or("invalid size")?;
+  let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+    (size as f32 - width * scale) / 2.0,
+    (size as f32 - height * scale) / 2.0,
+  );
+  resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+  Ok(pixmap.encode_png()?)
+}
+
+/// The size avatars are loaded at, github's largest
+pub(crate) const AVATAR_SIZE: u32 = 460;
+
+/// A github avatar with its size parameter set, `?size=` for `github.com/USER.png`
+/// and `?s=` for `avatars.githubusercontent.com`
+pub(crate) fn sized_avatar_url(url: &Url, size: u32) -> Url {
+  let param = match url.domain() {
+    Some("github.com") => "size",
+    _ => "s",
+  };
+
+  let mut url = url.clone();
+  let query = url
+    .query_pairs()
+    .into_owned()
+    .filter(|(name, _)| name != "s" && name != "size")
+    .collect::<Vec<_>>();
+  url
+    .query_pairs_mut()
+    .clear()
+    .extend_pairs(query)
+    .append_pair(param, &size.to_string());
+  url
+}
+
+/// Github's identicons are a horizontally symmetric 5x5 grid of one color on
+/// a `#f0f0f0` background, which uploaded avatars are very unlikely to match
+#[cfg(feature = "image")]
+fn is_identicon(image: &DynamicImage) -> bool {
+  let image = image.to_rgb8();
+  let (width, height) = image.dimensions();
+  if width != height || width == 0 {
+    return false;
+  }
+
+  let background = image::Rgb([0xf0, 0xf0, 0xf0]);
+  if image.get_pixel(0, 0) != &background {
+    return false;
+  }
+
+  let mut colors = std::collections::HashSet::new();
+  for (x, y, pixel) in image.enumerate_pixels() {
+    colors.insert(pixel.0);
+    if colors.len() > 2 || image.get_pixel(width - 1 - x, y) != pixel {
+      return false;
+    }
+  }
+
+  colors.len() == 2
 }
@@ -1,25 +1,71 @@
 use crate::{
-  blacklist::{is_badge, is_blacklisted_homepage},
-  get_token, github_api, RepoIcon, RepoIconKind,
+  blacklist::{is_allowed_host, is_badge, is_blacklisted_homepage},
+  client::{client, with_permit},
+  get_token,
+  github_api::{self, OwnerType},
+  homepage_backgrounds::load_homepage,
+  language_logo::language_logo_url,
+  observer::observe_phase,
+  primary_icon::{self, LogoScore},
+  repo_icon::{sized_avatar_url, AVATAR_SIZE},
+  Candidate, ColorScheme, Exclusion, IconDebug, Phase, PrimaryIcon, PrimaryPreference, RankedIcon,
+  RankingSnapshot, ReadmeImage, RepoContext, RepoIcon, RepoIconKind, RepoIconsBuilder,
+  SortStrategy, SourceWarning,
 };
 use async_recursion::async_recursion;
-use futures::future::join_all;
+use futures::{
+  future::{join, join_all},
+  Future, Stream,
+};
 use itertools::Itertools;
 use reqwest::{
   header::{HeaderMap, HeaderValue, AUTHORIZATION},
-  Client, IntoUrl, Url,
+  IntoUrl, Url,
 };
-use site_icons::{IconKind, Icons};
+use site_icons::{Icon, IconInfo, IconKind, Icons};
 use std::{
-  cmp::{max, min},
+  cell::{Cell, RefCell},
+  cmp::{max, min, Reverse},
   collections::HashMap,
   convert::TryInto,
   error::Error,
+  fmt::Display,
+  ops::Index,
 };
 use vec1::Vec1;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RepoIcons(Vec1<RepoIcon>);
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RepoIcons {
+  pub(crate) icons: Vec1<RepoIcon>,
+  #[serde(skip)]
+  pub(crate) truncated: bool,
+  #[serde(skip)]
+  pub(crate) private: bool,
+  #[serde(skip)]
+  pub(crate) candidates: Vec<Candidate>,
+  #[serde(skip)]
+  pub(crate) logo_score: Option<LogoScore>,
+  #[serde(skip)]
+  pub(crate) etag: Option<String>,
+  #[serde(skip)]
+  pub(crate) prefer_vector: bool,
+  #[serde(skip)]
+  pub(crate) primary_preference: PrimaryPreference,
+  #[serde(skip)]
+  pub(crate) warnings: Vec<SourceWarning>,
+  #[serde(skip)]
+  pub(crate) min_confidence: Option<f32>,
+}
+
+/// Icons grouped by their declared color scheme, each ranked the same as [`RepoIcons`]
+#[derive(Debug, Serialize)]
+pub struct ColorSchemeIcons<'a> {
+  pub light: Vec<&'a RepoIcon>,
+  pub dark: Vec<&'a RepoIcon>,
+  /// icons without a declared scheme, valid for both light & dark
+  pub any: Vec<&'a RepoIcon>,
+}
 
 impl RepoIcons {
   /// Fetch all the icons. Ordered from highest to lowest resolution
@@ -32,23 +78,393 @@ impl RepoIcons {
   ///   println("{:?}", icon)
   /// }
   /// ```
-  #[async_recursion(?Send)]
   pub async fn load(owner: &str, repo: &str) -> Result<Self, Box<dyn Error>> {
+    RepoIconsBuilder::new().load(owner, repo).await
+  }
+
+  /// Fetch only the repo metadata & readme, and return the top ranked readme logo.
+  ///
+  /// Skips the homepage, repo files, avatars and size detection, and extracts the
+  /// images offline (see [`crate::Readme::images_offline`]), so it's much faster
+  /// than [`RepoIcons::load`] but may return a lower quality icon, or none
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIcons;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// if let Some(image) = RepoIcons::load_fast("facebook", "react").await? {
+  ///   println!("{}", image.src)
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_fast(owner: &str, repo: &str) -> Result<Option<ReadmeImage>, Box<dyn Error>> {
+    RepoIconsBuilder::new().load_fast(owner, repo).await
+  }
+
+  /// Stream the ranking as it's refined, see [`RepoIconsBuilder::load_progressive`]
+  pub fn load_progressive(
+    owner: &str,
+    repo: &str,
+  ) -> impl Stream<Item = Result<RankingSnapshot, Box<dyn Error>>> {
+    RepoIconsBuilder::new().load_progressive(owner, repo)
+  }
+
+  /// Fetch the icon for a user / organization, see [`RepoIconsBuilder::load_owner`]
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIcons;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIcons::load_owner("facebook").await?;
+  /// println!("{:?}", icons.closest_match().kind);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_owner(login: &str) -> Result<Self, Box<dyn Error>> {
+    RepoIconsBuilder::new().load_owner(login).await
+  }
+
+  pub(crate) async fn load_owner_with(
+    options: &RepoIconsBuilder,
+    login: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    let owner = github_api::get_owner(options, login).await?;
+    let mut warnings = Vec::new();
+
+    // organizations can have a profile readme in their `.github` repo,
+    // which often has the org's logo
+    let profile_logo = match owner.owner_type {
+      OwnerType::Organization => {
+        match observe_phase(
+          options,
+          Phase::Readme,
+          github_api::Readme::load_path_with(options, login, ".github", "profile/README.md"),
+        )
+        .await
+        {
+          Ok(readme) => readme
+            .images()
+            .await
+            .into_iter()
+            .find(|image| image.is_logo_candidate()),
+          Err(err) => {
+            let not_found = err
+              .downcast_ref::<reqwest::Error>()
+              .and_then(|err| err.status())
+              == Some(reqwest::StatusCode::NOT_FOUND);
+            if !not_found {
+              warnings.push(SourceWarning {
+                source: Phase::Readme,
+                message: err.to_string(),
+              });
+            }
+            None
+          }
+        }
+      }
+      OwnerType::User | OwnerType::Bot => None,
+    };
+
+    let mut logo_icons = Icons::new();
+    if let Some(image) = &profile_logo {
+      let srcs =
+        std::iter::once(&image.src).chain(image.picture_sources.iter().map(|source| &source.src));
+      for src in srcs {
+        logo_icons.add_icon_with_headers(
+          src.clone(),
+          image.headers.clone(),
+          IconKind::SiteLogo,
+          None,
+        );
+      }
+    }
+
+    let avatar_url = sized_avatar_url(&owner.avatar_url, AVATAR_SIZE);
+    let (info, entries) = join(
+      with_permit(
+        options,
+        IconInfo::load(avatar_url.clone(), HeaderMap::new(), None),
+      ),
+      with_permit(options, logo_icons.entries()),
+    )
+    .await;
+    let info = info?;
+    let kind = match owner.owner_type {
+      OwnerType::Organization => RepoIconKind::OrgAvatar,
+      OwnerType::User | OwnerType::Bot => RepoIconKind::UserAvatar,
+    };
+
+    let mut icons = entries
+      .into_iter()
+      .filter(|entry| !is_badge(&entry.url))
+      .map(|entry| {
+        let image = profile_logo.as_ref().unwrap();
+        let color_scheme = image
+          .picture_sources
+          .iter()
+          .find(|source| source.src == entry.url)
+          .map_or(image.color_scheme, |source| source.color_scheme);
+
+        let mut icon = RepoIcon::new_with_headers(
+          entry.url,
+          entry.headers,
+          RepoIconKind::ReadmeImage,
+          entry.info,
+        );
+        icon.color_scheme = color_scheme;
+        icon.label = image.alt.clone().or_else(|| Some(login.to_string()));
+        icon
+      })
+      .collect::<Vec<_>>();
+    icons.sort_by(|a, b| a.info.cmp(&b.info));
+
+    // the profile logo is ranked above the generic avatar
+    let mut avatar = RepoIcon::new(avatar_url, kind, info);
+    avatar.label = Some(login.to_string());
+    avatar.detect_default_avatar(options).await;
+    icons.push(avatar);
+
+    Ok(RepoIcons {
+      icons: icons.try_into().unwrap(),
+      truncated: false,
+      private: false,
+      candidates: Vec::new(),
+      logo_score: None,
+      etag: None,
+      prefer_vector: false,
+      primary_preference: PrimaryPreference::Auto,
+      min_confidence: None,
+      warnings,
+    })
+  }
+
+  /// Fetch the icons for a gist, see [`RepoIconsBuilder::load_gist`]
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIcons;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// if let Some(icons) = RepoIcons::load_gist("aa5a315d61ae9438b18d").await? {
+  ///   println!("{:?}", icons.closest_match())
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_gist(id: &str) -> Result<Option<Self>, Box<dyn Error>> {
+    RepoIconsBuilder::new().load_gist(id).await
+  }
+
+  pub(crate) async fn load_gist_with(
+    options: &RepoIconsBuilder,
+    id: &str,
+  ) -> Result<Option<Self>, Box<dyn Error>> {
+    let readme = match observe_phase(
+      options,
+      Phase::Readme,
+      github_api::Readme::load_gist_with(options, id),
+    )
+    .await?
+    {
+      Some(readme) => readme,
+      None => return Ok(None),
+    };
+
+    let mut icons = Icons::new();
+
+    let declared_icon_url = readme.declared_icon().await.map(|(url, headers)| {
+      icons.add_icon_with_headers(url.clone(), headers, IconKind::SiteLogo, None);
+      url
+    });
+
+    let image = readme
+      .images()
+      .await
+      .into_iter()
+      .find(|image| image.is_logo_candidate());
+    if let Some(image) = &image {
+      let srcs =
+        std::iter::once(&image.src).chain(image.picture_sources.iter().map(|source| &source.src));
+      for src in srcs {
+        icons.add_icon_with_headers(src.clone(), image.headers.clone(), IconKind::SiteLogo, None);
+      }
+    }
+
+    let user_avatar_url = (!readme.owner.is_empty())
+      .then(|| format!("https://github.com/{}.png", readme.cased_owner).parse::<Url>())
+      .transpose()?
+      .map(|url| sized_avatar_url(&url, AVATAR_SIZE));
+    if let Some(user_avatar_url) = &user_avatar_url {
+      icons.add_icon(user_avatar_url.clone(), IconKind::SiteLogo, None);
+    }
+
+    let entries = options
+      .within_deadline(observe_phase(
+        options,
+        Phase::Sizes,
+        with_permit(options, icons.entries()),
+      ))
+      .await;
+    let truncated = entries.is_none();
+
+    let mut repo_icons = entries
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|entry| !is_badge(&entry.url))
+      .map(|entry| {
+        let kind = if declared_icon_url.as_ref() == Some(&entry.url) {
+          RepoIconKind::Declared
+        } else if user_avatar_url.as_ref() == Some(&entry.url) {
+          RepoIconKind::UserAvatar
+        } else {
+          RepoIconKind::ReadmeImage
+        };
+
+        let color_scheme = image
+          .as_ref()
+          .and_then(|image| {
+            image
+              .picture_sources
+              .iter()
+              .find(|source| source.src == entry.url)
+              .map(|source| source.color_scheme)
+              .or((image.src == entry.url).then_some(image.color_scheme))
+          })
+          .unwrap_or_default();
+
+        let label = match kind {
+          RepoIconKind::UserAvatar => Some(readme.cased_owner.clone()),
+          RepoIconKind::ReadmeImage => image.as_ref().and_then(|image| image.alt.clone()),
+          _ => None,
+        };
+
+        let mut repo_icon = RepoIcon::new_with_headers(entry.url, entry.headers, kind, entry.info);
+        repo_icon.color_scheme = color_scheme;
+        repo_icon.label = label;
+        repo_icon
+      })
+      .collect::<Vec<_>>();
+
+    if let Some(allowed_hosts) = &options.allowed_hosts {
+      repo_icons.retain(|icon| is_allowed_host(allowed_hosts, &icon.url, None));
+    }
+
+    for icon in &mut repo_icons {
+      icon.detect_default_avatar(options).await;
+    }
+
+    repo_icons.sort_by(|a, b| a.info.cmp(&b.info));
+    repo_icons.sort_by(|a, b| a.kind.cmp(&b.kind));
+    if options.prefer_vector {
+      repo_icons.sort_by_key(|icon| !icon.is_vector());
+    }
+
+    Ok(repo_icons.try_into().ok().map(|icons| {
+      RepoIcons {
+        icons,
+        truncated,
+        private: false,
+        candidates: Vec::new(),
+        logo_score: None,
+        etag: None,
+        prefer_vector: options.prefer_vector,
+        primary_preference: options.primary_preference,
+        min_confidence: options.min_confidence,
+        warnings: truncated
+          .then(|| SourceWarning {
+            source: Phase::Sizes,
+            message: "deadline exceeded".to_string(),
+          })
+          .into_iter()
+          .collect(),
+      }
+    }))
+  }
+
+  /// Fetch the icons for multiple repos, see [`RepoIconsBuilder::load_many`]
+  pub async fn load_many(
+    repos: &[(&str, &str)],
+  ) -> Vec<((String, String), Result<Self, Box<dyn Error>>)> {
+    RepoIconsBuilder::new().load_many(repos).await
+  }
+
+  pub(crate) async fn load_with(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    Self::load_reporting(options, owner, repo, None).await
+  }
+
+  /// Load the icons, passing the ranking to `progress` as it's refined
+  /// (see [`RepoIconsBuilder::load_progressive`]). The readme is only loaded once
+  #[async_recursion(?Send)]
+  pub(crate) async fn load_reporting(
+    options: &RepoIconsBuilder,
+    owner: &str,
+    repo: &str,
+    progress: Option<&'async_recursion dyn Fn(RankingSnapshot)>,
+  ) -> Result<Self, Box<dyn Error>> {
     let mut icons = Icons::new();
+    // set when a phase is cut short by the deadline
+    let truncated = Cell::new(false);
+    // only the repo metadata is required, the other sources are recorded
+    // as warnings when they fail
+    let warnings = RefCell::new(Vec::new());
+    let warn_source = |source: Phase, err: &dyn Display| {
+      warn!("{:?} failed {}", source, err);
+      warnings.borrow_mut().push(SourceWarning {
+        source,
+        message: err.to_string(),
+      });
+    };
 
-    let user_avatar_url: Url = format!("https://github.com/{}.png", owner).parse().unwrap();
+    let user_avatar_url = sized_avatar_url(
+      &format!("https://github.com/{}.png", owner).parse().unwrap(),
+      AVATAR_SIZE,
+    );
 
     // Check if the repo contains the owner's username, and load the user's avatar
     if repo.to_lowercase().contains(&owner.to_lowercase()) {
       icons.add_icon(user_avatar_url.clone(), IconKind::SiteLogo, None);
     }
 
-    let (prefixed_repo_icons, blob_icon, (entries, readme_image, repo_is_private)) = try_join!(
+    let (
+      prefixed_repo_icons,
+      blob_icon,
+      social_preview_icon,
+      (
+        entries,
+        readme_image,
+        declared_icon_url,
+        source_icons,
+        mut candidates,
+        logo_score,
+        homepage_scan,
+        readme,
+      ),
+    ) = try_join!(
       // Try and find prefixed repos, and load icons for them on GitHub
-      async {
-        let repos = github_api::get_user_repos(owner).await?;
+      observe_phase(options, Phase::PrefixedRepos, async {
+        let repos = if !options.prefixed_repos {
+          Ok(Vec::new())
+        } else {
+          github_api::get_user_repos(options, owner).await
+        };
+        let repos = match repos {
+          Ok(repos) => repos,
+          Err(err) => {
+            warn_source(Phase::PrefixedRepos, &err);
+            Vec::new()
+          }
+        };
+        let truncated = &truncated;
+        // the branch override is for this repo, not the prefixed ones
+        let prefixed_options = &RepoIconsBuilder {
+          branch: None,
+          ..options.clone()
+        };
 
-        Ok(
+        let icons = within_deadline(
+          options,
+          truncated,
           join_all(
             repos
               .into_iter()
@@ -57,104 +473,495 @@ impl RepoIcons {
                   && repo.to_lowercase().contains(possibly_prefixed_repo)
               })
               .map(async move |repo| {
-                RepoIcons::load(owner, &repo)
+                prefixed_options
+                  .load(owner, &repo)
                   .await
-                  .map(|icons| icons.0.into_vec())
-                  .unwrap_or(Vec::new())
+                  .map(|icons| {
+                    if icons.truncated {
+                      truncated.set(true);
+                    }
+                    icons.icons.into_vec()
+                  })
+                  .unwrap_or_else(|err| {
+                    warn_source(Phase::PrefixedRepos, &format!("{}/{} {}", owner, repo, err));
+                    Vec::new()
+                  })
               }),
-          )
-          .await
-          .into_iter()
-          .flatten(),
+          ),
         )
-      },
-      async {
-        if let Some((is_icon_field, blob)) = github_api::get_blob(owner, repo).await? {
-          RepoIcon::load_blob(blob, is_icon_field).await.map(Some)
-        } else {
-          Ok(None)
+        .await
+        .unwrap_or_default();
+
+        Ok::<_, Box<dyn Error>>(icons.into_iter().flatten())
+      }),
+      observe_phase(options, Phase::Blob, async {
+        if !options.repo_files {
+          return Ok(None);
+        }
+
+        let blob_icon = within_deadline(options, &truncated, async {
+          if let Some((is_icon_field, blob)) = github_api::get_blob(options, owner, repo).await? {
+            with_permit(options, RepoIcon::load_blob(blob, is_icon_field))
+              .await
+              .map(Some)
+          } else {
+            Ok(None)
+          }
+        })
+        .await;
+
+        match blob_icon {
+          Some(Ok(blob_icon)) => Ok(blob_icon),
+          Some(Err(err)) => {
+            warn_source(Phase::Blob, &err);
+            Ok(None)
+          }
+          None => Ok(None),
+        }
+      }),
+      observe_phase(options, Phase::SocialPreview, async {
+        if !options.social_preview {
+          return Ok(None);
+        }
+
+        let social_preview_icon = within_deadline(options, &truncated, async {
+          let (url, alt) = match github_api::get_social_preview(options, owner, repo).await {
+            Ok(Some(social_preview)) => social_preview,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+              warn_source(Phase::SocialPreview, &err);
+              return Ok(None);
+            }
+          };
+
+          let info =
+            with_permit(options, IconInfo::load(url.clone(), HeaderMap::new(), None)).await?;
+          let mut icon = RepoIcon::new(url, RepoIconKind::SocialPreview, info);
+          icon.label = alt;
+          Ok(Some(icon))
+        })
+        .await;
+
+        match social_preview_icon {
+          Some(Ok(social_preview_icon)) => Ok(social_preview_icon),
+          Some(Err::<_, Box<dyn Error>>(err)) => {
+            warn_source(Phase::SocialPreview, &err);
+            Ok(None)
+          }
+          None => Ok(None),
         }
-      },
+      }),
       // Try and extract images from the readme website, or directly in it
       async {
-        let readme = github_api::Readme::load(owner, repo).await?;
-
-        if let Some(homepage) = &readme.homepage {
-          if !is_blacklisted_homepage(homepage) {
-            warn_err!(
-              icons.load_website(homepage.clone()).await,
-              "failed to load website {}",
-              homepage
-            );
+        // without the repo metadata there's nothing to return
+        let readme = options
+          .within_deadline(observe_phase(
+            options,
+            Phase::Readme,
+            github_api::Readme::load_with(options, owner, repo),
+          ))
+          .await
+          .ok_or("deadline exceeded before the repo loaded")??;
+
+        if let Some(progress) = progress {
+          progress(RankingSnapshot::Readme(readme.images_offline().await));
+        }
+
+        let website = async {
+          let homepage = readme.homepage.as_ref().filter(|_| options.website)?;
+          if is_blacklisted_homepage(homepage) {
+            return None;
+          }
+
+          // boxed, as site_icons' futures overflow a 2MiB stack (eg. a tokio
+          // worker's) in debug builds
+          let scan = within_deadline(
+            options,
+            &truncated,
+            observe_phase(
+              options,
+              Phase::Website,
+              Box::pin(load_homepage(options, &mut icons, homepage)),
+            ),
+          )
+          .await?;
+
+          scan
+            .map_err(|err| warn_source(Phase::Website, &format!("{} {}", homepage, err)))
+            .ok()
+        };
+
+        let ctx = RepoContext {
+          owner: owner.to_string(),
+          repo: repo.to_string(),
+          homepage: readme.homepage.clone(),
+          client: client(options),
+        };
+        let sources = within_deadline(
+          options,
+          &truncated,
+          join_all(
+            options
+              .sources
+              .iter()
+              .map(|source| with_permit(options, source.discover(&ctx))),
+          ),
+        );
+
+        let (homepage_scan, source_icons) = join(website, sources).await;
+        let source_icons = source_icons.into_iter().flatten().flatten();
+
+        // probed separately from the website's icons, so they aren't lost
+        // if the website is too slow for the deadline
+        let mut readme_icons = Icons::new();
+
+        let declared_icon = match readme.declared_icon().await {
+          Some(declared_icon) => Some(declared_icon),
+          None if options.metadata_logo => readme.metadata_logo().await,
+          None => None,
+        };
+        let declared_icon_url = match declared_icon {
+          Some((url, headers)) => {
+            readme_icons.add_icon_with_headers(url.clone(), headers, IconKind::SiteLogo, None);
+            Some(url)
+          }
+          None => None,
+        };
+
+        let (mut images, mut candidates) = readme.images_with_excluded().await;
+        if options.scan_all_readmes {
+          for secondary in readme.secondary_readmes().await {
+            let (secondary_images, excluded) = secondary.images_with_excluded().await;
+            images.extend(secondary_images.into_iter().map(|mut image| {
+              image.in_secondary_readme = true;
+              image
+            }));
+            candidates.extend(excluded);
           }
+
+          // an image in several readmes is kept where it ranks highest
+          images.sort_by(|a, b| {
+            b.weight_with(&options.scoring_profile)
+              .cmp(&a.weight_with(&options.scoring_profile))
+              .then_with(|| a.tie_break(b))
+          });
+          images = images
+            .into_iter()
+            .unique_by(|image| image.src.clone())
+            .collect();
         }
+        let excluded = |image: &ReadmeImage, exclusion| Candidate {
+          url: image.src.to_string(),
+          kind: RepoIconKind::ReadmeImage,
+          excluded: Some(exclusion),
+          weight: Some(image.weight_with(&options.scoring_profile)),
+          signals: image.signals(),
+        };
 
-        let image = readme
-          .images()
+        if options.only_project_linked {
+          let (linked, unlinked) = images
+            .into_iter()
+            .partition(|image| image.links_to.is_some() || image.sourced_from_repo);
+          images = linked;
+          candidates.extend(
+            unlinked
+              .iter()
+              .map(|image| excluded(image, Exclusion::NotProjectLinked)),
+          );
+        }
+        if options.verify_urls {
+          let srcs = images.iter().map(|image| image.src.clone()).collect_vec();
+          let live = within_deadline(
+            options,
+            &truncated,
+            ReadmeImage::retain_live(options, images, 4),
+          )
           .await
-          .into_iter()
-          .find(|image| image.in_primary_heading);
+          .unwrap_or_default();
+
+          for src in srcs {
+            if !live.iter().any(|image| image.src == src) {
+              candidates.push(Candidate::excluded(
+                src,
+                RepoIconKind::ReadmeImage,
+                Exclusion::Unreachable,
+              ));
+            }
+          }
+          images = live;
+        }
+
+        let mut image = None;
+        let mut runner_up = None;
+        for candidate in images {
+          if image.is_none() && candidate.is_logo_candidate() {
+            image = Some(candidate);
+          } else if candidate.is_logo_candidate() {
+            let candidate = excluded(&candidate, Exclusion::Outranked);
+            runner_up = runner_up.or(candidate.weight);
+            candidates.push(candidate);
+          } else {
+            candidates.push(excluded(&candidate, Exclusion::NotLogoCandidate));
+          }
+        }
+
+        let logo_score = image.as_ref().map(|image| {
+          LogoScore::new(
+            image.weight_with(&options.scoring_profile),
+            runner_up,
+            &image.signals(),
+          )
+        });
 
         if let Some(image) = &image {
-          icons.add_icon_with_headers(
+          readme_icons.add_icon_with_headers(
             image.src.clone(),
             image.headers.clone(),
             IconKind::SiteLogo,
             None,
           );
+
+          // load the other color scheme variants of the logo
+          for source in &image.picture_sources {
+            readme_icons.add_icon_with_headers(
+              source.src.clone(),
+              image.headers.clone(),
+              IconKind::SiteLogo,
+              None,
+            );
+          }
+        }
+
+        let (site_entries, readme_entries) = observe_phase(
+          options,
+          Phase::Sizes,
+          join(
+            within_deadline(options, &truncated, with_permit(options, icons.entries())),
+            within_deadline(
+              options,
+              &truncated,
+              with_permit(options, readme_icons.entries()),
+            ),
+          ),
+        )
+        .await;
+
+        if site_entries.is_none() || readme_entries.is_none() {
+          warn_source(Phase::Sizes, &"deadline exceeded");
         }
 
-        let entries = icons.entries().await;
+        let entries = readme_entries
+          .unwrap_or_default()
+          .into_iter()
+          .chain(site_entries.unwrap_or_default())
+          .collect::<Vec<_>>();
+
+        if let Some(progress) = progress {
+          let sized = entries.iter().map(|entry| Icon {
+            url: entry.url.clone(),
+            headers: entry.headers.clone(),
+            kind: entry.kind.clone(),
+            info: entry.info.clone(),
+          });
+          progress(RankingSnapshot::Sizes(sized.sorted().collect()));
+        }
 
-        Ok((entries, image, readme.private))
+        Ok((
+          entries,
+          image,
+          declared_icon_url,
+          source_icons.collect::<Vec<_>>(),
+          candidates,
+          logo_score,
+          homepage_scan,
+          readme,
+        ))
       }
     )?;
 
     let mut repo_icons = entries
       .into_iter()
-      .filter(|icon| !is_badge(&icon.url))
+      .filter(|entry| {
+        let is_badge = is_badge(&entry.url);
+        if is_badge {
+          candidates.push(Candidate::excluded(
+            &entry.url,
+            RepoIconKind::Site(entry.kind.clone()),
+            Exclusion::Badge,
+          ));
+        }
+        !is_badge
+      })
       .map(|entry| {
         let is_user_avatar = entry.url == user_avatar_url;
-        let is_readme = readme_image
-          .as_ref()
-          .map(|image| image.src == entry.url)
-          .unwrap_or(false);
+        let is_declared = declared_icon_url.as_ref() == Some(&entry.url);
+        let readme_color_scheme = readme_image.as_ref().and_then(|image| {
+          if image.src == entry.url {
+            Some(image.color_scheme)
+          } else {
+            image
+              .picture_sources
+              .iter()
+              .find(|source| source.src == entry.url)
+              .map(|source| source.color_scheme)
+          }
+        });
 
-        RepoIcon::new_with_headers(
+        let mut repo_icon = RepoIcon::new_with_headers(
           entry.url,
           entry.headers,
-          if is_user_avatar {
+          if is_declared {
+            RepoIconKind::Declared
+          } else if is_user_avatar {
             RepoIconKind::UserAvatar
-          } else if is_readme {
+          } else if readme_color_scheme.is_some() {
             RepoIconKind::ReadmeImage
           } else {
             RepoIconKind::Site(entry.kind)
           },
           entry.info,
-        )
+        );
+        repo_icon.color_scheme = readme_color_scheme.unwrap_or_default();
+        repo_icon.label = match repo_icon.kind {
+          RepoIconKind::UserAvatar => Some(owner.to_string()),
+          _ => readme_color_scheme
+            .and(readme_image.as_ref())
+            .and_then(|image| image.alt.clone()),
+        };
+        if let Some(scan) = &homepage_scan {
+          scan.apply(&mut repo_icon);
+        }
+
+        if (options.debug || options.collect_breakdown) && readme_color_scheme.is_some() {
+          if let Some(image) = &readme_image {
+            repo_icon.debug = Some(IconDebug {
+              weight: image.weight_with(&options.scoring_profile),
+              signals: image.signals(),
+              score_breakdown: image.score_breakdown_with(&options.scoring_profile),
+            });
+          }
+        }
+
+        repo_icon
       })
       .collect::<Vec<_>>();
 
     if let Some(mut blob_icon) = blob_icon {
-      blob_icon.blob_set_private(repo_is_private);
+      blob_icon.blob_set_private(readme.private);
       repo_icons.push(blob_icon);
     }
 
+    repo_icons.extend(social_preview_icon);
+
     repo_icons.extend(prefixed_repo_icons);
 
+    repo_icons.extend(source_icons);
+
+    if repo_icons.is_empty() && options.language_logo {
+      if let Some(url) = readme
+        .language
+        .as_ref()
+        .and_then(|language| language_logo_url(options, language))
+      {
+        let mut icon = RepoIcon::new(url, RepoIconKind::LanguageLogo, IconInfo::SVG);
+        icon.label = readme.language.clone();
+        repo_icons.push(icon);
+      }
+    }
+
+    if let Some(allowed_hosts) = &options.allowed_hosts {
+      repo_icons.retain(|icon| {
+        let is_allowed = is_allowed_host(allowed_hosts, &icon.url, readme.homepage.as_ref());
+        if !is_allowed {
+          candidates.push(Candidate::excluded(
+            &icon.url,
+            icon.kind.clone(),
+            Exclusion::UntrustedHost,
+          ));
+        }
+        is_allowed
+      });
+    }
+
+    // prefixed repos' icons are files of another repo, so this is redetermined
+    for icon in &mut repo_icons {
+      icon.detect_default_avatar(options).await;
+      icon.sourced_from_repo = matches!(
+        icon.kind,
+        RepoIconKind::Blob(_) | RepoIconKind::IconField(_)
+      ) || readme.branch_and_path(&icon.url, true).await.is_some();
+    }
+
+    within_deadline(
+      options,
+      &truncated,
+      join_all(
+        repo_icons
+          .iter_mut()
+          .filter(|icon| options.svg_sizes && icon.is_svg() && icon.vector_size.is_none())
+          .map(async move |icon| {
+            if let Err(err) = icon.load_vector_size_with(options).await {
+              warn_source(Phase::Sizes, &format!("svg size of {} {}", icon.url, err));
+            }
+          }),
+      ),
+    )
+    .await;
+
+    if let Some(rewriter) = &options.private_url_rewriter {
+      for icon in repo_icons.iter_mut().filter(|icon| icon.is_auth_gated()) {
+        icon.url = rewriter.rewrite(&icon.url, &icon.headers);
+        icon.headers.clear();
+      }
+    }
+
     repo_icons.sort_by(|a, b| a.info.cmp(&b.info));
     repo_icons.sort_by(|a, b| a.kind.cmp(&b.kind));
+    if options.prefer_vector {
+      repo_icons.sort_by_key(|icon| !icon.is_vector());
+    }
 
     let repo_icons = repo_icons
       .into_iter()
       .unique_by(|icon| icon.url.clone())
       .collect::<Vec<_>>();
 
+    if options.debug {
+      candidates.sort_by_key(|candidate| Reverse(candidate.weight));
+      candidates.splice(
+        0..0,
+        repo_icons.iter().map(|icon| Candidate {
+          url: icon.url.to_string(),
+          kind: icon.kind.clone(),
+          excluded: None,
+          weight: icon.debug.as_ref().map(|debug| debug.weight),
+          signals: icon
+            .debug
+            .as_ref()
+            .map(|debug| debug.signals.clone())
+            .unwrap_or_default(),
+        }),
+      );
+    } else {
+      candidates.clear();
+    }
+
     let repo_icons: Vec1<RepoIcon> = repo_icons
       .try_into()
       .map_err(|_| "no icons found for repo")?;
 
-    Ok(RepoIcons(repo_icons))
+    Ok(RepoIcons {
+      icons: repo_icons,
+      truncated: truncated.get(),
+      private: readme.private,
+      candidates,
+      logo_score,
+      etag: readme.etag,
+      prefer_vector: options.prefer_vector,
+      primary_preference: options.primary_preference,
+      min_confidence: options.min_confidence,
+      warnings: warnings.into_inner(),
+    })
   }
 
   /// Fetch all icons using an API endpoint. Ordered from highest to lowest resolution
@@ -184,7 +991,7 @@ impl RepoIcons {
       );
     }
 
-    let repo_icons = Client::new()
+    let repo_icons = client(&RepoIconsBuilder::new())
       .get(endpoint)
       .headers(headers)
       .send()
@@ -196,13 +1003,129 @@ impl RepoIcons {
     Ok(repo_icons)
   }
 
+  /// Icons already in their ranked order, without any of the load's metadata
+  #[cfg(all(feature = "markdown", not(target_arch = "wasm32")))]
+  pub(crate) fn from_icons(icons: Vec<RepoIcon>) -> Option<Self> {
+    Some(RepoIcons {
+      icons: icons.try_into().ok()?,
+      truncated: false,
+      private: false,
+      candidates: Vec::new(),
+      logo_score: None,
+      etag: None,
+      prefer_vector: false,
+      primary_preference: PrimaryPreference::Auto,
+      min_confidence: None,
+      warnings: Vec::new(),
+    })
+  }
+
+  /// The optional sources that failed (eg. an unreachable homepage), while
+  /// the icons from the rest were still returned. Failing to load the repo
+  /// itself errors instead
+  pub fn warnings(&self) -> &[SourceWarning] {
+    &self.warnings
+  }
+
+  /// The icons in their ranked order
+  pub fn as_slice(&self) -> &[RepoIcon] {
+    self.icons.as_slice()
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, RepoIcon> {
+    self.icons.iter()
+  }
+
+  pub fn len(&self) -> usize {
+    self.icons.len()
+  }
+
+  /// Always false, as loading errors when there aren't any icons
+  pub fn is_empty(&self) -> bool {
+    false
+  }
+
+  /// The icon at a position in the ranked order
+  pub fn get(&self, index: usize) -> Option<&RepoIcon> {
+    self.icons.get(index)
+  }
+
+  /// Whether the load was cut short by [`RepoIconsBuilder::deadline`],
+  /// so some of the icons may be missing
+  pub fn truncated(&self) -> bool {
+    self.truncated
+  }
+
+  /// The ETag of the readme the icons were loaded from,
+  /// for [`RepoIconsBuilder::load_if_modified`]
+  pub fn etag(&self) -> Option<&str> {
+    self.etag.as_deref()
+  }
+
+  /// Whether the repo is private, so its files need the token to load
+  pub fn private(&self) -> bool {
+    self.private
+  }
+
+  /// Every candidate considered while loading, including the excluded ones
+  /// and why. Only kept when loaded with [`RepoIconsBuilder::debug`]
+  pub fn candidates(&self) -> &[Candidate] {
+    &self.candidates
+  }
+
+  /// A report of the [`RepoIcons::candidates`], one per line. The icons come
+  /// first in their ranked order, then the excluded candidates by weight
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIconsBuilder;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIconsBuilder::new()
+  ///   .debug(true)
+  ///   .load("facebook", "react")
+  ///   .await?;
+  /// println!("{}", icons.explain());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn explain(&self) -> String {
+    self.candidates.iter().map(ToString::to_string).join("\n")
+  }
+
+  /// The icons that are files in the repo, which won't rot like a third-party
+  /// link might. Favicons, avatars & the social preview are never in the repo
+  pub fn in_repo_only(&self) -> Vec<&RepoIcon> {
+    self
+      .icons
+      .iter()
+      .filter(|icon| icon.sourced_from_repo)
+      .collect()
+  }
+
+  /// The icons that aren't files in the repo, the inverse of [`RepoIcons::in_repo_only`]
+  pub fn external_only(&self) -> Vec<&RepoIcon> {
+    self
+      .icons
+      .iter()
+      .filter(|icon| !icon.sourced_from_repo)
+      .collect()
+  }
+
+  /// The icons that load without credentials, safe to hotlink
+  pub fn public_icons(&self) -> Vec<&RepoIcon> {
+    self
+      .icons
+      .iter()
+      .filter(|icon| !icon.is_auth_gated())
+      .collect()
+  }
+
   pub fn get_thumbnail_sizes(&self, resolutions: &[u32]) -> Vec<(u32, &RepoIcon)> {
     let mut resolutions = resolutions.to_vec();
     resolutions.sort_by(|a, b| b.cmp(a));
 
     let mut thumbnails = HashMap::new();
 
-    for icon in self.0.iter() {
+    for icon in self.icons.iter() {
       if let Some(sizes) = icon.info.sizes() {
         for icon_size in sizes.iter().map(|size| size.max_rect()) {
           let mut resolutions = resolutions.iter().peekable();
@@ -276,10 +1199,15 @@ impl RepoIcons {
     sizes
   }
 
+  /// Vector icons satisfy any size
   pub fn get_size(&self, width: u32, height: u32) -> &RepoIcon {
-    for icon in self.0.iter().rev() {
-      if let Some(size) = icon.info.size() {
-        if size.width >= width || size.height >= height {
+    for icon in self.icons.iter().rev() {
+      if icon.is_vector() {
+        return icon;
+      }
+
+      if let Some((icon_width, icon_height)) = icon.dimensions() {
+        if icon_width >= width || icon_height >= height {
           return icon;
         }
       }
@@ -288,16 +1216,155 @@ impl RepoIcons {
     self.closest_match()
   }
 
+  /// The icon for the repo (see [`RepoIcons::closest_match`]), with how confident
+  /// the ranking is from 0 to 1, so callers can decide whether to show it.
+  ///
+  /// Declared icons are 1, and readme logos are
+  /// `0.4 + 0.3 * weight / (weight + 16) + 0.2 * margin + 0.1 * strong`, where the
+  /// margin is the fraction of the weight the next best logo candidate is beaten by,
+  /// and strong is whether it mentions "logo" and is in the primary heading.
+  /// Other kinds have a fixed confidence, from 0.7 for repo files down to 0.3
+  /// for the owner's avatar (0.05 if it's a [`RepoIcon::default_avatar`])
+  /// and 0.2 for the social preview.
+  ///
+  /// `None` if the confidence is below [`RepoIconsBuilder::min_confidence`],
+  /// so automated pipelines can skip a likely wrong guess
+  pub fn primary(&self) -> Option<PrimaryIcon> {
+    let icon = self.closest_match();
+    let confidence = primary_icon::confidence(icon, self.logo_score);
+
+    if confidence < self.min_confidence.unwrap_or(0.0) {
+      return None;
+    }
+
+    Some(PrimaryIcon {
+      icon: icon.clone(),
+      confidence,
+    })
+  }
+
+  /// Whether two repos have the same icon, eg. to detect forks or reused
+  /// logos. Their [`RepoIcons::closest_match`] are compared by content hash,
+  /// so a file copied between repos matches despite its different urls, or by
+  /// their fingerprint when either isn't a repo file, see [`RepoIcon::is_same_image`]
+  pub fn primary_matches(&self, other: &RepoIcons) -> bool {
+    self.closest_match().is_same_image(other.closest_match())
+  }
+
+  /// The highest ranked icon, preferring a vector of the same kind
+  /// unless a raster of that kind has a strictly larger resolution.
+  /// With [`RepoIconsBuilder::prefer_vector`] a vector always wins, and with
+  /// [`RepoIconsBuilder::primary_preference`] only the icons of the preferred
+  /// shape are considered, if there are any
   pub fn closest_match(&self) -> &RepoIcon {
-    self.0.first()
+    let preference = self.primary_preference;
+    let has_preferred = self.icons.iter().any(|icon| preference.matches(icon));
+    let considered = || {
+      self
+        .icons
+        .iter()
+        .filter(move |icon| !has_preferred || preference.matches(icon))
+    };
+
+    let first = considered().next().unwrap_or(self.icons.first());
+    if self.prefer_vector && first.is_vector() {
+      return first;
+    }
+    let same_kind = || considered().filter(|icon| icon.kind == first.kind);
+
+    let vector = match same_kind().find(|icon| icon.is_vector()) {
+      Some(vector) => vector,
+      None => return first,
+    };
+    let vector_size = vector
+      .dimensions()
+      .map(|(width, height)| max(width, height))
+      .unwrap_or(0);
+
+    same_kind()
+      .filter(|icon| !icon.is_vector())
+      .filter_map(|icon| {
+        let (width, height) = icon.dimensions()?;
+        Some((icon, max(width, height)))
+      })
+      .filter(|(_, size)| *size > vector_size)
+      .max_by_key(|(_, size)| *size)
+      .map(|(icon, _)| icon)
+      .unwrap_or(vector)
+  }
+
+  /// The `n` highest ranked icons, each with a short reason summarizing its
+  /// strongest signals, eg. for a curation UI to let a human pick.
+  ///
+  /// Signals are only kept when loaded with [`RepoIconsBuilder::debug`],
+  /// otherwise the reason describes where the icon came from
+  pub fn top(&self, n: usize) -> Vec<RankedIcon> {
+    self.icons.iter().take(n).map(RankedIcon::new).collect()
+  }
+
+  /// The icons reordered by `strategy`, ties keep the default order
+  pub fn sorted_by(&self, strategy: SortStrategy) -> Vec<&RepoIcon> {
+    let mut icons = self.icons.iter().collect::<Vec<_>>();
+    icons.sort_by(|a, b| strategy.compare(a, b));
+    icons
+  }
+
+  /// Group the icons by color scheme, so themed UIs can pick
+  /// from the matching set and fall back to `any`
+  pub fn by_color_scheme(&self) -> ColorSchemeIcons<'_> {
+    let mut icons = ColorSchemeIcons {
+      light: Vec::new(),
+      dark: Vec::new(),
+      any: Vec::new(),
+    };
+
+    for icon in self.icons.iter() {
+      match icon.color_scheme {
+        ColorScheme::Light => icons.light.push(icon),
+        ColorScheme::Dark => icons.dark.push(icon),
+        ColorScheme::Any => icons.any.push(icon),
+      }
+    }
+
+    icons
   }
 }
 
+/// Run a phase within the load's deadline, marking the icons as truncated if it's cut short
+async fn within_deadline<F: Future>(
+  options: &RepoIconsBuilder,
+  truncated: &Cell<bool>,
+  future: F,
+) -> Option<F::Output> {
+  let output = options.within_deadline(future).await;
+  if output.is_none() {
+    truncated.set(true);
+  }
+  output
+}
+
 impl IntoIterator for RepoIcons {
   type Item = RepoIcon;
   type IntoIter = std::vec::IntoIter<Self::Item>;
 
   fn into_iter(self) -> Self::IntoIter {
-    self.0.into_iter()
+    self.icons.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a RepoIcons {
+  type Item = &'a RepoIcon;
+  type IntoIter = std::slice::Iter<'a, RepoIcon>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.icons.iter()
+  }
+}
+
+impl Index<usize> for RepoIcons {
+  type Output = RepoIcon;
+
+  fn index(&self, index: usize) -> &Self::Output {
+    &self.icons[index]
   }
 }
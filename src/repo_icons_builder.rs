@@ -0,0 +1,805 @@
+use crate::{
+  github_api::{
+    get_rate_limit_with, is_readme_modified, last_rate_limit,
+    readme::{METADATA_FIELDS, METADATA_FILES},
+    Readme,
+  },
+  request_budget::RequestBudget,
+  request_estimate::{self, RequestEstimate},
+  CandidateSource, HttpClient, PrimaryPreference, RankingSnapshot, ReadmeImage, RepoIcons,
+  RepoIconsObserver, ScoringProfile,
+};
+use futures::{
+  channel::mpsc,
+  future::{self, Either},
+  stream, Future, FutureExt, Stream, StreamExt,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use futures_timer::Delay;
+use std::{
+  collections::HashMap,
+  error::Error,
+  fmt::{self, Display},
+  sync::Arc,
+  time::Duration,
+};
+use url::Url;
+
+/// The result of [`RepoIconsBuilder::load_if_modified`]
+#[derive(Debug)]
+pub enum ConditionalLoad {
+  /// the readme hasn't changed, so the previous icons can be reused
+  NotModified,
+  Modified(RepoIcons),
+}
+
+/// The error of a load that was cancelled, see [`RepoIconsBuilder::load_cancellable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    write!(f, "the load was cancelled")
+  }
+}
+
+impl Error for Cancelled {}
+
+/// Rewrites the url of an icon that needs the token to load, eg. to a proxy
+/// on your backend, as browsers can't send the token to embed them
+///
+/// ```no_run
+/// # use reqwest::Url;
+/// # use repo_icons::RepoIconsBuilder;
+/// # use std::collections::HashMap;
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let icons = RepoIconsBuilder::new()
+///   .private_url_rewriter(|url: &Url, _headers: &HashMap<String, String>| {
+///     let mut proxy = Url::parse("https://myapp.com/icon-proxy").unwrap();
+///     proxy.query_pairs_mut().append_pair("url", url.as_str());
+///     proxy
+///   })
+///   .load("owner", "private-repo")
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait PrivateUrlRewriter: Send + Sync {
+  fn rewrite(&self, url: &Url, headers: &HashMap<String, String>) -> Url;
+}
+
+impl<F: Fn(&Url, &HashMap<String, String>) -> Url + Send + Sync> PrivateUrlRewriter for F {
+  fn rewrite(&self, url: &Url, headers: &HashMap<String, String>) -> Url {
+    self(url, headers)
+  }
+}
+
+/// Configure how the icons for a repo are loaded
+///
+/// ```no_run
+/// # use repo_icons::RepoIconsBuilder;
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let icons = RepoIconsBuilder::new()
+///   .social_preview(true)
+///   .load("facebook", "react")
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub struct RepoIconsBuilder {
+  pub(crate) social_preview: bool,
+  pub(crate) scoring_profile: ScoringProfile,
+  pub(crate) verify_urls: bool,
+  pub(crate) batch_concurrency: usize,
+  pub(crate) rate_limit_reserve: Option<u32>,
+  pub(crate) max_images: Option<usize>,
+  pub(crate) stop_on_confident_logo: Option<u8>,
+  pub(crate) max_readme_bytes: Option<usize>,
+  pub(crate) debug: bool,
+  pub(crate) collect_breakdown: bool,
+  pub(crate) resolve_camo: bool,
+  pub(crate) only_project_linked: bool,
+  pub(crate) allowed_hosts: Option<Vec<String>>,
+  pub(crate) language_logo: bool,
+  /// keyed by the lowercased language
+  pub(crate) language_logos: HashMap<String, Url>,
+  pub(crate) prefer_vector: bool,
+  pub(crate) primary_preference: PrimaryPreference,
+  pub(crate) min_confidence: Option<f32>,
+  pub(crate) use_graphql: bool,
+  pub(crate) branch: Option<String>,
+  pub(crate) metadata_logo: bool,
+  pub(crate) metadata_files: Vec<String>,
+  pub(crate) metadata_fields: Vec<String>,
+  pub(crate) scan_all_readmes: bool,
+  pub(crate) website: bool,
+  pub(crate) repo_files: bool,
+  pub(crate) prefixed_repos: bool,
+  pub(crate) svg_sizes: bool,
+  pub(crate) icon_directive: bool,
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) proxy: Option<reqwest::Proxy>,
+  pub(crate) user_agent: Option<reqwest::header::HeaderValue>,
+  #[derivative(Debug = "ignore")]
+  pub(crate) private_url_rewriter: Option<Arc<dyn PrivateUrlRewriter>>,
+  #[derivative(Debug = "ignore")]
+  pub(crate) observer: Option<Arc<dyn RepoIconsObserver>>,
+  #[derivative(Debug = "ignore")]
+  pub(crate) http_client: Option<Arc<dyn HttpClient>>,
+  pub(crate) max_concurrent_requests: usize,
+  /// shared by all the requests of a single load, including prefixed repos
+  #[derivative(Debug = "ignore")]
+  pub(crate) request_budget: Option<Arc<RequestBudget>>,
+  pub(crate) deadline: Option<Duration>,
+  #[derivative(Debug = "ignore")]
+  pub(crate) sources: Vec<Arc<dyn CandidateSource>>,
+  /// when the current load's deadline expires
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) deadline_at: Option<std::time::Instant>,
+}
+
+impl Default for RepoIconsBuilder {
+  fn default() -> Self {
+    Self {
+      social_preview: false,
+      scoring_profile: ScoringProfile::default(),
+      verify_urls: false,
+      batch_concurrency: 4,
+      rate_limit_reserve: None,
+      max_images: None,
+      stop_on_confident_logo: None,
+      max_readme_bytes: None,
+      debug: false,
+      collect_breakdown: false,
+      resolve_camo: false,
+      only_project_linked: false,
+      allowed_hosts: None,
+      language_logo: false,
+      language_logos: HashMap::new(),
+      prefer_vector: false,
+      primary_preference: PrimaryPreference::Auto,
+      min_confidence: None,
+      use_graphql: false,
+      branch: None,
+      metadata_logo: false,
+      metadata_files: METADATA_FILES.iter().map(|file| file.to_string()).collect(),
+      metadata_fields: METADATA_FIELDS
+        .iter()
+        .map(|field| field.to_string())
+        .collect(),
+      scan_all_readmes: false,
+      website: true,
+      repo_files: true,
+      prefixed_repos: true,
+      svg_sizes: true,
+      icon_directive: false,
+      #[cfg(not(target_arch = "wasm32"))]
+      proxy: None,
+      user_agent: None,
+      private_url_rewriter: None,
+      observer: None,
+      http_client: None,
+      max_concurrent_requests: 8,
+      request_budget: None,
+      deadline: None,
+      sources: Vec::new(),
+      #[cfg(not(target_arch = "wasm32"))]
+      deadline_at: None,
+    }
+  }
+}
+
+impl RepoIconsBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Only the readme's logo & the owner's avatar, skipping
+  /// the homepage, the repo's files, prefixed repos and svg sizes. A few
+  /// requests, for when speed matters more than the best icon
+  pub fn fast() -> Self {
+    Self::new()
+      .website(false)
+      .repo_files(false)
+      .prefixed_repos(false)
+      .svg_sizes(false)
+      .stop_on_confident_logo(24)
+  }
+
+  /// The readme, the homepage's favicons and the repo's icon files, with the
+  /// avatar as a fallback, skipping prefixed repos and svg sizes
+  pub fn balanced() -> Self {
+    Self::new().prefixed_repos(false).svg_sizes(false)
+  }
+
+  /// Every source: the social preview, every readme, metadata files, the
+  /// language's logo, the icon directive and svg sizes, with the readme's images
+  /// checked to resolve.
+  /// The most requests, for when the best icon matters more than speed
+  pub fn thorough() -> Self {
+    Self::new()
+      .social_preview(true)
+      .verify_urls(true)
+      .metadata_logo(true)
+      .scan_all_readmes(true)
+      .language_logo(true)
+      .icon_directive(true)
+  }
+
+  /// Include the repo's custom social preview image as a banner,
+  /// costs an additional request to the repo's github page
+  pub fn social_preview(mut self, social_preview: bool) -> Self {
+    self.social_preview = social_preview;
+    self
+  }
+
+  /// The weights used to rank the readme images
+  pub fn scoring_profile(mut self, scoring_profile: ScoringProfile) -> Self {
+    self.scoring_profile = scoring_profile;
+    self
+  }
+
+  /// Check the readme images resolve before choosing one, using a HEAD
+  /// request for each image, up to 4 at a time
+  pub fn verify_urls(mut self, verify_urls: bool) -> Self {
+    self.verify_urls = verify_urls;
+    self
+  }
+
+  /// How many repos [`RepoIconsBuilder::load_many`] loads at a time
+  pub fn batch_concurrency(mut self, batch_concurrency: usize) -> Self {
+    self.batch_concurrency = batch_concurrency.max(1);
+    self
+  }
+
+  /// Before loading each repo in [`RepoIconsBuilder::load_many`], wait for the
+  /// rate limit to reset if fewer than `reserve` requests remain. The limit is
+  /// read from the headers of the batch's responses as they arrive
+  pub fn rate_limit_reserve(mut self, reserve: u32) -> Self {
+    self.rate_limit_reserve = Some(reserve);
+    self
+  }
+
+  /// Only process the first `max_images` images in the readme
+  pub fn max_images(mut self, max_images: usize) -> Self {
+    self.max_images = Some(max_images);
+    self
+  }
+
+  /// Stop processing the readme images once a logo candidate weighs at least
+  /// `min_weight`, rather than checking every image of huge readmes (eg. awesome-lists)
+  pub fn stop_on_confident_logo(mut self, min_weight: u8) -> Self {
+    self.stop_on_confident_logo = Some(min_weight);
+    self
+  }
+
+  /// Truncate readmes larger than `max_readme_bytes` before parsing them
+  pub fn max_readme_bytes(mut self, max_readme_bytes: usize) -> Self {
+    self.max_readme_bytes = Some(max_readme_bytes);
+    self
+  }
+
+  /// Include the weight and signals of readme images in [`crate::RepoIcon::debug`]
+  pub fn debug(mut self, debug: bool) -> Self {
+    self.debug = debug;
+    self
+  }
+
+  /// Include [`crate::RepoIcon::debug`] with the points each signal added under
+  /// the scoring profile ([`crate::IconDebug::score_breakdown`]), without the
+  /// rest of `debug`'s candidates, eg. to compare scoring profiles
+  pub fn collect_breakdown(mut self, collect_breakdown: bool) -> Self {
+    self.collect_breakdown = collect_breakdown;
+    self
+  }
+
+  /// Use the original url of readme images github proxies through camo
+  pub fn resolve_camo(mut self, resolve_camo: bool) -> Self {
+    self.resolve_camo = resolve_camo;
+    self
+  }
+
+  /// Ignore readme images that neither link to the project nor are sourced
+  /// from the repo, eg. the logos of sponsors
+  pub fn only_project_linked(mut self, only_project_linked: bool) -> Self {
+    self.only_project_linked = only_project_linked;
+    self
+  }
+
+  /// Only return icons served from `allowed_hosts` or their subdomains. Github's
+  /// own domains and the repo's homepage are always trusted
+  ///
+  /// ```no_run
+  /// # use repo_icons::RepoIconsBuilder;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIconsBuilder::new()
+  ///   .allowed_hosts(&["cdn.jsdelivr.net"])
+  ///   .load("facebook", "react")
+  ///   .await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn allowed_hosts(mut self, allowed_hosts: &[&str]) -> Self {
+    self.allowed_hosts = Some(allowed_hosts.iter().map(|host| host.to_string()).collect());
+    self
+  }
+
+  /// Fall back to the logo of the repo's primary language (from devicon)
+  /// when no other icons are found
+  pub fn language_logo(mut self, language_logo: bool) -> Self {
+    self.language_logo = language_logo;
+    self
+  }
+
+  /// Override the logo used for a language by [`RepoIconsBuilder::language_logo`],
+  /// `language` is github's name for it, eg. `C++`
+  pub fn language_logo_for(mut self, language: &str, url: Url) -> Self {
+    self.language_logos.insert(language.to_lowercase(), url);
+    self
+  }
+
+  /// Rank svgs above every raster, so [`crate::RepoIcons::closest_match`] &
+  /// [`crate::RepoIcons::primary`] return one whenever it exists, even if a
+  /// raster is larger. Without any svgs the ranking is unchanged
+  pub fn prefer_vector(mut self, prefer_vector: bool) -> Self {
+    self.prefer_vector = prefer_vector;
+    self
+  }
+
+  /// Whether [`crate::RepoIcons::primary`] & [`crate::RepoIcons::closest_match`]
+  /// pick a square logo or a wide banner over a higher ranked icon
+  pub fn primary_preference(mut self, primary_preference: PrimaryPreference) -> Self {
+    self.primary_preference = primary_preference;
+    self
+  }
+
+  /// The confidence (from 0 to 1) below which [`crate::RepoIcons::primary`]
+  /// returns `None`, eg. `0.5` to skip avatar & social preview fallbacks.
+  /// The icons themselves are unaffected
+  pub fn min_confidence(mut self, min_confidence: f32) -> Self {
+    self.min_confidence = Some(min_confidence);
+    self
+  }
+
+  /// Fetch the repo's metadata & readme with a single GitHub GraphQL query,
+  /// rather than separate REST requests. The readme is then rendered locally
+  /// with the `markdown` feature, or with the markdown api (a second request)
+  /// without it. GraphQL needs a token, so without one (or if the query
+  /// errors) the REST api is used instead
+  pub fn use_graphql(mut self, use_graphql: bool) -> Self {
+    self.use_graphql = use_graphql;
+    self
+  }
+
+  /// Load the readme & repo files of a branch rather than the repo's default
+  /// branch, eg. for mirrors with a stale default. The load errors if the
+  /// repo doesn't have the branch. Only the REST api can load a branch's
+  /// readme, so this takes precedence over [`RepoIconsBuilder::use_graphql`]
+  pub fn branch(mut self, branch: &str) -> Self {
+    self.branch = Some(branch.to_string());
+    self
+  }
+
+  /// Use the logo declared in the repo's metadata files (eg. the `logo` field
+  /// of `package.json` or `CITATION.cff`) as a declared icon, when the readme
+  /// doesn't declare one. Each file is a request
+  pub fn metadata_logo(mut self, metadata_logo: bool) -> Self {
+    self.metadata_logo = metadata_logo;
+    self
+  }
+
+  /// Override the files (paths in the repo) & fields
+  /// [`RepoIconsBuilder::metadata_logo`] checks, both in order of preference
+  pub fn metadata_logo_fields(mut self, files: &[&str], fields: &[&str]) -> Self {
+    self.metadata_files = files.iter().map(|file| file.to_string()).collect();
+    self.metadata_fields = fields.iter().map(|field| field.to_string()).collect();
+    self
+  }
+
+  /// Also look for the logo in the readmes of `docs/` and `.github/`, ranking
+  /// their images together with the readme's. Images of those readmes are
+  /// ranked slightly below the readme's, see
+  /// [`crate::ScoringProfile::secondary_readme_penalty`]. Each directory is
+  /// a request, and each readme found is a few more
+  pub fn scan_all_readmes(mut self, scan_all_readmes: bool) -> Self {
+    self.scan_all_readmes = scan_all_readmes;
+    self
+  }
+
+  /// Load the favicons & logos of the repo's homepage, on by default
+  pub fn website(mut self, website: bool) -> Self {
+    self.website = website;
+    self
+  }
+
+  /// Look for icon files & manifest icon fields in the repo's files, on by default
+  pub fn repo_files(mut self, repo_files: bool) -> Self {
+    self.repo_files = repo_files;
+    self
+  }
+
+  /// Include the icons of the owner's repos this repo's name is prefixed
+  /// with (eg. `react` for `react-dom`), on by default. Each is a load of its own
+  pub fn prefixed_repos(mut self, prefixed_repos: bool) -> Self {
+    self.prefixed_repos = prefixed_repos;
+    self
+  }
+
+  /// Download svgs without a known size to read their dimensions, on by
+  /// default. Without it svgs are ranked without a size
+  pub fn svg_sizes(mut self, svg_sizes: bool) -> Self {
+    self.svg_sizes = svg_sizes;
+    self
+  }
+
+  /// Fetch the readme's source to read its `<!-- repo-icon: ... -->` directive,
+  /// ranking the declared icon above every other icon. Off by default, as the
+  /// rendered html has comments stripped, so this costs another request to the
+  /// readme. The source is also rendered locally if github fails to render it
+  pub fn icon_directive(mut self, icon_directive: bool) -> Self {
+    self.icon_directive = icon_directive;
+    self
+  }
+
+  /// Rewrite the urls of icons that need the token to load (files of private
+  /// repos), so they can be embedded directly. Rewritten icons have no headers
+  pub fn private_url_rewriter<R: PrivateUrlRewriter + 'static>(mut self, rewriter: R) -> Self {
+    self.private_url_rewriter = Some(Arc::new(rewriter));
+    self
+  }
+
+  /// Route the requests through a proxy, instead of the proxy from the
+  /// `HTTP_PROXY` / `HTTPS_PROXY` / `NO_PROXY` env vars.
+  ///
+  /// `site_icons` (websites & icon sizes) uses its own client, so
+  /// it only follows the env vars
+  ///
+  /// ```no_run
+  /// # use reqwest::Proxy;
+  /// # use repo_icons::RepoIconsBuilder;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIconsBuilder::new()
+  ///   .proxy(Proxy::all("http://proxy.corp:8080")?)
+  ///   .load("facebook", "react")
+  ///   .await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+    self.proxy = Some(proxy);
+    self
+  }
+
+  /// Send the requests with `user_agent`, instead of the one from
+  /// [`crate::set_user_agent`]. This includes the homepage, but not the icons
+  /// & manifests `site_icons` fetches with its own client
+  ///
+  /// ```no_run
+  /// # use reqwest::header::HeaderValue;
+  /// # use repo_icons::RepoIconsBuilder;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let icons = RepoIconsBuilder::new()
+  ///   .user_agent(HeaderValue::from_static("my-app/1.0"))
+  ///   .load("facebook", "react")
+  ///   .await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn user_agent(mut self, user_agent: reqwest::header::HeaderValue) -> Self {
+    self.user_agent = Some(user_agent);
+    self
+  }
+
+  /// Report the requests & phases of loading to `observer`
+  pub fn observer(mut self, observer: Arc<dyn RepoIconsObserver>) -> Self {
+    self.observer = Some(observer);
+    self
+  }
+
+  /// The max number of requests in flight at once, across a whole load (or
+  /// all of [`RepoIconsBuilder::load_many`]'s loads). The requests `site_icons`
+  /// makes for a homepage / icon count as one
+  pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+    self.max_concurrent_requests = max_concurrent_requests.max(1);
+    self
+  }
+
+  /// Cap the total time of a load. The icons found before the deadline are
+  /// returned with [`RepoIcons::truncated`] set, it only errors if the repo
+  /// itself didn't load in time. Not supported on wasm
+  pub fn deadline(mut self, deadline: Duration) -> Self {
+    self.deadline = Some(deadline);
+    self
+  }
+
+  /// Send the requests to GitHub & the homepage with a custom client, eg. to
+  /// run on a runtime other than tokio. Icon sizes & favicons are still
+  /// fetched by `site_icons` with reqwest, see [`HttpClient`]
+  pub fn http_client<C: HttpClient + 'static>(mut self, http_client: C) -> Self {
+    self.http_client = Some(Arc::new(http_client));
+    self
+  }
+
+  /// Discover icons from a custom source as well as the built-in ones
+  pub fn add_source<S: CandidateSource + 'static>(mut self, source: S) -> Self {
+    self.sources.push(Arc::new(source));
+    self
+  }
+
+  pub async fn load(&self, owner: &str, repo: &str) -> Result<RepoIcons, Box<dyn Error>> {
+    // prefixed repos are loaded as part of the same load
+    if self.request_budget.is_some() {
+      return RepoIcons::load_with(self, owner, repo).await;
+    }
+
+    RepoIcons::load_with(&self.start_load(), owner, repo).await
+  }
+
+  /// Fetch only the repo metadata & rendered readme (2 requests), and return
+  /// the top ranked readme logo, see [`RepoIcons::load_fast`]. The readme's
+  /// source isn't fetched, so the icon directive is ignored
+  pub async fn load_fast(
+    &self,
+    owner: &str,
+    repo: &str,
+  ) -> Result<Option<ReadmeImage>, Box<dyn Error>> {
+    let options = self.clone().icon_directive(false);
+    let readme = Readme::load_with(&options, owner, repo).await?;
+
+    Ok(
+      readme
+        .images_offline()
+        .await
+        .into_iter()
+        .find(|image| image.is_logo_candidate()),
+    )
+  }
+
+  /// Load the icons, unless `cancel` resolves first (eg. when a web server's
+  /// client disconnects). The load is dropped, aborting its in-flight
+  /// requests, and it errors with [`Cancelled`]
+  ///
+  /// ```no_run
+  /// # use futures::FutureExt;
+  /// # use repo_icons::RepoIconsBuilder;
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// let (tx, rx) = futures::channel::oneshot::channel::<()>();
+  /// let icons = RepoIconsBuilder::new()
+  ///   .load_cancellable("facebook", "react", rx.map(|_| ()))
+  ///   .await;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_cancellable<C: Future<Output = ()>>(
+    &self,
+    owner: &str,
+    repo: &str,
+    cancel: C,
+  ) -> Result<RepoIcons, Box<dyn Error>> {
+    match future::select(Box::pin(self.load(owner, repo)), Box::pin(cancel)).await {
+      Either::Left((icons, _)) => icons,
+      Either::Right(_) => Err(Cancelled.into()),
+    }
+  }
+
+  /// Estimate how many requests loading the icons would make with these
+  /// options, eg. to budget a batch against the rate limit. Only the readme &
+  /// the owner's repos are loaded (so it costs ~4 requests itself), the rest is
+  /// approximated from the readme's candidates. Custom sources aren't counted
+  pub async fn estimate_requests(
+    &self,
+    owner: &str,
+    repo: &str,
+  ) -> Result<RequestEstimate, Box<dyn Error>> {
+    request_estimate::estimate_requests(self, owner, repo).await
+  }
+
+  /// Reload the icons only if the readme changed since the `etag` of a previous
+  /// load ([`RepoIcons::etag`]). Unchanged readmes cost a single request, which
+  /// doesn't count against the rate limit
+  ///
+  /// ```no_run
+  /// # use repo_icons::{ConditionalLoad, RepoIcons, RepoIconsBuilder};
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// # struct Cache;
+  /// # impl Cache {
+  /// #   fn insert(&mut self, _owner: &str, _repo: &str, _icons: RepoIcons) {}
+  /// # }
+  /// # let (etag, mut cache) = ("\"5d1f\"", Cache);
+  /// match RepoIconsBuilder::new().load_if_modified("facebook", "react", &etag).await? {
+  ///   ConditionalLoad::NotModified => {}
+  ///   ConditionalLoad::Modified(icons) => cache.insert("facebook", "react", icons),
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn load_if_modified(
+    &self,
+    owner: &str,
+    repo: &str,
+    etag: &str,
+  ) -> Result<ConditionalLoad, Box<dyn Error>> {
+    let options = self.start_load();
+    if !is_readme_modified(&options, owner, repo, etag).await? {
+      return Ok(ConditionalLoad::NotModified);
+    }
+
+    Ok(ConditionalLoad::Modified(
+      RepoIcons::load_with(&options, owner, repo).await?,
+    ))
+  }
+
+  /// Load the icon for a user / organization, their avatar. For organizations
+  /// the logo of their profile readme (in the `.github` repo) is ranked first,
+  /// when they have one
+  pub async fn load_owner(&self, login: &str) -> Result<RepoIcons, Box<dyn Error>> {
+    RepoIcons::load_owner_with(&self.start_load(), login).await
+  }
+
+  /// Load the icons for a gist, from its first markdown file.
+  /// `None` if the gist doesn't have any markdown files
+  pub async fn load_gist(&self, id: &str) -> Result<Option<RepoIcons>, Box<dyn Error>> {
+    RepoIcons::load_gist_with(&self.start_load(), id).await
+  }
+
+  /// The options for a new load, with its own deadline. It has its own request
+  /// budget too, unless it's part of a batch sharing one
+  fn start_load(&self) -> RepoIconsBuilder {
+    let mut options = self.clone();
+    options
+      .request_budget
+      .get_or_insert_with(|| Arc::new(RequestBudget::new(self.max_concurrent_requests)));
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      options.deadline_at = self
+        .deadline
+        .map(|deadline| std::time::Instant::now() + deadline);
+    }
+    options
+  }
+
+  /// Load the icons, emitting the ranking as it's refined: first the readme images
+  /// by weight, then the readme's logo & homepage icons by size, then the final
+  /// icons. It's a single load, so costs the same requests as [`RepoIconsBuilder::load`]
+  ///
+  /// ```no_run
+  /// # use futures::StreamExt;
+  /// # use repo_icons::{RankingSnapshot, RepoIconsBuilder};
+  /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+  /// # fn render(_snapshot: RankingSnapshot) {}
+  /// let mut snapshots = Box::pin(RepoIconsBuilder::new().load_progressive("facebook", "react"));
+  /// while let Some(snapshot) = snapshots.next().await {
+  ///   render(snapshot?);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn load_progressive(
+    &self,
+    owner: &str,
+    repo: &str,
+  ) -> impl Stream<Item = Result<RankingSnapshot, Box<dyn Error>>> {
+    let (owner, repo) = (owner.to_string(), repo.to_string());
+    let options = self.start_load();
+
+    // the snapshots are sent through the channel in order, so the load's
+    // stream is only polled to drive it
+    let (sender, snapshots) = mpsc::unbounded();
+    let load = async move {
+      let emit = |snapshot| {
+        let _ = sender.unbounded_send(Ok(snapshot));
+      };
+      let icons = RepoIcons::load_reporting(&options, &owner, &repo, Some(&emit)).await;
+      let _ = sender.unbounded_send(icons.map(RankingSnapshot::Complete));
+    };
+
+    stream::select(
+      snapshots,
+      stream::once(load).filter_map(|_| future::ready(None)),
+    )
+  }
+
+  /// Like [`RepoIconsBuilder::load_progressive`], ending with a [`Cancelled`]
+  /// error if `cancel` resolves before the icons are complete
+  pub fn load_progressive_cancellable<C: Future<Output = ()>>(
+    &self,
+    owner: &str,
+    repo: &str,
+    cancel: C,
+  ) -> impl Stream<Item = Result<RankingSnapshot, Box<dyn Error>>> {
+    let cancel = cancel.shared();
+
+    let cancelled = {
+      let cancel = cancel.clone();
+      stream::once(async move { cancel.now_or_never().map(|_| Err(Cancelled.into())) })
+        .filter_map(future::ready)
+    };
+
+    self
+      .load_progressive(owner, repo)
+      .take_until(cancel)
+      .chain(cancelled)
+  }
+
+  /// Load the icons for multiple repos, a failure for
+  /// one repo doesn't affect the others
+  pub async fn load_many(
+    &self,
+    repos: &[(&str, &str)],
+  ) -> Vec<((String, String), Result<RepoIcons, Box<dyn Error>>)> {
+    // the repos share a budget, so `max_concurrent_requests` caps the whole batch
+    let batch = &RepoIconsBuilder {
+      request_budget: Some(Arc::new(RequestBudget::new(self.max_concurrent_requests))),
+      ..self.clone()
+    };
+
+    stream::iter(repos)
+      .map(async move |&(owner, repo)| {
+        batch.wait_for_rate_limit().await;
+
+        (
+          (owner.to_string(), repo.to_string()),
+          RepoIcons::load_with(&batch.start_load(), owner, repo).await,
+        )
+      })
+      .buffered(self.batch_concurrency)
+      .collect()
+      .await
+  }
+
+  /// Run a future until the load's deadline, `None` if it expires first.
+  /// The timer doesn't need a tokio runtime, so this works with any executor
+  pub(crate) async fn within_deadline<F: Future>(&self, future: F) -> Option<F::Output> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(deadline_at) = self.deadline_at {
+      let remaining = deadline_at.saturating_duration_since(std::time::Instant::now());
+      return match future::select(Box::pin(future), Delay::new(remaining)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+      };
+    }
+
+    Some(future.await)
+  }
+
+  async fn wait_for_rate_limit(&self) {
+    let reserve = match self.rate_limit_reserve {
+      Some(reserve) => reserve,
+      None => return,
+    };
+
+    // the batch's responses keep the recorded limit current, so the api is
+    // only polled before the first response
+    let rate_limit = match last_rate_limit() {
+      Some(rate_limit) => rate_limit,
+      None => match get_rate_limit_with(self).await {
+        Ok(rate_limit) => rate_limit,
+        Err(err) => {
+          warn!("failed to get rate limit {}", err);
+          return;
+        }
+      },
+    };
+
+    if rate_limit.remaining > reserve {
+      return;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+      let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0);
+      let wait = Duration::from_secs(rate_limit.reset.saturating_sub(now));
+
+      info!("rate limit almost reached, waiting {:?}", wait);
+      Delay::new(wait).await;
+    }
+  }
+}
@@ -0,0 +1,126 @@
+use crate::{github_api, RepoIcons, RepoIconsBuilder};
+use cached::{Cached, SizedCache};
+use std::{
+  error::Error,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// the lowercased owner & repo, and the branch the icons were loaded from
+type Key = (String, String, Option<String>);
+
+/// An in-memory LRU cache of [`RepoIcons`] results, with a TTL
+///
+/// ```no_run
+/// # use repo_icons::RepoIconsCache;
+/// # use std::time::Duration;
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = RepoIconsCache::new(Duration::from_secs(60 * 60), 1000);
+///
+/// // only the first load hits the network
+/// let icons = cache.load("facebook", "react").await?;
+/// let icons = cache.load("facebook", "react").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RepoIconsCache {
+  options: RepoIconsBuilder,
+  ttl: Duration,
+  store: Mutex<SizedCache<Key, (Instant, RepoIcons)>>,
+}
+
+impl RepoIconsCache {
+  pub fn new(ttl: Duration, max_entries: usize) -> Self {
+    Self::with_options(RepoIconsBuilder::new(), ttl, max_entries)
+  }
+
+  /// A cache that loads the icons with `options`. Icons of a
+  /// [`RepoIconsBuilder::branch`] are cached separately from the default branch's
+  ///
+  /// ```
+  /// # use repo_icons::{RepoIconsBuilder, RepoIconsCache};
+  /// # use std::time::Duration;
+  /// let cache = RepoIconsCache::with_options(
+  ///   RepoIconsBuilder::balanced(),
+  ///   Duration::from_secs(60 * 60),
+  ///   1000,
+  /// );
+  /// ```
+  pub fn with_options(options: RepoIconsBuilder, ttl: Duration, max_entries: usize) -> Self {
+    Self {
+      options,
+      ttl,
+      store: Mutex::new(SizedCache::with_size(max_entries)),
+    }
+  }
+
+  pub async fn load(&self, owner: &str, repo: &str) -> Result<RepoIcons, Box<dyn Error>> {
+    if let Some(repo_icons) = self.get(owner, repo) {
+      return Ok(repo_icons);
+    }
+
+    self.load_fresh(owner, repo).await
+  }
+
+  /// Load the icons from the network even when they're cached, replacing the
+  /// cached icons. For when a repo is known to have just changed its logo.
+  ///
+  /// The owner's repos & where the repo redirects to are fetched again too,
+  /// but not the redirects of the other repos its readme links to, which are
+  /// shared with the loads of every other repo. The repo's files are cached by
+  /// their tree sha, so they're never stale
+  pub async fn load_fresh(&self, owner: &str, repo: &str) -> Result<RepoIcons, Box<dyn Error>> {
+    github_api::forget_user_repos(owner).await;
+    github_api::forget_repo_redirect(&owner.to_lowercase(), &repo.to_lowercase()).await;
+
+    let repo_icons = self.options.load(owner, repo).await?;
+    // partial results shouldn't stick around for the whole ttl,
+    // but neither should the stale icons they were meant to replace
+    if repo_icons.truncated() {
+      self.invalidate(owner, repo);
+    } else {
+      self.insert(owner, repo, repo_icons.clone());
+    }
+
+    Ok(repo_icons)
+  }
+
+  pub fn get(&self, owner: &str, repo: &str) -> Option<RepoIcons> {
+    let key = self.key(owner, repo);
+    let mut store = self.store.lock().unwrap();
+
+    let repo_icons = store
+      .cache_get(&key)
+      .filter(|(inserted, _)| inserted.elapsed() < self.ttl)
+      .map(|(_, repo_icons)| repo_icons.clone());
+    if repo_icons.is_none() {
+      store.cache_remove(&key);
+    }
+
+    repo_icons
+  }
+
+  pub fn insert(&self, owner: &str, repo: &str, repo_icons: RepoIcons) {
+    self
+      .store
+      .lock()
+      .unwrap()
+      .cache_set(self.key(owner, repo), (Instant::now(), repo_icons));
+  }
+
+  pub fn invalidate(&self, owner: &str, repo: &str) {
+    self
+      .store
+      .lock()
+      .unwrap()
+      .cache_remove(&self.key(owner, repo));
+  }
+
+  fn key(&self, owner: &str, repo: &str) -> Key {
+    (
+      owner.to_lowercase(),
+      repo.to_lowercase(),
+      self.options.branch.clone(),
+    )
+  }
+}
@@ -0,0 +1,41 @@
+use futures::{
+  channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+  lock::Mutex,
+  StreamExt,
+};
+
+/// A semaphore limiting how many requests are in flight at once,
+/// shared by every request of a load
+pub(crate) struct RequestBudget {
+  permits: Mutex<UnboundedReceiver<()>>,
+  release: UnboundedSender<()>,
+}
+
+pub(crate) struct Permit<'a>(&'a RequestBudget);
+
+impl RequestBudget {
+  pub fn new(max_concurrent_requests: usize) -> Self {
+    let (release, permits) = unbounded();
+    for _ in 0..max_concurrent_requests.max(1) {
+      release.unbounded_send(()).unwrap();
+    }
+
+    Self {
+      permits: Mutex::new(permits),
+      release,
+    }
+  }
+
+  /// Wait for a request slot, which is freed when the permit is dropped
+  pub async fn acquire(&self) -> Permit<'_> {
+    // the sender lives as long as the budget, so the stream never ends
+    self.permits.lock().await.next().await;
+    Permit(self)
+  }
+}
+
+impl Drop for Permit<'_> {
+  fn drop(&mut self) {
+    let _ = self.0.release.unbounded_send(());
+  }
+}
@@ -0,0 +1,132 @@
+use crate::{
+  blacklist::is_blacklisted_homepage,
+  client::get_token,
+  github_api::{self, Readme},
+  RepoIconsBuilder,
+};
+use std::error::Error;
+
+/// A rough range of how many requests a load makes, see
+/// [`RepoIconsBuilder::estimate_requests`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestEstimate {
+  pub min: usize,
+  pub max: usize,
+}
+
+impl RequestEstimate {
+  fn add(&mut self, (min, max): (usize, usize)) {
+    self.min += min;
+    self.max += max;
+  }
+}
+
+/// the repo metadata, the readme's html, and the canonical repo if it moved
+const README: (usize, usize) = (2, 3);
+/// the readme's source, for the icon directive
+const README_SOURCE: (usize, usize) = (1, 1);
+/// the graphql query, then rendering the readme with the markdown api
+/// unless the `markdown` feature renders it locally
+const GRAPHQL_README: usize = if cfg!(feature = "markdown") { 1 } else { 2 };
+/// the repo's files, its package.json & the icon file
+const BLOB: (usize, usize) = (1, 4);
+/// the repo's page & the size of the preview
+const SOCIAL_PREVIEW: (usize, usize) = (1, 2);
+/// the page (twice, for `site_icons` & the backgrounds), its manifest & the
+/// sizes of the icons found on it
+const HOMEPAGE: (usize, usize) = (2, 12);
+/// checking whether a link to another repo is a redirect to this repo
+const REDIRECTS_PER_IMAGE: usize = 2;
+
+pub(crate) async fn estimate_requests(
+  options: &RepoIconsBuilder,
+  owner: &str,
+  repo: &str,
+) -> Result<RequestEstimate, Box<dyn Error>> {
+  let readme = Readme::load_with(options, owner, repo).await?;
+  let repos = if options.prefixed_repos {
+    github_api::get_user_repos(options, owner).await?
+  } else {
+    Vec::new()
+  };
+
+  let repo = repo.to_lowercase();
+  let prefixed_repos = repos
+    .iter()
+    .filter(|possibly_prefixed_repo| {
+      **possibly_prefixed_repo != repo && repo.contains(*possibly_prefixed_repo)
+    })
+    .count();
+
+  // the owner's repos
+  let owner_repos = options.prefixed_repos as usize;
+  let mut estimate = RequestEstimate {
+    min: owner_repos,
+    max: owner_repos,
+  };
+
+  // each prefixed repo is a load of its own, whose readme isn't known
+  for _ in 0..=prefixed_repos {
+    estimate.add(readme_requests(options));
+    if options.repo_files {
+      estimate.add(BLOB);
+    }
+    if options.social_preview {
+      estimate.add(SOCIAL_PREVIEW);
+    }
+  }
+  estimate.add((0, prefixed_repos * HOMEPAGE.1));
+
+  // listing `docs/` & `.github/`, and loading a readme in either
+  if options.scan_all_readmes {
+    estimate.add((2, 2 + 2 * README.1));
+  }
+
+  if let Some(homepage) = readme.homepage.as_ref().filter(|_| options.website) {
+    if !is_blacklisted_homepage(homepage) {
+      estimate.add(HOMEPAGE);
+    }
+  }
+
+  let images = readme.images_offline().await;
+  estimate.add((0, images.len() * REDIRECTS_PER_IMAGE));
+  if options.verify_urls {
+    estimate.add((images.len(), images.len()));
+  }
+
+  // the size of every candidate is probed, and svgs are downloaded for their size
+  let mut candidates = images
+    .iter()
+    .find(|image| image.is_logo_candidate())
+    .map_or(0, |image| 1 + image.picture_sources.len());
+  if readme.declared_icon.is_some() {
+    candidates += 1;
+  } else if options.metadata_logo {
+    // the metadata files, and the logo if one declares it
+    let files = options.metadata_files.len();
+    estimate.add((files, files + 2));
+  }
+  if repo.contains(&owner.to_lowercase()) {
+    candidates += 1;
+  }
+  let svg_sizes = if options.svg_sizes { 2 } else { 1 };
+  estimate.add((candidates, candidates * svg_sizes));
+
+  Ok(estimate)
+}
+
+/// Loading the repo metadata & readme, with graphql when it'd be used
+fn readme_requests(options: &RepoIconsBuilder) -> (usize, usize) {
+  let (min, max) = if options.icon_directive {
+    (README.0 + README_SOURCE.0, README.1 + README_SOURCE.1)
+  } else {
+    README
+  };
+
+  if options.use_graphql && options.branch.is_none() && get_token().is_some() {
+    // the query falls back to the rest api when it errors
+    return (GRAPHQL_README, GRAPHQL_README + max);
+  }
+
+  (min, max)
+}
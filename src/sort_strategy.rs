@@ -0,0 +1,51 @@
+use crate::RepoIcon;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How [`crate::RepoIcons::sorted_by`] orders the icons. Icons that compare
+/// equal keep their default order
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SortStrategy {
+  /// the default order, by kind then resolution
+  #[default]
+  Weight,
+  /// largest first, icons with an unknown size are pushed to the end
+  PixelArea,
+  /// closest to 1:1 first, icons with an unknown size are pushed to the end
+  Squareness,
+  /// svgs first
+  PreferVector,
+}
+
+impl SortStrategy {
+  pub(crate) fn compare(&self, a: &RepoIcon, b: &RepoIcon) -> Ordering {
+    match self {
+      SortStrategy::Weight => Ordering::Equal,
+      SortStrategy::PixelArea => compare_known(a, b, |icon| {
+        let (width, height) = icon.dimensions()?;
+        Some(width as f32 * height as f32)
+      }),
+      SortStrategy::Squareness => compare_known(a, b, |icon| {
+        let (width, height) = icon.dimensions()?;
+        let (width, height) = (width as f32, height as f32);
+        Some(width.min(height) / width.max(height))
+      }),
+      SortStrategy::PreferVector => b.is_vector().cmp(&a.is_vector()),
+    }
+  }
+}
+
+/// Compare by a value descending, pushing icons without a value to the end
+fn compare_known<F: Fn(&RepoIcon) -> Option<f32>>(
+  a: &RepoIcon,
+  b: &RepoIcon,
+  value: F,
+) -> Ordering {
+  match (value(a), value(b)) {
+    (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+    (Some(_), None) => Ordering::Less,
+    (None, Some(_)) => Ordering::Greater,
+    (None, None) => Ordering::Equal,
+  }
+}
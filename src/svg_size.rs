@@ -0,0 +1,79 @@
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The notional size of a vector icon
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct VectorSize {
+  pub width: u32,
+  pub height: u32,
+}
+
+/// The notional size of an svg, from its `width` / `height` attributes,
+/// falling back to the `viewBox` for the missing dimensions
+pub(crate) fn parse_svg_size(svg: &str) -> Option<VectorSize> {
+  let document = Html::parse_fragment(svg);
+  let svg = document.select(selector!("svg")).next()?;
+  let svg = svg.value();
+
+  // relative lengths (eg. 100%) depend on the container, so they're ignored
+  let length = |name| {
+    svg
+      .attr(name)
+      .and_then(|value: &str| value.trim().trim_end_matches("px").parse::<f32>().ok())
+      .filter(|value| *value > 0.0)
+  };
+
+  let view_box = svg
+    .attr("viewBox")
+    .or(svg.attr("viewbox"))
+    .and_then(|view_box| {
+      let values = view_box
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+      match values[..] {
+        [_, _, width, height] if width > 0.0 && height > 0.0 => Some((width, height)),
+        _ => None,
+      }
+    });
+
+  let (width, height) = match (length("width"), length("height"), view_box) {
+    (Some(width), Some(height), _) => (width, height),
+    (Some(width), None, Some((view_width, view_height))) => {
+      (width, width * view_height / view_width)
+    }
+    (None, Some(height), Some((view_width, view_height))) => {
+      (height * view_width / view_height, height)
+    }
+    (None, None, Some(view_box)) => view_box,
+    _ => return None,
+  };
+
+  let (width, height) = (width.round() as u32, height.round() as u32);
+  if width == 0 || height == 0 {
+    return None;
+  }
+
+  Some(VectorSize { width, height })
+}
+
+/// The raster embedded by an svg that's only a wrapper around a data uri
+/// `<image>`, so it doesn't scale any better than the raster itself
+pub(crate) fn embedded_raster(svg: &str) -> Option<Url> {
+  let document = Html::parse_fragment(svg);
+
+  document.select(selector!("svg image")).find_map(|image| {
+    // `xlink:href` is parsed into the xlink namespace, so it can't be selected
+    let (_, href) = image.value().attrs().find(|(name, _)| *name == "href")?;
+    let href = href.trim();
+
+    // the data uri is most of the svg, rather than eg. a texture in a real vector
+    let dominant = href.len() * 2 >= svg.len();
+    (dominant && href.starts_with("data:image/") && !href.starts_with("data:image/svg"))
+      .then(|| Url::parse(href).ok())
+      .flatten()
+  })
+}
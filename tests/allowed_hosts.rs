@@ -0,0 +1,78 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::future::LocalBoxFuture;
+use repo_icons::{
+  CandidateSource, Exclusion, IconInfo, IconKind, RepoContext, RepoIcon, RepoIconKind,
+  RepoIconsBuilder,
+};
+use serde_json::json;
+
+const UNTRUSTED: &str = "https://cdn.untrusted.example/logo.svg";
+const RAW: &str = "https://raw.githubusercontent.com/allowed-owner/widget/main/logo.svg";
+const HOMEPAGE: &str = "https://assets.widget.dev/logo.svg";
+const CDN: &str = "https://cdn.jsdelivr.net/gh/allowed-owner/widget/logo.svg";
+
+struct Hosts;
+
+impl CandidateSource for Hosts {
+  fn discover<'a>(&'a self, _: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let kind = RepoIconKind::Site(IconKind::SiteLogo);
+      [UNTRUSTED, RAW, HOMEPAGE, CDN]
+        .iter()
+        .map(|url| RepoIcon::new(url.parse().unwrap(), kind.clone(), IconInfo::SVG))
+        .collect()
+    })
+  }
+}
+
+fn mock(repo: &str) -> MockClient {
+  MockClient::new().repo_with(
+    "allowed-owner",
+    repo,
+    json!({ "homepage": "https://widget.dev" }),
+    &logo_readme(PNG),
+    "",
+  )
+}
+
+#[tokio::test]
+async fn icons_on_untrusted_hosts_are_dropped() {
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock("widget"))
+    .add_source(Hosts)
+    .allowed_hosts(&["cdn.jsdelivr.net"])
+    .debug(true)
+    .load("allowed-owner", "widget")
+    .await
+    .unwrap();
+
+  let urls = icons
+    .iter()
+    .map(|icon| icon.url.as_str())
+    .collect::<Vec<_>>();
+  assert!(!urls.contains(&UNTRUSTED), "{:?}", urls);
+  for url in [RAW, HOMEPAGE, CDN, PNG] {
+    assert!(urls.contains(&url), "{} isn't in {:?}", url, urls);
+  }
+
+  let untrusted = icons
+    .candidates()
+    .iter()
+    .find(|candidate| candidate.url == UNTRUSTED)
+    .unwrap();
+  assert_eq!(untrusted.excluded, Some(Exclusion::UntrustedHost));
+}
+
+#[tokio::test]
+async fn every_host_is_allowed_by_default() {
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock("unrestricted"))
+    .add_source(Hosts)
+    .load("allowed-owner", "unrestricted")
+    .await
+    .unwrap();
+
+  assert!(icons.iter().any(|icon| icon.url.as_str() == UNTRUSTED));
+}
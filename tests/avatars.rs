@@ -0,0 +1,108 @@
+mod common;
+
+use common::{MockClient, Server, PNG_BYTES};
+use repo_icons::{IconInfo, RepoIcon, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+fn avatar(url: &str) -> RepoIcon {
+  RepoIcon::new(
+    url.parse().unwrap(),
+    RepoIconKind::UserAvatar,
+    IconInfo::SVG,
+  )
+}
+
+/// An owner whose avatar is served by `avatars`
+fn owner(login: &str, avatars: &Server) -> MockClient {
+  MockClient::new().route(
+    &format!("https://api.github.com/users/{}", login),
+    200,
+    &json!({
+      "login": login,
+      "type": "User",
+      "avatar_url": avatars.url(&format!("/u/{}", login)),
+    })
+    .to_string(),
+  )
+}
+
+#[test]
+fn avatars_can_be_requested_at_any_size() {
+  assert_eq!(
+    avatar("https://github.com/octocat.png?size=460")
+      .avatar_url(64)
+      .unwrap()
+      .as_str(),
+    "https://github.com/octocat.png?size=64"
+  );
+  assert_eq!(
+    avatar("https://avatars.githubusercontent.com/u/583231?v=4&s=460")
+      .avatar_url(40)
+      .unwrap()
+      .as_str(),
+    "https://avatars.githubusercontent.com/u/583231?v=4&s=40"
+  );
+
+  let logo = RepoIcon::new(
+    "https://cdn.example.org/logo.svg".parse().unwrap(),
+    RepoIconKind::ReadmeImage,
+    IconInfo::SVG,
+  );
+  assert_eq!(logo.avatar_url(64), None);
+}
+
+#[tokio::test]
+async fn an_owners_avatar_is_loaded_at_a_high_resolution() {
+  let avatars = Server::start().page("/u/sized-user", "image/png", PNG_BYTES);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(owner("sized-user", &avatars))
+    .load_owner("sized-user")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].url.path(), "/u/sized-user");
+  assert_eq!(icons[0].url.query(), Some("s=460"));
+  assert!(!icons[0].default_avatar);
+}
+
+/// A github style identicon, a symmetric 5x5 grid of one color on `#f0f0f0`
+#[cfg(feature = "image")]
+fn identicon() -> Vec<u8> {
+  use image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+
+  let grid = ["10001", "01110", "11011", "01010", "10101"];
+  let image = RgbImage::from_fn(70, 70, |x, y| {
+    let cell = |n: u32| (n.saturating_sub(10) / 10).min(4) as usize;
+    let border = !(10..60).contains(&x) || !(10..60).contains(&y);
+    if !border && grid[cell(y)].as_bytes()[cell(x)] == b'1' {
+      Rgb([0x4c, 0xa2, 0x5e])
+    } else {
+      Rgb([0xf0, 0xf0, 0xf0])
+    }
+  });
+
+  let mut png = Vec::new();
+  DynamicImage::ImageRgb8(image)
+    .write_to(&mut std::io::Cursor::new(&mut png), ImageOutputFormat::Png)
+    .unwrap();
+  png
+}
+
+#[cfg(feature = "image")]
+#[tokio::test]
+async fn a_default_identicon_is_flagged() {
+  let png = identicon();
+  let avatars = Server::start().page("/u/identicon-user", "image/png", &png);
+  let mock =
+    owner("identicon-user", &avatars).route_bytes(&avatars.url("/u/identicon-user"), 200, &png);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_owner("identicon-user")
+    .await
+    .unwrap();
+
+  assert!(icons[0].default_avatar);
+  assert!(icons.primary().unwrap().confidence < 0.1);
+}
@@ -0,0 +1,41 @@
+use repo_icons::Readme;
+
+async fn image_src(src: &str) -> (String, bool) {
+  let html = format!(
+    r#"<div id="readme"><article><img src="{}" alt="logo"></article></div>"#,
+    src
+  );
+  let readme = Readme::new("blob-owner", "widget", &html, false, "main", None);
+
+  let image = readme.images_offline().await.remove(0);
+  (image.src.to_string(), image.sourced_from_repo)
+}
+
+const RAW: &str = "https://raw.githubusercontent.com/blob-owner/widget/main/assets/logo.png";
+
+#[tokio::test]
+async fn blob_urls_with_raw_true_are_rewritten_to_raw_urls() {
+  assert_eq!(
+    image_src("https://github.com/blob-owner/widget/blob/main/assets/logo.png?raw=true").await,
+    (RAW.to_string(), true)
+  );
+}
+
+#[tokio::test]
+async fn relative_blob_paths_are_rewritten_to_raw_urls() {
+  assert_eq!(
+    image_src("/blob-owner/widget/blob/main/assets/logo.png?raw=true").await,
+    (RAW.to_string(), true)
+  );
+}
+
+#[tokio::test]
+async fn raw_and_tree_paths_are_recognized_the_same() {
+  for kind in ["raw", "tree"] {
+    let src = format!(
+      "https://github.com/blob-owner/widget/{}/main/assets/logo.png",
+      kind
+    );
+    assert_eq!(image_src(&src).await, (RAW.to_string(), true), "{}", kind);
+  }
+}
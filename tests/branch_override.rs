@@ -0,0 +1,69 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+
+const README: &str = r#"<div id="readme"><article><p align="center"><img src="assets/logo.png" alt="logo" width="200" height="200"></p><h1>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn the_branch_override_is_used_for_raw_urls() {
+  let mock = MockClient::new()
+    .repo("branch-owner", "widget", README)
+    .route(
+      "https://api.github.com/repos/branch-owner/widget/branches/release",
+      200,
+      r#"{"name":"release"}"#,
+    );
+  let options = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .branch("release");
+
+  let readme = Readme::load_with(&options, "branch-owner", "widget")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/branch-owner/widget/release/assets/logo.png"
+  );
+  assert!(images[0].sourced_from_repo);
+  assert!(mock
+    .urls()
+    .contains(&"https://api.github.com/repos/branch-owner/widget/readme?ref=release".to_string()));
+}
+
+#[tokio::test]
+async fn the_default_branch_is_used_without_an_override() {
+  let mock = MockClient::new().repo("branch-owner", "default", README);
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+
+  let readme = Readme::load_with(&options, "branch-owner", "default")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/branch-owner/default/main/assets/logo.png"
+  );
+  assert!(!mock.urls().iter().any(|url| url.contains("/branches/")));
+}
+
+#[tokio::test]
+async fn a_missing_branch_is_an_error() {
+  let mock = MockClient::new().repo("branch-owner", "missing", README);
+  let options = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .branch("does-not-exist");
+
+  let err = match Readme::load_with(&options, "branch-owner", "missing").await {
+    Ok(_) => panic!("loaded a branch that doesn't exist"),
+    Err(err) => err,
+  };
+
+  assert_eq!(
+    err.to_string(),
+    "branch does-not-exist doesn't exist in branch-owner/missing"
+  );
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "cache")]
+
+mod common;
+
+use common::{logo_readme, MockClient, PNG, SQUARE_PNG};
+use repo_icons::{RepoIconsBuilder, RepoIconsCache};
+use std::time::Duration;
+
+fn cache(mock: &MockClient, ttl: Duration) -> RepoIconsCache {
+  RepoIconsCache::with_options(RepoIconsBuilder::fast().http_client(mock.clone()), ttl, 10)
+}
+
+#[tokio::test]
+async fn cache_hit_makes_no_requests() {
+  let mock = MockClient::new().repo("cache-owner", "widget", &logo_readme(PNG));
+  let cache = cache(&mock, Duration::from_secs(60));
+
+  let icons = cache.load("cache-owner", "widget").await.unwrap();
+  let requests = mock.request_count();
+  assert!(requests > 0);
+
+  let cached = cache.load("Cache-Owner", "Widget").await.unwrap();
+  assert_eq!(cached, icons);
+  assert_eq!(mock.request_count(), requests);
+}
+
+#[tokio::test]
+async fn sub_second_ttls_expire() {
+  let mock = MockClient::new().repo("cache-ttl-owner", "widget", &logo_readme(PNG));
+  let cache = cache(&mock, Duration::from_millis(50));
+
+  cache.load("cache-ttl-owner", "widget").await.unwrap();
+  assert!(cache.get("cache-ttl-owner", "widget").is_some());
+
+  let requests = mock.request_count();
+
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  assert!(cache.get("cache-ttl-owner", "widget").is_none());
+
+  // the expired entry is fetched again, and cached again
+  cache.load("cache-ttl-owner", "widget").await.unwrap();
+  assert!(mock.request_count() > requests);
+  assert!(cache.get("cache-ttl-owner", "widget").is_some());
+}
+
+#[tokio::test]
+async fn load_fresh_fetches_the_owners_repos_again() {
+  let owner_repos = "https://api.github.com/users/cache-fresh-owner/repos";
+  let mock = MockClient::new()
+    .repo("cache-fresh-owner", "widget", &logo_readme(PNG))
+    .route(owner_repos, 200, r#"[{ "name": "widget" }]"#);
+  let cache = RepoIconsCache::with_options(
+    RepoIconsBuilder::new().http_client(mock.clone()),
+    Duration::from_secs(60),
+    10,
+  );
+  let fetches = || {
+    mock
+      .urls()
+      .iter()
+      .filter(|url| url.starts_with(owner_repos))
+      .count()
+  };
+
+  cache.load("cache-fresh-owner", "widget").await.unwrap();
+  cache.load("cache-fresh-owner", "widget").await.unwrap();
+  assert_eq!(fetches(), 1);
+
+  // the repo's logo changed since it was cached
+  mock
+    .clone()
+    .repo("cache-fresh-owner", "widget", &logo_readme(SQUARE_PNG));
+  let fresh = cache
+    .load_fresh("cache-fresh-owner", "widget")
+    .await
+    .unwrap();
+  assert_eq!(fetches(), 2);
+  assert_eq!(fresh[0].url.as_str(), SQUARE_PNG);
+
+  let cached = cache.get("cache-fresh-owner", "widget").unwrap();
+  assert_eq!(cached, fresh);
+  assert_eq!(cached[0].url.as_str(), SQUARE_PNG);
+}
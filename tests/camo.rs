@@ -0,0 +1,56 @@
+use repo_icons::{Exclusion, Readme};
+
+const LOGO: &str = "https://example.org/logo.png";
+
+/// `camo.githubusercontent.com/<digest>/<hex encoded url>`
+fn camo(url: &str) -> String {
+  let hex = url
+    .bytes()
+    .map(|byte| format!("{:02x}", byte))
+    .collect::<String>();
+  format!("https://camo.githubusercontent.com/0123abcd/{}", hex)
+}
+
+fn readme(html: &str, resolve_camo: bool) -> Readme {
+  let html = format!(r#"<div id="readme"><article>{}</article></div>"#, html);
+  let mut readme = Readme::new("camo-owner", "widget", &html, false, "main", None);
+  readme.resolve_camo = resolve_camo;
+  readme
+}
+
+#[tokio::test]
+async fn a_camo_wrapped_badge_is_excluded_by_its_canonical_url() {
+  let html = format!(
+    r#"<img src="{}" data-canonical-src="https://img.shields.io/badge/build-passing-green">"#,
+    camo("https://img.shields.io/badge/build-passing-green")
+  );
+
+  let (images, excluded) = readme(&html, false).images_with_excluded().await;
+
+  assert!(images.is_empty());
+  assert_eq!(excluded[0].excluded, Some(Exclusion::Badge));
+}
+
+#[tokio::test]
+async fn a_camo_wrapped_logo_keeps_both_urls() {
+  let html = format!(r#"<img src="{}" alt="logo">"#, camo(LOGO));
+
+  let image = readme(&html, false).images().await.remove(0);
+
+  assert_eq!(image.src.as_str(), camo(LOGO));
+  assert_eq!(image.canonical_src.unwrap().as_str(), LOGO);
+}
+
+#[tokio::test]
+async fn resolve_camo_uses_the_original_url() {
+  let html = format!(
+    r#"<img src="{}" data-canonical-src="{}" alt="logo">"#,
+    camo(LOGO),
+    LOGO
+  );
+
+  let image = readme(&html, true).images().await.remove(0);
+
+  assert_eq!(image.src.as_str(), LOGO);
+  assert_eq!(image.canonical_src.unwrap().as_str(), LOGO);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::{future, StreamExt};
+use repo_icons::{Cancelled, RankingSnapshot, RepoIconsBuilder};
+use std::time::{Duration, Instant};
+
+fn slow_mock(repo: &str) -> MockClient {
+  MockClient::new()
+    .repo("cancel-owner", repo, &logo_readme(PNG))
+    .delay(Duration::from_millis(300))
+}
+
+#[tokio::test]
+async fn cancelling_mid_load_resolves_to_cancelled() {
+  let mock = slow_mock("widget");
+  let start = Instant::now();
+
+  let result = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load_cancellable(
+      "cancel-owner",
+      "widget",
+      tokio::time::sleep(Duration::from_millis(50)),
+    )
+    .await;
+
+  let err = result.expect_err("the load was cancelled");
+  assert!(err.downcast_ref::<Cancelled>().is_some(), "{}", err);
+  assert!(start.elapsed() < Duration::from_millis(250));
+  // the in-flight requests were dropped before they were answered
+  assert_eq!(mock.request_count(), 0);
+}
+
+#[tokio::test]
+async fn a_load_that_isnt_cancelled_completes() {
+  let icons = RepoIconsBuilder::fast()
+    .http_client(MockClient::new().repo("cancel-owner", "completed", &logo_readme(PNG)))
+    .load_cancellable("cancel-owner", "completed", future::pending())
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].url.as_str(), PNG);
+}
+
+#[tokio::test]
+async fn a_cancelled_progressive_load_ends_with_cancelled() {
+  let snapshots = RepoIconsBuilder::fast()
+    .http_client(slow_mock("progressive"))
+    .load_progressive_cancellable(
+      "cancel-owner",
+      "progressive",
+      tokio::time::sleep(Duration::from_millis(50)),
+    )
+    .collect::<Vec<_>>()
+    .await;
+
+  let last = snapshots.last().unwrap();
+  let err = last.as_ref().expect_err("the last snapshot is the error");
+  assert!(err.downcast_ref::<Cancelled>().is_some(), "{}", err);
+  assert!(!snapshots
+    .iter()
+    .any(|snapshot| matches!(snapshot, Ok(RankingSnapshot::Complete(_)))));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::future::LocalBoxFuture;
+use repo_icons::{
+  CandidateSource, IconInfo, IconKind, RepoContext, RepoIcon, RepoIconKind, RepoIconsBuilder,
+};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+type Contexts = Arc<Mutex<Vec<(String, String, Option<String>)>>>;
+
+struct Cdn {
+  contexts: Contexts,
+}
+
+impl CandidateSource for Cdn {
+  fn discover<'a>(&'a self, ctx: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let homepage = ctx.homepage.as_ref().map(|homepage| homepage.to_string());
+      let context = (ctx.owner.clone(), ctx.repo.clone(), homepage);
+      self.contexts.lock().unwrap().push(context);
+
+      let kind = RepoIconKind::Site(IconKind::SiteLogo);
+      let cdn_url = format!("https://cdn.example.org/{}.svg", ctx.repo);
+      vec![
+        RepoIcon::new(cdn_url.parse().unwrap(), kind.clone(), IconInfo::SVG),
+        // also found in the readme, so it's deduplicated
+        RepoIcon::new(PNG.parse().unwrap(), kind, IconInfo::SVG),
+      ]
+    })
+  }
+}
+
+#[tokio::test]
+async fn a_custom_source_feeds_the_results() {
+  let mock = MockClient::new().repo_with(
+    "source-owner",
+    "widget",
+    json!({ "homepage": "https://widget.dev" }),
+    &logo_readme(PNG),
+    "",
+  );
+  let contexts = Contexts::default();
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .add_source(Cdn {
+      contexts: contexts.clone(),
+    })
+    .load("source-owner", "widget")
+    .await
+    .unwrap();
+
+  let urls = icons
+    .iter()
+    .map(|icon| icon.url.as_str())
+    .collect::<Vec<_>>();
+  assert!(
+    urls.contains(&"https://cdn.example.org/widget.svg"),
+    "{:?}",
+    urls
+  );
+  assert_eq!(urls.iter().filter(|url| **url == PNG).count(), 1);
+
+  assert_eq!(
+    *contexts.lock().unwrap(),
+    [(
+      "source-owner".to_string(),
+      "widget".to_string(),
+      Some("https://widget.dev/".to_string())
+    )]
+  );
+}
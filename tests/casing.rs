@@ -0,0 +1,62 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+const HTML: &str = r#"<div id="readme"><article><p align="center"><img src="assets/Logo.png" alt="logo"><img src="/docs/Banner.png"></p><h1>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn raw_urls_keep_the_repos_casing() {
+  let readme = Readme::new("CasedOwner", "Widget-Kit", HTML, false, "Main", None);
+
+  let srcs = readme
+    .images()
+    .await
+    .into_iter()
+    .map(|image| image.src.to_string())
+    .collect::<Vec<_>>();
+
+  assert!(srcs.contains(
+    &"https://raw.githubusercontent.com/CasedOwner/Widget-Kit/Main/assets/Logo.png".to_string()
+  ));
+  assert!(srcs.contains(
+    &"https://raw.githubusercontent.com/CasedOwner/Widget-Kit/Main/docs/Banner.png".to_string()
+  ));
+  // matching is still case insensitive
+  assert_eq!(readme.owner, "casedowner");
+  assert_eq!(readme.repo, "widget-kit");
+}
+
+#[tokio::test]
+async fn a_loaded_repo_uses_the_canonical_casing() {
+  let mock = MockClient::new()
+    .route(
+      "https://api.github.com/repos/casedowner/loaded-kit",
+      200,
+      &json!({
+        "owner": { "login": "CasedOwner" },
+        "name": "Loaded-Kit",
+        "default_branch": "Develop",
+        "private": false,
+        "homepage": null,
+      })
+      .to_string(),
+    )
+    .route(
+      "https://api.github.com/repos/casedowner/loaded-kit/readme",
+      200,
+      HTML,
+    );
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  // requested in lowercase, github responds with the canonical casing
+  let readme = Readme::load_with(&options, "casedowner", "loaded-kit")
+    .await
+    .unwrap();
+
+  assert_eq!(
+    readme.images().await[0].src.as_str(),
+    "https://raw.githubusercontent.com/CasedOwner/Loaded-Kit/Develop/assets/Logo.png"
+  );
+}
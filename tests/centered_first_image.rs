@@ -0,0 +1,37 @@
+use repo_icons::{Readme, Signal};
+
+fn readme(html: &str) -> Readme {
+  Readme::new("centered-owner", "widget", html, false, "main", None)
+}
+
+#[tokio::test]
+async fn a_centered_logo_before_the_heading_ranks_first() {
+  let readme = readme(
+    r#"<div id="readme"><article><p align="center"><img src="https://widget.dev/logo.png" width="160" alt="Widget"></p><h1>Widget</h1><p>A widget toolkit.</p><p><img src="docs/screenshot.png" width="800" alt="widget logo in use"></p><h2>Usage</h2></article></div>"#,
+  );
+
+  let images = readme.images_offline().await;
+
+  assert_eq!(images[0].src.as_str(), "https://widget.dev/logo.png");
+  assert!(images[0].is_first_image);
+  assert!(images[0].is_align_center);
+  assert!(
+    images[0].signals().contains(&Signal::CenteredFirstImage),
+    "{:?}",
+    images[0].signals()
+  );
+  assert!(images[0].is_logo_candidate());
+  assert!(images[0].weight() > images[1].weight());
+}
+
+#[tokio::test]
+async fn a_centered_first_image_needs_a_size_or_to_be_from_the_repo() {
+  let readme = readme(
+    r#"<div id="readme"><article><p align="center"><img src="https://widget.dev/logo.png"></p><h1>Widget</h1></article></div>"#,
+  );
+
+  let images = readme.images_offline().await;
+
+  assert!(images[0].is_align_center);
+  assert!(!images[0].signals().contains(&Signal::CenteredFirstImage));
+}
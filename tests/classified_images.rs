@@ -0,0 +1,50 @@
+use repo_icons::{ImageKind, Readme};
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn every_image_of_a_mixed_readme_is_classified() {
+  let html = r#"<div id="readme"><article>
+    <p align="center"><img src="https://example.org/logo.png" alt="Widget logo" width="200" height="200"></p>
+    <h1>Widget</h1>
+    <p>
+      <img src="https://img.shields.io/badge/build-passing-green.svg" alt="build">
+      <img src="https://img.shields.io/npm/v/widget.svg" alt="npm">
+    </p>
+    <img src="https://example.org/banner.png" alt="banner">
+    <h2>Demo</h2>
+    <img src="https://example.org/screenshot.png" alt="screenshot of the editor">
+    <img src="https://opengraph.githubassets.com/1/classified-owner/widget" alt="preview">
+    <h2>Contributors</h2>
+    <img src="https://avatars.githubusercontent.com/u/1" alt="someone">
+    <img src="https://example.org/diagram.png" alt="architecture">
+  </article></div>"#;
+  let readme = Readme::new("classified-owner", "widget", html, false, "main", None);
+
+  let images = readme.classified_images().await;
+  let mut counts = HashMap::new();
+  for image in &images {
+    *counts.entry(image.kind).or_insert(0) += 1;
+  }
+
+  assert_eq!(images.len(), 8);
+  assert_eq!(counts[&ImageKind::Logo], 1);
+  assert_eq!(counts[&ImageKind::Badge], 2);
+  assert_eq!(counts[&ImageKind::Banner], 1);
+  assert_eq!(counts[&ImageKind::Screenshot], 1);
+  assert_eq!(counts[&ImageKind::Placeholder], 1);
+  assert_eq!(counts[&ImageKind::Avatar], 1);
+  assert_eq!(counts[&ImageKind::Other], 1);
+
+  // badges are excluded before they're scored
+  let badge = images
+    .iter()
+    .find(|image| image.kind == ImageKind::Badge)
+    .unwrap();
+  assert!(badge.image.is_none());
+  let logo = images
+    .iter()
+    .find(|image| image.kind == ImageKind::Logo)
+    .unwrap();
+  assert_eq!(logo.src.as_str(), "https://example.org/logo.png");
+  assert!(logo.image.is_some());
+}
@@ -0,0 +1,38 @@
+use repo_icons::{Readme, Signal};
+
+/// The same image in a collapsed `<details>`, then an open one, then in the
+/// `<summary>` of a collapsed one, all below the primary heading's section.
+/// They mention a logo, so the penalty isn't lost to the weight's clamping
+const README: &str = r#"<div id="readme"><article><h1>Widget</h1><p>A widget toolkit.</p><h2>Screenshots</h2>
+<details><summary>Light theme</summary><p><img src="assets/collapsed.png" alt="logo" width="300"></p></details>
+<details open><summary>Dark theme</summary><p><img src="assets/open.png" alt="logo" width="300"></p></details>
+<details><summary><img src="assets/summary.png" alt="logo" width="300"></summary><p>More themes</p></details>
+</article></div>"#;
+
+#[tokio::test]
+async fn an_open_details_outranks_a_collapsed_one() {
+  let readme = Readme::new("details-owner", "widget", README, false, "main", None);
+
+  let images = readme.images_offline().await;
+  let image = |file: &str| {
+    images
+      .iter()
+      .find(|image| image.src.path().ends_with(file))
+      .unwrap()
+  };
+
+  let collapsed = image("/collapsed.png");
+  assert!(collapsed.in_collapsed_details);
+  assert!(collapsed.signals().contains(&Signal::InCollapsedDetails));
+  // the summary is shown while it's collapsed
+  assert!(!image("/open.png").in_collapsed_details);
+  assert!(!image("/summary.png").in_collapsed_details);
+
+  // penalized rather than excluded, despite being further up
+  let files = images
+    .iter()
+    .map(|image| image.src.path().rsplit('/').next().unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(files, ["open.png", "summary.png", "collapsed.png"]);
+  assert!(collapsed.weight() < image("/open.png").weight());
+}
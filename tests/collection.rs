@@ -0,0 +1,48 @@
+mod common;
+
+use common::{MockClient, PNG, SQUARE_PNG};
+use repo_icons::{RepoIcons, RepoIconsBuilder};
+
+async fn load() -> RepoIcons {
+  let html = format!(
+    r#"<div id="readme"><article><h1><picture><source media="(prefers-color-scheme: dark)" srcset="{}"><img src="{}" alt="logo" width="200" height="200"></picture> Widget</h1></article></div>"#,
+    SQUARE_PNG, PNG
+  );
+  let mock = MockClient::new().repo("collection-owner", "widget", &html);
+
+  RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("collection-owner", "widget")
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn a_loaded_result_can_be_indexed_and_iterated() {
+  let icons = load().await;
+
+  assert!(!icons.is_empty());
+  assert_eq!(icons.len(), 2);
+  assert_eq!(icons.as_slice().len(), icons.len());
+  assert_eq!(icons.get(0), Some(&icons[0]));
+  assert_eq!(icons.get(icons.len()), None);
+
+  let borrowed = (&icons)
+    .into_iter()
+    .map(|icon| icon.url.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    borrowed,
+    icons
+      .iter()
+      .map(|icon| icon.url.clone())
+      .collect::<Vec<_>>()
+  );
+  assert_eq!(borrowed[0], icons[0].url);
+
+  // the ranked order is kept when iterating by value
+  let first = icons[0].clone();
+  let owned = icons.into_iter().collect::<Vec<_>>();
+  assert_eq!(owned.len(), 2);
+  assert_eq!(owned[0], first);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, SQUARE_PNG};
+use repo_icons::RepoIconsBuilder;
+
+#[tokio::test]
+async fn light_and_dark_variants_are_grouped_separately() {
+  let readme = format!(
+    r#"<div id="readme"><article><p align="center"><picture><source media="(prefers-color-scheme: dark)" srcset="{}"><img src="{}" alt="logo" width="200" height="200"></picture></p><h1>Project</h1></article></div>"#,
+    SQUARE_PNG, PNG
+  );
+  let mock = MockClient::new().repo("scheme-owner", "themed", &readme);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("scheme-owner", "themed")
+    .await
+    .unwrap();
+  let sets = icons.by_color_scheme();
+
+  let urls = |icons: &[&repo_icons::RepoIcon]| {
+    icons
+      .iter()
+      .map(|icon| icon.url.to_string())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(urls(&sets.light), [PNG]);
+  assert_eq!(urls(&sets.dark), [SQUARE_PNG]);
+  assert!(sets.any.is_empty(), "{:?}", urls(&sets.any));
+}
+
+#[tokio::test]
+async fn icons_without_a_scheme_are_any() {
+  let mock = MockClient::new().repo("scheme-owner", "plain", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("scheme-owner", "plain")
+    .await
+    .unwrap();
+  let sets = icons.by_color_scheme();
+
+  assert!(sets.light.is_empty());
+  assert!(sets.dark.is_empty());
+  assert_eq!(sets.any.len(), icons.len());
+  assert_eq!(sets.any[0].url.as_str(), PNG);
+}
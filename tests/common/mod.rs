@@ -0,0 +1,249 @@
+//! An [`HttpClient`] that answers from canned routes and records the requests,
+//! so loads can be tested without the network
+#![allow(dead_code)]
+
+mod server;
+
+#[allow(unused_imports)]
+pub use server::Server;
+
+use futures::future::LocalBoxFuture;
+use repo_icons::HttpClient;
+use reqwest::{header::ACCEPT, Request, Response, ResponseBuilderExt};
+use serde_json::json;
+use std::{
+  error::Error,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+  },
+  time::Duration,
+};
+
+/// a 1x1 png
+pub const PNG: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
+/// the bytes of a 1x1 png
+pub const PNG_BYTES: &[u8] = &[
+  0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+  0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+  0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x60, 0x60, 0x60, 0xf8,
+  0x0f, 0x00, 0x01, 0x04, 0x01, 0x00, 0x5f, 0xe5, 0xc3, 0x4b, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+  0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+/// a 2x2 png
+pub const SQUARE_PNG: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAYAAABytg0kAAAAEElEQVR4nGNgYGD4D8UQBgAd9AP9yOH2qAAAAABJRU5ErkJggg==";
+/// a 3x1 png
+pub const WIDE_PNG: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAMAAAABCAYAAAAb4BS0AAAADklEQVR4nGNgYGD4D8MADv4C/iBZvuwAAAAASUVORK5CYII=";
+
+struct Route {
+  url: String,
+  method: Option<String>,
+  accept: Option<String>,
+  status: u16,
+  headers: Vec<(String, String)>,
+  body: Vec<u8>,
+}
+
+#[derive(Default)]
+struct Inner {
+  routes: Mutex<Vec<Route>>,
+  requests: Mutex<Vec<Request>>,
+  in_flight: AtomicUsize,
+  max_in_flight: AtomicUsize,
+  delay: Mutex<Option<Duration>>,
+  response_headers: Mutex<Vec<(String, String)>>,
+}
+
+#[derive(Clone, Default)]
+pub struct MockClient(Arc<Inner>);
+
+impl MockClient {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Answer requests to `url` (ignoring the query), the latest route wins
+  pub fn route(self, url: &str, status: u16, body: &str) -> Self {
+    self.route_bytes(url, status, body.as_bytes())
+  }
+
+  /// [`MockClient::route`] with a binary body
+  pub fn route_bytes(self, url: &str, status: u16, body: &[u8]) -> Self {
+    self.0.routes.lock().unwrap().push(Route {
+      url: url.to_string(),
+      method: None,
+      accept: None,
+      status,
+      headers: Vec::new(),
+      body: body.to_vec(),
+    });
+    self
+  }
+
+  /// Only match the last route for requests with the `method`
+  pub fn method(self, method: &str) -> Self {
+    self.last_route(|route| route.method = Some(method.to_string()));
+    self
+  }
+
+  /// Only match the last route when the request's Accept contains `accept`
+  pub fn accept(self, accept: &str) -> Self {
+    self.last_route(|route| route.accept = Some(accept.to_string()));
+    self
+  }
+
+  /// Add a header to the last route's response
+  pub fn header(self, name: &str, value: &str) -> Self {
+    self.last_route(|route| route.headers.push((name.to_string(), value.to_string())));
+    self
+  }
+
+  /// Add a header to every response
+  pub fn response_header(self, name: &str, value: &str) -> Self {
+    let header = (name.to_string(), value.to_string());
+    self.0.response_headers.lock().unwrap().push(header);
+    self
+  }
+
+  /// Wait before answering, so requests overlap
+  pub fn delay(self, delay: Duration) -> Self {
+    *self.0.delay.lock().unwrap() = Some(delay);
+    self
+  }
+
+  /// The repo's metadata, its rendered readme & the readme's source
+  pub fn repo(self, owner: &str, repo: &str, readme_html: &str) -> Self {
+    self.repo_with(owner, repo, json!({}), readme_html, "")
+  }
+
+  /// [`MockClient::repo`] with extra metadata fields & the readme's source
+  pub fn repo_with(
+    self,
+    owner: &str,
+    repo: &str,
+    metadata: serde_json::Value,
+    readme_html: &str,
+    readme_source: &str,
+  ) -> Self {
+    let mut repo_json = json!({
+      "owner": { "login": owner },
+      "name": repo,
+      "default_branch": "main",
+      "private": false,
+      "homepage": null,
+      "topics": [],
+      "language": null,
+      "description": null,
+    });
+    for (key, value) in metadata.as_object().unwrap() {
+      repo_json[key] = value.clone();
+    }
+
+    let api = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    self
+      .route(&api, 200, &repo_json.to_string())
+      .route(&format!("{}/readme", api), 200, readme_html)
+      .accept("html")
+      .route(&format!("{}/readme", api), 200, readme_source)
+      .accept("raw")
+  }
+
+  pub fn urls(&self) -> Vec<String> {
+    let requests = self.0.requests.lock().unwrap();
+    requests
+      .iter()
+      .map(|request| request.url().to_string())
+      .collect()
+  }
+
+  pub fn request_count(&self) -> usize {
+    self.0.requests.lock().unwrap().len()
+  }
+
+  /// The most requests that were in flight at once
+  pub fn max_in_flight(&self) -> usize {
+    self.0.max_in_flight.load(Ordering::SeqCst)
+  }
+
+  /// The value of a header the `nth` request was sent with
+  pub fn request_header(&self, nth: usize, name: &str) -> Option<String> {
+    let requests = self.0.requests.lock().unwrap();
+    let value = requests.get(nth)?.headers().get(name)?;
+    Some(value.to_str().unwrap().to_string())
+  }
+
+  fn last_route(&self, f: impl FnOnce(&mut Route)) {
+    f(self.0.routes.lock().unwrap().last_mut().unwrap())
+  }
+
+  fn respond(&self, request: &Request) -> Response {
+    let mut url = request.url().clone();
+    url.set_query(None);
+    let accept = request
+      .headers()
+      .get(ACCEPT)
+      .and_then(|accept| accept.to_str().ok())
+      .unwrap_or_default();
+
+    let routes = self.0.routes.lock().unwrap();
+    let route = routes.iter().rev().find(|route| {
+      route.url == url.as_str()
+        && route
+          .method
+          .as_ref()
+          .is_none_or(|method| method == request.method().as_str())
+        && route
+          .accept
+          .as_ref()
+          .is_none_or(|route_accept| accept.contains(route_accept.as_str()))
+    });
+
+    // the requested url, as the mock doesn't redirect
+    let mut response = http::Response::builder().url(request.url().clone());
+    for (name, value) in self.0.response_headers.lock().unwrap().iter() {
+      response = response.header(name.as_str(), value.as_str());
+    }
+    let body = match route {
+      Some(route) => {
+        response = response.status(route.status);
+        for (name, value) in &route.headers {
+          response = response.header(name.as_str(), value.as_str());
+        }
+        route.body.clone()
+      }
+      None => {
+        response = response.status(404);
+        br#"{"message":"Not Found"}"#.to_vec()
+      }
+    };
+
+    response.body(body).unwrap().into()
+  }
+}
+
+impl HttpClient for MockClient {
+  fn execute(&self, request: Request) -> LocalBoxFuture<'_, Result<Response, Box<dyn Error>>> {
+    Box::pin(async move {
+      let in_flight = self.0.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+      self.0.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+      let delay = *self.0.delay.lock().unwrap();
+      if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+      }
+
+      let response = self.respond(&request);
+      self.0.requests.lock().unwrap().push(request);
+      self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+      Ok(response)
+    })
+  }
+}
+
+/// A readme with a single logo
+pub fn logo_readme(src: &str) -> String {
+  format!(
+    r#"<div id="readme"><article><p align="center"><img src="{}" alt="logo" width="200" height="200"></p><h1>Project</h1></article></div>"#,
+    src
+  )
+}
@@ -0,0 +1,127 @@
+//! A local http server, for the requests `site_icons` makes with its own
+//! client, which a [`super::MockClient`] can't answer
+
+use std::{
+  collections::HashMap,
+  io::{BufRead, BufReader, Write},
+  net::{TcpListener, TcpStream},
+  sync::{Arc, Mutex},
+  thread,
+  time::Duration,
+};
+
+struct Page {
+  status: u16,
+  content_type: String,
+  body: Vec<u8>,
+  delay: Option<Duration>,
+}
+
+#[derive(Clone)]
+pub struct Server {
+  origin: String,
+  pages: Arc<Mutex<HashMap<String, Page>>>,
+  requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Server {
+  pub fn start() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = Server {
+      origin: format!("http://{}", listener.local_addr().unwrap()),
+      pages: Default::default(),
+      requests: Default::default(),
+    };
+
+    let handler = server.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        let handler = handler.clone();
+        thread::spawn(move || handler.respond(stream));
+      }
+    });
+
+    server
+  }
+
+  /// The absolute url of a path on the server
+  pub fn url(&self, path: &str) -> String {
+    format!("{}{}", self.origin, path)
+  }
+
+  /// Serve `body` at `path`, unknown paths are a 404
+  pub fn page(self, path: &str, content_type: &str, body: &[u8]) -> Self {
+    self.page_with(path, 200, content_type, body, None)
+  }
+
+  pub fn page_with(
+    self,
+    path: &str,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+    delay: Option<Duration>,
+  ) -> Self {
+    let page = Page {
+      status,
+      content_type: content_type.to_string(),
+      body: body.to_vec(),
+      delay,
+    };
+    self.pages.lock().unwrap().insert(path.to_string(), page);
+    self
+  }
+
+  /// The paths requested, in order
+  pub fn requests(&self) -> Vec<String> {
+    self.requests.lock().unwrap().clone()
+  }
+
+  fn respond(&self, mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+      return;
+    }
+    loop {
+      let mut header = String::new();
+      match reader.read_line(&mut header) {
+        Ok(0) | Err(_) => break,
+        Ok(_) if header == "\r\n" => break,
+        Ok(_) => {}
+      }
+    }
+
+    let path = request_line
+      .split_whitespace()
+      .nth(1)
+      .unwrap_or("/")
+      .split('?')
+      .next()
+      .unwrap()
+      .to_string();
+    self.requests.lock().unwrap().push(path.clone());
+
+    let (status, content_type, body, delay) = match self.pages.lock().unwrap().get(&path) {
+      Some(page) => (
+        page.status,
+        page.content_type.clone(),
+        page.body.clone(),
+        page.delay,
+      ),
+      None => (404, "text/plain".to_string(), b"Not Found".to_vec(), None),
+    };
+    if let Some(delay) = delay {
+      thread::sleep(delay);
+    }
+
+    let head = format!(
+      "HTTP/1.1 {} OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+      status,
+      content_type,
+      body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&body);
+  }
+}
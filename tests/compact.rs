@@ -0,0 +1,38 @@
+#![cfg(feature = "compact")]
+
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{RepoIcons, RepoIconsBuilder};
+
+#[tokio::test]
+async fn round_trip_keeps_the_icons_and_load_metadata() {
+  let mock = MockClient::new()
+    .repo("compact-owner", "widget", &logo_readme(PNG))
+    .route(
+      "https://api.github.com/repos/compact-owner/widget/readme",
+      200,
+      &logo_readme(PNG),
+    )
+    .accept("html")
+    .header("ETag", "\"readme-etag\"");
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .min_confidence(0.1)
+    .debug(true)
+    .load("compact-owner", "widget")
+    .await
+    .unwrap();
+  assert_eq!(icons.etag(), Some("\"readme-etag\""));
+
+  let decoded = RepoIcons::from_bytes(&icons.to_bytes().unwrap()).unwrap();
+  assert_eq!(decoded, icons);
+  assert_eq!(decoded.etag(), Some("\"readme-etag\""));
+  assert_eq!(decoded.explain(), icons.explain());
+}
+
+#[test]
+fn invalid_bytes_error() {
+  assert!(RepoIcons::from_bytes(&[0xff, 0xff, 0xff]).is_err());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::RepoIconsBuilder;
+use std::time::Duration;
+
+/// A load whose metadata, readme, readme source & readme directories are fetched at once
+async fn max_in_flight(repo: &str, max_concurrent_requests: usize) -> usize {
+  let mock = MockClient::new().delay(Duration::from_millis(20)).repo(
+    "concurrency-owner",
+    repo,
+    &logo_readme(PNG),
+  );
+
+  RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .icon_directive(true)
+    .scan_all_readmes(true)
+    .max_concurrent_requests(max_concurrent_requests)
+    .load("concurrency-owner", repo)
+    .await
+    .unwrap();
+
+  assert!(mock.request_count() >= 5, "{:?}", mock.urls());
+  mock.max_in_flight()
+}
+
+#[tokio::test]
+async fn in_flight_requests_never_exceed_the_limit() {
+  assert_eq!(max_in_flight("limited", 2).await, 2);
+  assert_eq!(max_in_flight("serial", 1).await, 1);
+}
+
+#[tokio::test]
+async fn requests_overlap_without_a_tight_limit() {
+  assert!(max_in_flight("unlimited", 16).await > 2);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{ConditionalLoad, RepoIconsBuilder};
+
+const README: &str = "https://api.github.com/repos/etag-owner/{}/readme";
+
+#[tokio::test]
+async fn an_unchanged_readme_isnt_downloaded() {
+  let mock = MockClient::new()
+    .repo("etag-owner", "unchanged", &logo_readme(PNG))
+    .route(&README.replace("{}", "unchanged"), 304, "");
+
+  let result = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load_if_modified("etag-owner", "unchanged", "\"abc\"")
+    .await
+    .unwrap();
+
+  assert!(matches!(result, ConditionalLoad::NotModified));
+  assert_eq!(mock.urls(), [README.replace("{}", "unchanged")]);
+  assert_eq!(
+    mock.request_header(0, "If-None-Match").as_deref(),
+    Some("\"abc\"")
+  );
+}
+
+#[tokio::test]
+async fn a_changed_readme_is_reloaded_with_its_new_etag() {
+  let mock = MockClient::new()
+    .repo("etag-owner", "changed", "")
+    .route(&README.replace("{}", "changed"), 200, &logo_readme(PNG))
+    .accept("html")
+    .header("ETag", "\"def\"");
+
+  let result = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_if_modified("etag-owner", "changed", "\"abc\"")
+    .await
+    .unwrap();
+
+  let icons = match result {
+    ConditionalLoad::Modified(icons) => icons,
+    ConditionalLoad::NotModified => panic!("the readme changed"),
+  };
+  assert_eq!(icons.etag(), Some("\"def\""));
+  assert_eq!(icons[0].url.as_str(), PNG);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{logo_readme, PNG};
+use repo_icons::Readme;
+use std::time::{Duration, Instant};
+
+/// an awesome-list: a logo, followed by hundreds of listed projects' logos
+fn awesome_list() -> Readme {
+  let logo = logo_readme(PNG);
+  let entries = (0..200)
+    .map(|i| {
+      format!(
+        r#"<li><a href="https://project{0}.dev"><img src="https://example.org/logos/{0}.png" width="16"></a> Project {0}</li>"#,
+        i
+      )
+    })
+    .collect::<String>();
+  let html = logo.replace("</article>", &format!("<ul>{}</ul></article>", entries));
+
+  Readme::new(
+    "confident-owner",
+    "awesome-widgets",
+    &html,
+    false,
+    "main",
+    None,
+  )
+}
+
+#[tokio::test]
+async fn stops_processing_images_after_a_confident_logo() {
+  let mut readme = awesome_list();
+  readme.stop_on_confident_logo = Some(24);
+
+  let start = Instant::now();
+  let images = readme.images().await;
+
+  assert_eq!(images.len(), 1);
+  assert_eq!(images[0].src.as_str(), PNG);
+  assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn processes_every_image_by_default() {
+  let images = awesome_list().images().await;
+
+  assert_eq!(images.len(), 201);
+  assert_eq!(images[0].src.as_str(), PNG);
+}
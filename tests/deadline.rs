@@ -0,0 +1,62 @@
+mod common;
+
+use common::{logo_readme, MockClient, Server, PNG, PNG_BYTES};
+use repo_icons::RepoIconsBuilder;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn slow_size_probing_returns_the_readme_logo_at_the_deadline() {
+  let homepage = r#"<html><head><link rel="icon" href="/slow.png"></head></html>"#;
+  let site = Server::start().page_with(
+    "/slow.png",
+    200,
+    "image/png",
+    PNG_BYTES,
+    Some(Duration::from_secs(5)),
+  );
+  let mock = MockClient::new()
+    .repo_with(
+      "deadline-owner",
+      "widget",
+      json!({ "homepage": site.url("/") }),
+      &logo_readme(PNG),
+      "",
+    )
+    .route(&site.url("/"), 200, homepage)
+    .header("Content-Type", "text/html");
+
+  let started = Instant::now();
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .website(true)
+    .deadline(Duration::from_millis(800))
+    .load("deadline-owner", "widget")
+    .await
+    .unwrap();
+
+  assert!(started.elapsed() < Duration::from_secs(3));
+  assert!(site.requests().contains(&"/slow.png".to_string()));
+  assert!(icons.truncated());
+  assert_eq!(icons.len(), 1);
+  assert_eq!(icons[0].url.as_str(), PNG);
+}
+
+#[tokio::test]
+async fn a_load_without_metadata_by_the_deadline_is_an_error() {
+  let mock = MockClient::new().delay(Duration::from_secs(5)).repo(
+    "deadline-owner",
+    "slow",
+    &logo_readme(PNG),
+  );
+
+  let started = Instant::now();
+  let result = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .deadline(Duration::from_millis(200))
+    .load("deadline-owner", "slow")
+    .await;
+
+  assert!(result.is_err());
+  assert!(started.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::RepoIconsBuilder;
+use serde_json::json;
+
+#[tokio::test]
+async fn the_debug_payload_lists_the_matched_signals() {
+  let mock = MockClient::new().repo("debug-owner", "widget", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .debug(true)
+    .load("debug-owner", "widget")
+    .await
+    .unwrap();
+
+  let json = serde_json::to_value(&icons).unwrap();
+  let debug = &json[0]["debug"];
+  assert!(debug["weight"].as_u64().unwrap() > 0);
+  for signal in [
+    "align_center",
+    "size_attrs",
+    "logo_mention",
+    "centered_first_image",
+  ] {
+    assert!(
+      debug["signals"]
+        .as_array()
+        .unwrap()
+        .contains(&json!(signal)),
+      "{} isn't in {}",
+      signal,
+      debug
+    );
+  }
+}
+
+#[tokio::test]
+async fn the_debug_payload_is_omitted_by_default() {
+  let mock = MockClient::new().repo("debug-owner", "plain", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("debug-owner", "plain")
+    .await
+    .unwrap();
+
+  let json = serde_json::to_value(&icons).unwrap();
+  assert!(json[0].get("debug").is_none(), "{}", json[0]);
+}
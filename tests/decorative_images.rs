@@ -0,0 +1,39 @@
+use repo_icons::{Exclusion, Readme};
+
+const README: &str = r#"<div id="readme"><article><p align="center"><img src="assets/logo.png" width="160" alt="Widget"></p><h1>Widget</h1>
+<p><img src="assets/sparkles.gif"></p><h2>Install</h2>
+<p><img src="assets/sparkles.gif"></p><h2>Usage</h2><p><img src="assets/screenshot.png"></p>
+<p><img src="assets/sparkles.gif"></p><h2>License</h2><p><img src="assets/line-break.png"></p><p><img src="assets/screenshot.png"></p>
+</article></div>"#;
+
+#[tokio::test]
+async fn a_repeated_divider_is_excluded_and_the_logo_kept() {
+  let readme = Readme::new("divider-owner", "widget", README, false, "main", None);
+
+  let (images, excluded) = readme.images_with_excluded().await;
+
+  let exclusion = |src: &str| {
+    excluded
+      .iter()
+      .filter(|candidate| candidate.url.ends_with(src))
+      .map(|candidate| candidate.excluded)
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(
+    exclusion("sparkles.gif"),
+    vec![Some(Exclusion::Repeated); 3]
+  );
+  assert_eq!(exclusion("line-break.png"), [Some(Exclusion::Decorative)]);
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/divider-owner/widget/main/assets/logo.png"
+  );
+  // an image that's only used twice isn't a divider
+  let srcs = images
+    .iter()
+    .map(|image| image.src.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(srcs.len(), 3, "{:?}", srcs);
+  assert!(srcs.iter().all(|src| !src.ends_with(".gif")), "{:?}", srcs);
+}
@@ -0,0 +1,54 @@
+use repo_icons::{Exclusion, Readme, ReadmeImage, Signal};
+
+const LOGO: &str = r#"<p><img src="assets/logo.png" alt="Widget"></p>"#;
+
+/// The logo `copies` times, spread out from the very top to the bottom
+fn readme(copies: usize) -> Readme {
+  let filler = "<p>Some documentation.</p>".repeat(30);
+  let html = format!(
+    r#"<div id="readme"><article>{}</article></div>"#,
+    vec![LOGO; copies].join(&filler)
+  );
+
+  Readme::new("position-owner", "widget", &html, false, "main", None)
+}
+
+fn position_bonus(image: &ReadmeImage) -> i16 {
+  image
+    .score_breakdown()
+    .into_iter()
+    .find(|(signal, _)| *signal == Signal::DocumentPosition)
+    .map_or(0, |(_, points)| points)
+}
+
+#[tokio::test]
+async fn the_top_copy_of_an_image_ranks_higher() {
+  let images = readme(2).images_offline().await;
+
+  assert_eq!(images.len(), 2);
+  let (top, bottom) = (&images[0], &images[1]);
+  assert_eq!(top.src, bottom.src);
+  assert_eq!(top.document_index, 0);
+  assert!(top.document_position() < 0.1, "{}", top.document_position());
+  assert!(
+    bottom.document_position() > 0.9,
+    "{}",
+    bottom.document_position()
+  );
+
+  assert!(position_bonus(top) > position_bonus(bottom));
+  assert!(top.weight() > bottom.weight());
+}
+
+#[tokio::test]
+async fn three_copies_are_a_divider_wherever_they_are() {
+  let (images, excluded) = readme(3).images_with_excluded().await;
+
+  // the repeated exclusion comes before the position is weighed, so even the
+  // top copy is excluded
+  assert!(images.is_empty(), "{:?}", images);
+  assert_eq!(excluded.len(), 3);
+  assert!(excluded
+    .iter()
+    .all(|candidate| candidate.excluded == Some(Exclusion::Repeated)));
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "download")]
+
+mod common;
+
+use common::{MockClient, PNG_BYTES};
+use repo_icons::{IconFormat, RepoIcons, RepoIconsBuilder};
+use serde_json::json;
+use std::{fs, path::PathBuf};
+
+const LOGO: &str = "https://cdn.example.org/widget/logo";
+const BANNER: &str = "https://cdn.example.org/widget/banner.svg";
+const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="64"></svg>"#;
+
+fn icons() -> RepoIcons {
+  let icon = |url, kind| {
+    json!({
+      "url": url,
+      "headers": { "Authorization": "Bearer secret" },
+      "kind": kind,
+      "type": "png",
+      "size": "64x64",
+    })
+  };
+
+  serde_json::from_value(json!([
+    icon(LOGO, "readme_image"),
+    icon(BANNER, "readme_image"),
+  ]))
+  .unwrap()
+}
+
+fn mock() -> MockClient {
+  MockClient::new()
+    .route_bytes(LOGO, 200, PNG_BYTES)
+    .route(BANNER, 200, SVG)
+}
+
+/// An empty directory of its own
+fn dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("repo-icons-{}-{}", name, std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  dir
+}
+
+#[tokio::test]
+async fn icons_are_saved_by_their_fingerprint() {
+  let dir = dir("download");
+  let icons = icons();
+  let mock = mock();
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+
+  let downloaded = icons.download_all_with(&options, &dir).await.unwrap();
+
+  assert_eq!(downloaded.len(), 2);
+  for (downloaded, icon) in downloaded.iter().zip(&icons) {
+    assert!(!downloaded.skipped);
+    assert_eq!(downloaded.icon.url, icon.url);
+  }
+  // the extension is detected from the data, not the url
+  assert_eq!(downloaded[0].format, IconFormat::Png);
+  assert_eq!(
+    downloaded[0].path,
+    dir.join(format!("{}.png", icons[0].fingerprint()))
+  );
+  assert_eq!(downloaded[1].format, IconFormat::Svg);
+  assert_eq!(
+    downloaded[1].path,
+    dir.join(format!("{}.svg", icons[1].fingerprint()))
+  );
+  assert_eq!(fs::read(&downloaded[0].path).unwrap(), PNG_BYTES);
+  assert_eq!(
+    mock.request_header(0, "authorization").as_deref(),
+    Some("Bearer secret")
+  );
+}
+
+#[tokio::test]
+async fn saved_icons_arent_downloaded_again() {
+  let dir = dir("redownload");
+  let icons = icons();
+  let mock = mock();
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+
+  let first = icons.download_all_with(&options, &dir).await.unwrap();
+  let requests = mock.request_count();
+  let second = icons.download_all_with(&options, &dir).await.unwrap();
+
+  assert_eq!(requests, 2);
+  assert_eq!(mock.request_count(), requests);
+  assert!(second.iter().all(|downloaded| downloaded.skipped));
+  let paths = |downloaded: &[repo_icons::DownloadedIcon]| {
+    downloaded
+      .iter()
+      .map(|downloaded| downloaded.path.clone())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(paths(&first), paths(&second));
+}
+
+#[tokio::test]
+async fn icons_that_fail_to_download_are_left_out() {
+  let dir = dir("failed-download");
+  let mock = MockClient::new().route_bytes(LOGO, 200, PNG_BYTES);
+  let options = RepoIconsBuilder::new().http_client(mock);
+
+  let downloaded = icons().download_all_with(&options, &dir).await.unwrap();
+
+  assert_eq!(downloaded.len(), 1);
+  assert_eq!(downloaded[0].icon.url.as_str(), LOGO);
+  assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
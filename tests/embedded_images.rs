@@ -0,0 +1,49 @@
+use repo_icons::Readme;
+
+fn readme(html: &str) -> Readme {
+  Readme::new("embed-owner", "widget", html, false, "main", None)
+}
+
+#[tokio::test]
+async fn an_object_embedded_logo_is_a_candidate() {
+  let readme = readme(
+    r#"<div id="readme"><article><p align="center"><object data="docs/logo.svg" type="image/svg+xml" width="120"></object></p><h1>Widget</h1><p><object data="docs/demo.html" type="text/html"></object></p><p><object data="https://img.shields.io/badge/build-passing-green.svg" type="image/svg+xml"></object></p></article></div>"#,
+  );
+
+  let images = readme.images_offline().await;
+
+  // the html document & the badge aren't images
+  assert_eq!(images.len(), 1, "{:?}", images);
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/embed-owner/widget/main/docs/logo.svg"
+  );
+  assert!(images[0].sourced_from_repo);
+  assert!(images[0].is_logo_candidate());
+}
+
+#[tokio::test]
+async fn an_inline_svg_logo_is_a_data_uri() {
+  let readme = readme(
+    r#"<div id="readme"><article><p align="center"><svg viewBox="0 0 64 64" width="64" height="64"><circle cx="32" cy="32" r="30"></circle><svg x="16" y="16" width="32" height="32"><rect width="32" height="32"></rect></svg></svg></p><h1>Widget</h1></article></div>"#,
+  );
+
+  let images = readme.images_offline().await;
+
+  // the nested svg is part of the logo
+  assert_eq!(images.len(), 1, "{:?}", images);
+  let src = images[0].src.as_str();
+  assert!(src.starts_with("data:image/svg+xml,%3Csvg"), "{}", src);
+  let svg = percent_encoding::percent_decode_str(src.trim_start_matches("data:image/svg+xml,"))
+    .decode_utf8()
+    .unwrap();
+  assert!(
+    svg.contains(r#"xmlns="http://www.w3.org/2000/svg""#),
+    "{}",
+    svg
+  );
+  assert!(svg.contains("<circle"), "{}", svg);
+  assert!(svg.contains("<rect"), "{}", svg);
+  assert!(images[0].has_size_attrs);
+  assert!(images[0].is_logo_candidate());
+}
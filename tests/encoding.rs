@@ -0,0 +1,65 @@
+mod common;
+
+use common::{MockClient, PNG};
+use repo_icons::{Readme, RepoIconsBuilder, Signal};
+use serde_json::json;
+
+const README: &str = "https://api.github.com/repos/encoding-owner/{}/readme";
+
+async fn load(repo: &str, content_type: &str, body: &[u8]) -> Readme {
+  let mock = MockClient::new()
+    .repo_with("encoding-owner", repo, json!({}), "", "")
+    .route_bytes(&README.replace("{}", repo), 200, body)
+    .accept("html")
+    .header("Content-Type", content_type);
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  Readme::load_with(&options, "encoding-owner", repo)
+    .await
+    .unwrap()
+}
+
+fn readme_bytes(alt: &[u8]) -> Vec<u8> {
+  let mut html = format!(
+    r#"<div id="readme"><article><p align="center"><img src="{}" alt=""#,
+    PNG
+  )
+  .into_bytes();
+  html.extend_from_slice(alt);
+  html.extend_from_slice(br#"" width="200"></p><h1>Widget</h1></article></div>"#);
+  html
+}
+
+#[tokio::test]
+async fn a_latin1_readme_is_decoded_from_its_charset() {
+  // "Logo für Widget" in latin-1
+  let readme = load(
+    "latin1",
+    "text/html; charset=ISO-8859-1",
+    &readme_bytes(b"Logo f\xfcr Widget"),
+  )
+  .await;
+
+  let image = readme.images().await.remove(0);
+  assert_eq!(image.alt.as_deref(), Some("Logo für Widget"));
+  assert!(image.signals().contains(&Signal::LogoMention));
+}
+
+#[tokio::test]
+async fn undeclared_non_utf8_falls_back_to_windows_1252() {
+  let readme = load("undeclared", "text/html", &readme_bytes(b"Logo \xe9t\xe9")).await;
+
+  let image = readme.images().await.remove(0);
+  assert_eq!(image.alt.as_deref(), Some("Logo été"));
+}
+
+#[tokio::test]
+async fn a_leading_bom_is_stripped() {
+  let mut body = b"\xef\xbb\xbf".to_vec();
+  body.extend(readme_bytes("Logo".as_bytes()));
+  let readme = load("bom", "text/html", &body).await;
+
+  let image = readme.images().await.remove(0);
+  assert_eq!(image.alt.as_deref(), Some("Logo"));
+  assert!(image.signals().contains(&Signal::LogoMention));
+}
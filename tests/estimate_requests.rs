@@ -0,0 +1,65 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, SQUARE_PNG, WIDE_PNG};
+use repo_icons::{RepoIconsBuilder, RequestEstimate};
+
+/// a logo with light & dark variants, three candidates to size
+fn themed_readme() -> String {
+  format!(
+    r#"<div id="readme"><article><h1><picture><source media="(prefers-color-scheme: dark)" srcset="{}"><source media="(prefers-color-scheme: light)" srcset="{}"><img src="{}" alt="logo"></picture></h1><p><img src="https://example.org/screenshot.png"></p></article></div>"#,
+    SQUARE_PNG, WIDE_PNG, PNG
+  )
+}
+
+async fn estimate(options: RepoIconsBuilder, repo: &str, html: &str) -> RequestEstimate {
+  let mock = MockClient::new().repo("estimate-owner", repo, html);
+
+  options
+    .http_client(mock)
+    .estimate_requests("estimate-owner", repo)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn svg_sizes_scale_with_the_candidates() {
+  for (repo, html, candidates) in [
+    ("single", logo_readme(PNG), 1),
+    ("themed", themed_readme(), 3),
+  ] {
+    let without = estimate(RepoIconsBuilder::fast(), repo, &html).await;
+    let with = estimate(RepoIconsBuilder::fast().svg_sizes(true), repo, &html).await;
+
+    assert_eq!(with.min, without.min, "{}", repo);
+    assert_eq!(with.max - without.max, candidates, "{}", repo);
+  }
+}
+
+#[tokio::test]
+async fn verifying_urls_costs_a_request_per_image() {
+  let html = themed_readme();
+  let without = estimate(RepoIconsBuilder::fast(), "verified", &html).await;
+  let with = estimate(
+    RepoIconsBuilder::fast().verify_urls(true),
+    "verified",
+    &html,
+  )
+  .await;
+
+  assert_eq!(with.min - without.min, 2);
+  assert_eq!(with.max - without.max, 2);
+}
+
+#[tokio::test]
+async fn the_estimate_only_fetches_the_readme() {
+  let mock = MockClient::new().repo("estimate-owner", "cheap", &logo_readme(PNG));
+
+  let estimate = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .estimate_requests("estimate-owner", "cheap")
+    .await
+    .unwrap();
+
+  assert!(estimate.min <= estimate.max);
+  assert!(mock.request_count() <= estimate.min);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{MockClient, PNG};
+use repo_icons::{Exclusion, RepoIconsBuilder};
+
+const BADGE: &str = "https://img.shields.io/badge/build-passing-green.svg";
+
+#[tokio::test]
+async fn a_badge_is_reported_as_excluded() {
+  let html = format!(
+    r#"<div id="readme"><article><p><img src="{}" alt="build"></p><p align="center"><img src="{}" alt="logo" width="200" height="200"></p><h1>Project</h1></article></div>"#,
+    BADGE, PNG
+  );
+  let mock = MockClient::new().repo("explain-owner", "widget", &html);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .debug(true)
+    .load("explain-owner", "widget")
+    .await
+    .unwrap();
+
+  let badge = icons
+    .candidates()
+    .iter()
+    .find(|candidate| candidate.url == BADGE)
+    .unwrap_or_else(|| panic!("the badge isn't in {}", icons.explain()));
+  assert_eq!(badge.excluded, Some(Exclusion::Badge));
+
+  let report = icons.explain();
+  let lines = report.lines().collect::<Vec<_>>();
+  assert!(lines[0].contains(PNG), "{}", report);
+  assert!(!lines[0].contains("excluded"), "{}", report);
+  let badge_line = lines
+    .iter()
+    .find(|line| line.contains(BADGE))
+    .expect("the badge is in the report");
+  assert!(badge_line.contains("(excluded: badge)"), "{}", report);
+}
+
+#[tokio::test]
+async fn candidates_are_only_kept_with_debug() {
+  let mock = MockClient::new().repo("explain-owner", "plain", &common::logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("explain-owner", "plain")
+    .await
+    .unwrap();
+
+  assert!(icons.candidates().is_empty());
+  assert!(icons.explain().is_empty());
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{Server, PNG_BYTES};
+use repo_icons::{favicons_for_site, ColorScheme, RepoIconKind};
+
+const MASK: &[u8] =
+  br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M0 0h16v16H0z"/></svg>"#;
+
+#[tokio::test]
+async fn link_tags_manifests_and_mask_icons_are_found() {
+  let server = Server::start()
+    .page(
+      "/",
+      "text/html",
+      br##"<html><head><title>Widget</title><link rel="icon" href="/icon.png"><link rel="manifest" href="/manifest.json"><link rel="mask-icon" href="/mask.svg" color="#5bbad5"></head></html>"##,
+    )
+    .page(
+      "/manifest.json",
+      "application/json",
+      br#"{ "icons": [{ "src": "/manifest-icon.png", "sizes": "1x1", "type": "image/png" }] }"#,
+    )
+    .page("/icon.png", "image/png", PNG_BYTES)
+    .page("/manifest-icon.png", "image/png", PNG_BYTES)
+    .page("/mask.svg", "image/svg+xml", MASK);
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  let paths = icons.iter().map(|icon| icon.url.path()).collect::<Vec<_>>();
+  assert!(paths.contains(&"/icon.png"), "{:?}", paths);
+  assert!(paths.contains(&"/manifest-icon.png"), "{:?}", paths);
+
+  let mask = icons
+    .iter()
+    .find(|icon| icon.kind == RepoIconKind::MaskIcon)
+    .expect("the mask icon");
+  assert_eq!(mask.url.path(), "/mask.svg");
+  assert_eq!(mask.mask_color.as_deref(), Some("#5bbad5"));
+
+  let favicon = icons
+    .iter()
+    .find(|icon| icon.url.path() == "/icon.png")
+    .unwrap();
+  assert_eq!(favicon.label.as_deref(), Some("Widget"));
+}
+
+#[tokio::test]
+async fn the_bare_favicon_is_found_without_link_tags() {
+  let server = Server::start()
+    .page("/", "text/html", b"<html><head></head></html>")
+    .page("/favicon.ico", "image/png", PNG_BYTES);
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  assert_eq!(icons.len(), 1);
+  assert_eq!(icons[0].url.path(), "/favicon.ico");
+}
+
+#[tokio::test]
+async fn css_background_logos_are_found_in_one_download() {
+  let server = Server::start()
+    .page(
+      "/",
+      "text/html",
+      br#"<html><head><link rel="icon" href="/icon.png"><style>@media (min-width: 600px) { .brand { background: url("/brand.png") no-repeat; } } .hero { background-image: url(/hero.png); }</style></head><body><div class="hero-logo" style="background-image: url('/hero-logo.png')"></div><div class="hero"></div><a class="brand"></a></body></html>"#,
+    )
+    .page("/icon.png", "image/png", PNG_BYTES)
+    .page("/hero-logo.png", "image/png", PNG_BYTES)
+    .page("/brand.png", "image/png", PNG_BYTES)
+    .page("/hero.png", "image/png", PNG_BYTES);
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  let paths = icons.iter().map(|icon| icon.url.path()).collect::<Vec<_>>();
+  // from the inline style & the <style> block. The hero's background isn't
+  // a logo
+  assert!(paths.contains(&"/hero-logo.png"), "{:?}", paths);
+  assert!(paths.contains(&"/brand.png"), "{:?}", paths);
+  assert!(!paths.contains(&"/hero.png"), "{:?}", paths);
+  // site_icons & the scan share the homepage's html
+  let requests = server.requests();
+  assert_eq!(
+    requests.iter().filter(|path| *path == "/").count(),
+    1,
+    "{:?}",
+    requests
+  );
+}
+
+#[tokio::test]
+async fn an_external_sprite_logo_is_found() {
+  let server = Server::start()
+    .page(
+      "/",
+      "text/html",
+      br##"<html><head><link rel="icon" href="/icon.png"></head><body><svg style="display: none"><symbol id="menu" viewBox="0 0 16 16"></symbol></svg><a class="navbar-logo" href="/"><svg><use xlink:href="/sprite.svg#logo"></use></svg></a><button><svg><use href="#menu"></use></svg></button></body></html>"##,
+    )
+    .page("/icon.png", "image/png", PNG_BYTES)
+    .page(
+      "/sprite.svg",
+      "image/svg+xml",
+      br#"<svg xmlns="http://www.w3.org/2000/svg"><symbol id="logo" viewBox="0 0 32 32"><path d="M0 0h32v32H0z"/></symbol></svg>"#,
+    );
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  let sprite = icons
+    .iter()
+    .find(|icon| icon.url.path() == "/sprite.svg")
+    .expect("the sprite");
+  assert_eq!(
+    sprite.url.as_str(),
+    format!("{}#logo", server.url("/sprite.svg"))
+  );
+  // the inline symbol can't be loaded on its own
+  assert!(
+    icons.iter().all(|icon| icon.url.fragment() != Some("menu")),
+    "{:?}",
+    icons
+  );
+}
+
+#[tokio::test]
+async fn favicon_media_queries_tag_their_color_scheme() {
+  let server = Server::start()
+    .page(
+      "/",
+      "text/html",
+      br#"<html><head><link rel="icon" href="/icon-light.png" media="(prefers-color-scheme: light)"><link rel="icon" href="/icon-dark.png" media="(prefers-color-scheme: dark)"><link rel="apple-touch-icon" href="/touch.png"></head></html>"#,
+    )
+    .page("/icon-light.png", "image/png", PNG_BYTES)
+    .page("/icon-dark.png", "image/png", PNG_BYTES)
+    .page("/touch.png", "image/png", PNG_BYTES);
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  let color_scheme = |path: &str| {
+    icons
+      .iter()
+      .find(|icon| icon.url.path() == path)
+      .unwrap_or_else(|| panic!("{} isn't in {:?}", path, icons))
+      .color_scheme
+  };
+  assert_eq!(color_scheme("/icon-light.png"), ColorScheme::Light);
+  assert_eq!(color_scheme("/icon-dark.png"), ColorScheme::Dark);
+  // without a media query
+  assert_eq!(color_scheme("/touch.png"), ColorScheme::Any);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{MockClient, PNG, SQUARE_PNG, WIDE_PNG};
+use repo_icons::{RepoIconsBatch, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_batch_is_flattened_to_one_entry_per_repo() {
+  let readme = format!(
+    r#"<div id="readme"><article><p align="center"><picture><source media="(prefers-color-scheme: dark)" srcset="{}"><source media="(prefers-color-scheme: light)" srcset="{}"><img src="{}" alt="logo" width="200" height="200"></picture></p><h1>Widget</h1></article></div>"#,
+    SQUARE_PNG, PNG, WIDE_PNG
+  );
+  let mock = MockClient::new().repo("feed-owner", "widget", &readme);
+
+  let batch = RepoIconsBatch::from(
+    RepoIconsBuilder::fast()
+      .http_client(mock)
+      .load_many(&[("feed-owner", "widget"), ("feed-owner", "missing")])
+      .await,
+  );
+  let feed = serde_json::to_value(batch.to_feed()).unwrap();
+
+  assert_eq!(
+    feed,
+    json!([
+      {
+        "owner": "feed-owner",
+        "repo": "widget",
+        "primary": {
+          "url": WIDE_PNG,
+          "source": "readme_image",
+          "size": { "width": 3, "height": 1 },
+        },
+        "alternates": [
+          {
+            "url": SQUARE_PNG,
+            "source": "readme_image",
+            "size": { "width": 2, "height": 2 },
+          },
+          {
+            "url": PNG,
+            "source": "readme_image",
+            "size": { "width": 1, "height": 1 },
+          },
+        ],
+        "error": null,
+      },
+      {
+        "owner": "feed-owner",
+        "repo": "missing",
+        "primary": null,
+        "alternates": [],
+        "error": "Not Found",
+      },
+    ])
+  );
+}
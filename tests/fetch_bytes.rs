@@ -0,0 +1,52 @@
+mod common;
+
+use common::{MockClient, PNG_BYTES};
+use repo_icons::{IconFormat, IconInfo, RepoIcon, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+use std::collections::HashMap;
+
+const URL: &str = "https://cdn.example.org/download";
+
+fn private_icon() -> RepoIcon {
+  let info = serde_json::from_value::<IconInfo>(json!({ "type": "png", "size": "1x1" })).unwrap();
+  let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+  RepoIcon::new_with_headers(
+    URL.parse().unwrap(),
+    headers,
+    RepoIconKind::ReadmeImage,
+    info,
+  )
+}
+
+#[tokio::test]
+async fn the_format_is_detected_from_the_bytes() {
+  let mock = MockClient::new()
+    .route_bytes(URL, 200, PNG_BYTES)
+    .header("Content-Type", "application/octet-stream");
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+
+  let (data, format) = private_icon()
+    .fetch_bytes_with(&options, 1024)
+    .await
+    .unwrap();
+
+  assert_eq!(&data[..], PNG_BYTES);
+  assert_eq!(format, IconFormat::Png);
+  assert_eq!(
+    mock.request_header(0, "authorization").as_deref(),
+    Some("Bearer secret")
+  );
+}
+
+#[tokio::test]
+async fn icons_over_the_limit_are_rejected() {
+  let mock = MockClient::new().route_bytes(URL, 200, PNG_BYTES);
+  let options = RepoIconsBuilder::new().http_client(mock);
+
+  let err = private_icon()
+    .fetch_bytes_with(&options, 16)
+    .await
+    .unwrap_err();
+
+  assert_eq!(err.to_string(), "icon is larger than 16 bytes");
+}
@@ -0,0 +1,46 @@
+use repo_icons::{IconInfo, RepoIcon, RepoIconKind};
+
+fn icon(url: &str) -> RepoIcon {
+  RepoIcon::new(
+    url.parse().unwrap(),
+    RepoIconKind::ReadmeImage,
+    IconInfo::SVG,
+  )
+}
+
+#[test]
+fn the_order_of_the_query_doesnt_change_the_fingerprint() {
+  let a = icon("https://cdn.example.org/logo.svg?v=2&theme=dark");
+  let b = icon("https://cdn.example.org/logo.svg?theme=dark&v=2");
+  let fragment = icon("https://cdn.example.org/logo.svg?v=2&theme=dark#light");
+
+  assert_eq!(a.fingerprint(), b.fingerprint());
+  assert_eq!(a.fingerprint(), fragment.fingerprint());
+  assert!(a.is_same_image(&b));
+}
+
+#[test]
+fn a_different_url_has_a_different_fingerprint() {
+  let logo = icon("https://cdn.example.org/logo.svg?v=2");
+
+  for other in [
+    "https://cdn.example.org/logo.svg?v=3",
+    "https://cdn.example.org/banner.svg?v=2",
+    "https://other.example.org/logo.svg?v=2",
+  ] {
+    assert_ne!(logo.fingerprint(), icon(other).fingerprint(), "{}", other);
+    assert!(!logo.is_same_image(&icon(other)));
+  }
+}
+
+#[test]
+fn the_fingerprint_is_stable() {
+  let url = "https://cdn.example.org/logo.svg";
+
+  assert_eq!(icon(url).fingerprint(), icon(url).fingerprint());
+  // the kind & info aren't part of the key, only the asset
+  let raster =
+    serde_json::from_value(serde_json::json!({ "type": "png", "size": "64x64" })).unwrap();
+  let other_kind = RepoIcon::new(url.parse().unwrap(), RepoIconKind::SocialPreview, raster);
+  assert_eq!(icon(url).fingerprint(), other_kind.fingerprint());
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+const RAW_URL: &str = "https://gist.githubusercontent.com/gist-owner/abc123/raw/deadbeef/README.md";
+
+fn gist(owner: Option<&str>, files: serde_json::Value) -> String {
+  json!({
+    "owner": owner.map(|login| json!({ "login": login })),
+    "files": files,
+  })
+  .to_string()
+}
+
+#[tokio::test]
+async fn relative_images_resolve_against_the_gists_raw_files() {
+  let files = json!({
+    "notes.txt": {
+      "filename": "notes.txt",
+      "language": "Text",
+      "raw_url": "https://gist.githubusercontent.com/gist-owner/abc123/raw/deadbeef/notes.txt",
+      "truncated": false,
+      "content": "not the readme",
+    },
+    "README.md": {
+      "filename": "README.md",
+      "language": "Markdown",
+      "raw_url": RAW_URL,
+      "truncated": false,
+      "content": "<p align=\"center\"><img src=\"logo.png\" alt=\"logo\"></p>",
+    },
+  });
+  let mock = MockClient::new()
+    .route(
+      "https://api.github.com/gists/abc123",
+      200,
+      &gist(Some("gist-owner"), files),
+    )
+    .route(
+      "https://api.github.com/markdown",
+      200,
+      &logo_readme("logo.png"),
+    )
+    .method("POST");
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  let readme = Readme::load_gist_with(&options, "abc123")
+    .await
+    .unwrap()
+    .unwrap();
+
+  let images = readme.images().await;
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://gist.githubusercontent.com/gist-owner/abc123/raw/deadbeef/logo.png"
+  );
+}
+
+#[tokio::test]
+async fn loads_the_logo_of_a_gist() {
+  let files = json!({
+    "README.md": {
+      "filename": "README.md",
+      "language": "Markdown",
+      "raw_url": RAW_URL,
+      "truncated": true,
+      "content": null,
+    },
+  });
+  let mock = MockClient::new()
+    .route(
+      "https://api.github.com/gists/def456",
+      200,
+      &gist(None, files),
+    )
+    .route(RAW_URL, 200, "# Widget")
+    .route("https://api.github.com/markdown", 200, &logo_readme(PNG))
+    .method("POST");
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load_gist("def456")
+    .await
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(icons[0].url.as_str(), PNG);
+  // truncated files are downloaded in full
+  assert!(mock.urls().contains(&RAW_URL.to_string()));
+}
+
+#[tokio::test]
+async fn a_gist_without_markdown_has_no_readme() {
+  let files = json!({
+    "main.rs": {
+      "filename": "main.rs",
+      "language": "Rust",
+      "raw_url": "https://gist.githubusercontent.com/gist-owner/abc789/raw/deadbeef/main.rs",
+      "truncated": false,
+      "content": "fn main() {}",
+    },
+  });
+  let mock = MockClient::new().route(
+    "https://api.github.com/gists/abc789",
+    200,
+    &gist(Some("gist-owner"), files),
+  );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_gist("abc789")
+    .await
+    .unwrap();
+
+  assert!(icons.is_none());
+}
@@ -0,0 +1,53 @@
+use repo_icons::{ProjectLink, Readme};
+
+fn readme(homepage: &str) -> Readme {
+  let html = r#"<div id="readme">
+    <a href="https://github.com/homepage-owner/widget"><img src="https://example.org/logo.png"></a>
+    <a href="https://github.com/someone/else"><img src="https://example.org/sponsor.png"></a>
+    <a href="https://github.com/features/actions"><img src="https://example.org/actions.png"></a>
+  </div>"#;
+
+  Readme::new(
+    "homepage-owner",
+    "widget",
+    html,
+    false,
+    "main",
+    Some(homepage.parse().unwrap()),
+  )
+}
+
+#[tokio::test]
+async fn the_repos_own_github_page_isnt_a_website() {
+  let readme = readme("https://github.com/homepage-owner/widget");
+
+  let images = readme.images().await;
+  assert_eq!(images.len(), 3);
+  for image in images {
+    assert_ne!(image.links_to, Some(ProjectLink::Website), "{}", image.src);
+  }
+}
+
+#[tokio::test]
+async fn github_com_isnt_a_website() {
+  let readme = readme("https://github.com");
+
+  assert_eq!(
+    readme
+      .is_link_to_project(&"https://github.com/someone/else".parse().unwrap())
+      .await,
+    None
+  );
+}
+
+#[tokio::test]
+async fn other_homepages_are_still_websites() {
+  let readme = readme("https://widget.dev");
+
+  assert_eq!(
+    readme
+      .is_link_to_project(&"https://widget.dev/docs".parse().unwrap())
+      .await,
+    Some(ProjectLink::Website)
+  );
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::{MockClient, PNG_BYTES};
+use repo_icons::{IconFormat, IconInfo, Readme, RepoIcon, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+// uploads are named by an id, which can happen to contain a badge / decorative keyword
+const UPLOAD: &str = "https://user-images.githubusercontent.com/4242/98765432-status-line";
+const ATTACHMENT: &str =
+  "https://github.com/user-attachments/assets/0badge00-1e2f-4a5b-9c8d-7e6f5a4b3c2d";
+
+#[tokio::test]
+async fn uploads_are_ranked_rather_than_filtered() {
+  let html = format!(
+    r#"<div id="readme"><article><p align="center"><img src="{}" alt="logo" width="200" height="200"></p><h1>Widget</h1><p><img src="{}"></p></article></div>"#,
+    UPLOAD, ATTACHMENT
+  );
+  let readme = Readme::new("upload-owner", "widget", &html, false, "main", None);
+
+  let classified = readme.classified_images().await;
+  assert_eq!(classified.len(), 2);
+  assert!(classified.iter().all(|image| image.image.is_some()));
+
+  let images = readme.images().await;
+  assert_eq!(images[0].src.as_str(), UPLOAD);
+  assert!(images[0].is_logo_candidate());
+  assert_eq!(images[1].src.as_str(), ATTACHMENT);
+}
+
+#[tokio::test]
+async fn an_extensionless_uploads_format_comes_from_its_bytes() {
+  let mock = MockClient::new()
+    .route_bytes(UPLOAD, 200, PNG_BYTES)
+    .header("Content-Type", "application/octet-stream");
+  let options = RepoIconsBuilder::new().http_client(mock);
+  let info = serde_json::from_value::<IconInfo>(json!({ "type": "png", "size": "1x1" })).unwrap();
+  let icon = RepoIcon::new(UPLOAD.parse().unwrap(), RepoIconKind::ReadmeImage, info);
+
+  let (data, format) = icon.fetch_bytes_with(&options, 1024).await.unwrap();
+
+  assert_eq!(format, IconFormat::Png);
+  assert_eq!(&data[..], PNG_BYTES);
+}
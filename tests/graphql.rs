@@ -0,0 +1,56 @@
+mod common;
+
+use common::{MockClient, PNG};
+use repo_icons::{set_token, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn graphql_loads_the_readme_without_rest_requests() {
+  set_token("test-token");
+
+  let source = format!(
+    "<p align=\"center\"><img src=\"{}\" alt=\"logo\" width=\"200\"></p>\n\n# Project\n",
+    PNG
+  );
+  let response = json!({
+    "data": {
+      "repository": {
+        "owner": { "login": "graphql-owner" },
+        "name": "widget",
+        "defaultBranchRef": { "name": "main" },
+        "isPrivate": false,
+        "homepageUrl": null,
+        "description": null,
+        "repositoryTopics": { "nodes": [] },
+        "primaryLanguage": null,
+        "readmeMd": { "text": source, "isTruncated": false },
+        "readmeLowerMd": null,
+        "readmeTitleMd": null,
+        "readmeMarkdown": null,
+        "readme": null,
+      }
+    }
+  });
+  let mock = MockClient::new()
+    .route("https://api.github.com/graphql", 200, &response.to_string())
+    .route(
+      "https://api.github.com/markdown",
+      200,
+      &common::logo_readme(PNG),
+    );
+
+  let options = RepoIconsBuilder::fast()
+    .use_graphql(true)
+    .http_client(mock.clone());
+  let image = options
+    .load_fast("graphql-owner", "widget")
+    .await
+    .unwrap()
+    .expect("the readme's logo");
+  assert_eq!(image.src.as_str(), PNG);
+
+  // the readme is rendered locally with the markdown feature
+  let expected = if cfg!(feature = "markdown") { 1 } else { 2 };
+  assert_eq!(mock.request_count(), expected, "{:?}", mock.urls());
+  assert!(mock.urls().iter().all(|url| !url.contains("/repos/")));
+}
@@ -0,0 +1,39 @@
+use repo_icons::{Exclusion, Readme};
+
+/// Decoys that would outrank the logo, if they were visible
+const README: &str = r#"<div id="readme"><article><p align="center" hidden><img src="assets/print-logo.png" alt="logo" width="300" height="300"></p><div style="display:none"><img src="assets/fallback-logo.png" alt="logo" width="300" height="300"></div><template><img src="assets/template-logo.png" alt="logo"></template><h1 align="center"><img src="assets/logo-mark.png" aria-hidden="true" alt="logo" width="300" height="300"><img src="assets/logo.png" alt="Widget" width="120" height="120"><br>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn only_the_visible_logo_is_returned() {
+  let readme = Readme::new("hidden-owner", "widget", README, false, "main", None);
+
+  let (images, excluded) = readme.images_with_excluded().await;
+
+  assert_eq!(images.len(), 1, "{:?}", images);
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/hidden-owner/widget/main/assets/logo.png"
+  );
+  assert!(images[0].is_logo_candidate());
+
+  let exclusion = |src: &str| {
+    excluded
+      .iter()
+      .find(|candidate| candidate.url == src)
+      .unwrap_or_else(|| panic!("{} isn't in {:?}", src, excluded))
+      .excluded
+  };
+  assert_eq!(exclusion("assets/print-logo.png"), Some(Exclusion::Hidden));
+  assert_eq!(
+    exclusion("assets/fallback-logo.png"),
+    Some(Exclusion::Hidden)
+  );
+  assert_eq!(
+    exclusion("assets/template-logo.png"),
+    Some(Exclusion::Hidden)
+  );
+  assert_eq!(
+    exclusion("assets/logo-mark.png"),
+    Some(Exclusion::Decorative)
+  );
+}
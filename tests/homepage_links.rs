@@ -0,0 +1,47 @@
+use repo_icons::{ProjectLink, Readme};
+
+async fn links_to(homepage: &str, href: &str) -> Option<ProjectLink> {
+  let html = format!(
+    r#"<div id="readme"><a href="{}"><img src="https://example.org/logo.png" alt="logo"></a></div>"#,
+    href
+  );
+  let readme = Readme::new(
+    "links-owner",
+    "widget",
+    &html,
+    false,
+    "main",
+    Some(homepage.parse().unwrap()),
+  );
+
+  let mut images = readme.images().await;
+  images.remove(0).links_to
+}
+
+#[tokio::test]
+async fn the_root_of_a_subdomain_homepages_site_is_a_homepage_link() {
+  assert_eq!(
+    links_to("https://docs.example.com", "https://example.com").await,
+    Some(ProjectLink::Website)
+  );
+}
+
+#[tokio::test]
+async fn the_root_site_isnt_a_homepage_link_for_a_homepage_path() {
+  assert_eq!(
+    links_to("https://docs.example.com/project", "https://example.com").await,
+    None
+  );
+}
+
+#[tokio::test]
+async fn shared_hosts_arent_their_subdomains_site() {
+  assert_eq!(
+    links_to("https://widget.vercel.app", "https://vercel.app").await,
+    None
+  );
+  assert_eq!(
+    links_to("https://widget.co.uk", "https://co.uk").await,
+    None
+  );
+}
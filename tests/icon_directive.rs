@@ -0,0 +1,97 @@
+mod common;
+
+use common::{logo_readme, MockClient, Server, PNG, PNG_BYTES, SQUARE_PNG};
+use repo_icons::{Readme, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+/// A load of a readme whose source has the `directive`, with a centered logo
+/// that outweighs every other image
+async fn load(repo: &str, directive: &str) -> repo_icons::RepoIcons {
+  let source = format!(
+    "{}\n\n<p align=\"center\"><img src=\"logo.png\"></p>\n",
+    directive
+  );
+  let mock = MockClient::new().repo_with(
+    "directive-owner",
+    repo,
+    json!({}),
+    &logo_readme(SQUARE_PNG),
+    &source,
+  );
+
+  RepoIconsBuilder::fast()
+    .http_client(mock)
+    .icon_directive(true)
+    .load("directive-owner", repo)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn the_declared_icon_ranks_above_the_readmes_logo() {
+  let icons = load("comment", &format!("<!-- repo-icon: {} -->", PNG)).await;
+
+  assert_eq!(icons[0].kind, RepoIconKind::Declared);
+  assert_eq!(icons[0].url.as_str(), PNG);
+  assert_eq!(icons[1].kind, RepoIconKind::ReadmeImage);
+  assert_eq!(icons[1].url.as_str(), SQUARE_PNG);
+}
+
+#[tokio::test]
+async fn a_link_rel_icon_declares_an_absolute_url() {
+  let site = Server::start().page("/icon.png", "image/png", PNG_BYTES);
+  let icon = site.url("/icon.png");
+
+  let icons = load("link", &format!(r#"<link rel="icon" href="{}">"#, icon)).await;
+
+  assert_eq!(icons[0].kind, RepoIconKind::Declared);
+  assert_eq!(icons[0].url.as_str(), icon);
+}
+
+#[tokio::test]
+async fn a_repo_relative_directive_resolves_to_the_raw_file() {
+  let mock = MockClient::new().repo_with(
+    "directive-owner",
+    "relative",
+    json!({}),
+    &logo_readme(SQUARE_PNG),
+    "<!--\n  repo-icon: assets/icon.svg\n-->\n# Widget\n",
+  );
+  let options = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .icon_directive(true);
+
+  let readme = Readme::load_with(&options, "directive-owner", "relative")
+    .await
+    .unwrap();
+
+  assert_eq!(
+    readme.declared_icon().await.unwrap().0.as_str(),
+    "https://raw.githubusercontent.com/directive-owner/relative/main/assets/icon.svg"
+  );
+}
+
+#[tokio::test]
+async fn the_directive_is_opt_in() {
+  let mock = MockClient::new().repo_with(
+    "directive-owner",
+    "default",
+    json!({}),
+    &logo_readme(SQUARE_PNG),
+    &format!("<!-- repo-icon: {} -->", PNG),
+  );
+
+  let icons = RepoIconsBuilder::new()
+    .http_client(mock.clone())
+    .website(false)
+    .repo_files(false)
+    .prefixed_repos(false)
+    .load("directive-owner", "default")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::ReadmeImage);
+  // the readme's source isn't fetched
+  let readme = "https://api.github.com/repos/directive-owner/default/readme";
+  assert_eq!(mock.urls().iter().filter(|url| *url == readme).count(), 1);
+}
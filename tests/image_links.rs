@@ -0,0 +1,72 @@
+use repo_icons::{ProjectLink, Readme};
+
+async fn links_to(src: &str, href: &str) -> Option<ProjectLink> {
+  let html = format!(
+    r#"<div id="readme"><a href="{}"><img src="{}" alt="logo"></a></div>"#,
+    href, src
+  );
+  let readme = Readme::new("image-links-owner", "widget", &html, false, "main", None);
+
+  let mut images = readme.images().await;
+  images.remove(0).links_to
+}
+
+#[tokio::test]
+async fn a_raw_src_linked_to_its_blob_isnt_a_repo_link() {
+  assert_eq!(
+    links_to(
+      "https://raw.githubusercontent.com/image-links-owner/widget/main/assets/logo.png",
+      "https://github.com/image-links-owner/widget/blob/main/assets/logo.png",
+    )
+    .await,
+    None
+  );
+}
+
+#[tokio::test]
+async fn a_src_linked_with_raw_true_isnt_a_repo_link() {
+  assert_eq!(
+    links_to(
+      "https://raw.githubusercontent.com/image-links-owner/widget/main/assets/logo.png",
+      "https://github.com/image-links-owner/widget/blob/main/assets/logo.png?raw=true",
+    )
+    .await,
+    None
+  );
+  assert_eq!(
+    links_to(
+      "https://github.com/image-links-owner/widget/raw/main/assets/logo.png",
+      "https://github.com/image-links-owner/widget/blob/main/assets/logo.png?raw=true",
+    )
+    .await,
+    None
+  );
+}
+
+#[tokio::test]
+async fn a_link_to_another_file_is_still_a_repo_link() {
+  assert_eq!(
+    links_to(
+      "https://raw.githubusercontent.com/image-links-owner/widget/main/assets/logo.png",
+      "https://github.com/image-links-owner/widget/blob/main/docs/README.md",
+    )
+    .await,
+    Some(ProjectLink::Repo)
+  );
+}
+
+#[tokio::test]
+async fn an_anchor_link_isnt_a_project_link() {
+  let src = "https://raw.githubusercontent.com/image-links-owner/widget/main/assets/logo.png";
+
+  assert_eq!(links_to(src, "#user-content-logo").await, None);
+  // github's generated anchors, even on a link that's otherwise a repo link
+  assert_eq!(
+    links_to(
+      src,
+      "https://github.com/image-links-owner/widget/blob/main/docs/README.md#user-content-logo",
+    )
+    .await,
+    None
+  );
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{ProjectLink, Readme, RepoIconsBuilder};
+use serde_json::json;
+
+const README: &str = r#"<div id="readme"><article><p align="center"><a href="https://widget.dev"><img src="https://github.com/offline-owner/widget/raw/main/logo.png" alt="logo"></a></p><h1>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn offline_extraction_makes_no_requests() {
+  let mock = MockClient::new().repo_with(
+    "offline-owner",
+    "widget",
+    json!({ "homepage": "https://widget.dev" }),
+    README,
+    "",
+  );
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+  let readme = Readme::load_with(&options, "offline-owner", "widget")
+    .await
+    .unwrap();
+  let loaded = mock.request_count();
+
+  let offline = readme.images_offline().await;
+  assert_eq!(mock.request_count(), loaded, "{:?}", mock.urls());
+
+  // same-repo matching still works by the literal owner/repo, but links aren't checked
+  assert_eq!(offline.len(), 1);
+  assert!(offline[0].sourced_from_repo);
+  assert_eq!(offline[0].links_to, None);
+
+  let online = readme.images().await;
+  assert_eq!(online[0].links_to, Some(ProjectLink::Website));
+}
+
+#[tokio::test]
+async fn offline_extraction_is_deterministic() {
+  let readme = Readme::new("offline-owner", "widget", README, false, "main", None);
+
+  assert_eq!(readme.images_offline().await, readme.images_offline().await);
+}
@@ -0,0 +1,38 @@
+use repo_icons::RepoIcons;
+use serde_json::json;
+
+const RAW: &str = "https://raw.githubusercontent.com/in-repo-owner/widget/main/logo.png";
+const CDN: &str = "https://cdn.example.org/widget/logo.png";
+const FAVICON: &str = "https://widget.dev/favicon.png";
+
+fn icons() -> RepoIcons {
+  let icon = |url, kind, sourced_from_repo| {
+    json!({
+      "url": url,
+      "headers": {},
+      "kind": kind,
+      "type": "png",
+      "size": "64x64",
+      "sourced_from_repo": sourced_from_repo,
+    })
+  };
+
+  serde_json::from_value(json!([
+    icon(CDN, "readme_image", false),
+    icon(RAW, "readme_image", true),
+    icon(FAVICON, "site_favicon", false),
+  ]))
+  .unwrap()
+}
+
+fn urls<'a>(icons: impl IntoIterator<Item = &'a repo_icons::RepoIcon>) -> Vec<&'a str> {
+  icons.into_iter().map(|icon| icon.url.as_str()).collect()
+}
+
+#[test]
+fn cdn_hosted_images_arent_in_the_repo() {
+  let icons = icons();
+
+  assert_eq!(urls(icons.in_repo_only()), [RAW]);
+  assert_eq!(urls(icons.external_only()), [CDN, FAVICON]);
+}
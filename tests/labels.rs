@@ -0,0 +1,66 @@
+mod common;
+
+use common::{MockClient, Server, PNG, PNG_BYTES};
+use repo_icons::{favicons_for_site, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+const HOMEPAGE: &str =
+  r#"<html><head><title>Widget Site</title><link rel="icon" href="/icon.png"></head></html>"#;
+
+fn site() -> Server {
+  Server::start()
+    .page("/", "text/html", HOMEPAGE.as_bytes())
+    .page("/icon.png", "image/png", PNG_BYTES)
+}
+
+#[tokio::test]
+async fn a_favicon_is_labelled_with_the_sites_title() {
+  let server = site();
+
+  let icons = favicons_for_site(server.url("/").parse().unwrap())
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].url.path(), "/icon.png");
+  assert_eq!(icons[0].label.as_deref(), Some("Widget Site"));
+}
+
+#[tokio::test]
+async fn every_source_of_a_load_is_labelled() {
+  let server = site();
+  let readme = format!(
+    r#"<div id="readme"><article><p align="center"><img src="{}" alt="Widget logo" width="200" height="200"></p><h1>Widget</h1></article></div>"#,
+    PNG
+  );
+  // the homepage is loaded with the client, and its icons are sized by site_icons
+  let mock = MockClient::new()
+    .repo_with(
+      "label-owner",
+      "widget",
+      json!({ "homepage": server.url("/") }),
+      &readme,
+      "",
+    )
+    .route(&server.url("/"), 200, HOMEPAGE)
+    .header("Content-Type", "text/html");
+
+  let icons = RepoIconsBuilder::new()
+    .repo_files(false)
+    .prefixed_repos(false)
+    .http_client(mock)
+    .load("label-owner", "widget")
+    .await
+    .unwrap();
+
+  let readme_logo = icons
+    .iter()
+    .find(|icon| icon.kind == RepoIconKind::ReadmeImage)
+    .unwrap();
+  assert_eq!(readme_logo.label.as_deref(), Some("Widget logo"));
+
+  let favicon = icons
+    .iter()
+    .find(|icon| icon.url.path() == "/icon.png")
+    .unwrap();
+  assert_eq!(favicon.label.as_deref(), Some("Widget Site"));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+const README: &str =
+  r#"<div id="readme"><article><h1>Widget</h1><p>No logo here</p></article></div>"#;
+
+fn mock(repo: &str, language: &str, readme: &str) -> MockClient {
+  MockClient::new().repo_with(
+    "language-owner",
+    repo,
+    json!({ "language": language }),
+    readme,
+    "",
+  )
+}
+
+#[tokio::test]
+async fn a_repo_without_images_falls_back_to_its_languages_logo() {
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock("crate", "Rust", README))
+    .language_logo(true)
+    .load("language-owner", "crate")
+    .await
+    .unwrap();
+
+  let logo = icons
+    .iter()
+    .find(|icon| icon.kind == RepoIconKind::LanguageLogo)
+    .unwrap();
+  assert_eq!(
+    logo.url.as_str(),
+    "https://cdn.jsdelivr.net/gh/devicons/devicon/icons/rust/rust-original.svg"
+  );
+  assert_eq!(logo.label.as_deref(), Some("Rust"));
+}
+
+#[tokio::test]
+async fn the_callers_mapping_takes_precedence() {
+  let mascot = "https://example.org/ferris.svg";
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock("mascot", "Rust", README))
+    .language_logo(true)
+    .language_logo_for("rust", mascot.parse().unwrap())
+    .load("language-owner", "mascot")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::LanguageLogo);
+  assert_eq!(icons[0].url.as_str(), mascot);
+}
+
+#[tokio::test]
+async fn the_fallback_isnt_used_when_theres_a_logo() {
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock("logo", "Rust", &logo_readme(PNG)))
+    .language_logo(true)
+    .load("language-owner", "logo")
+    .await
+    .unwrap();
+
+  assert!(icons
+    .iter()
+    .all(|icon| icon.kind != RepoIconKind::LanguageLogo));
+}
+
+#[tokio::test]
+async fn the_fallback_is_opt_in() {
+  let result = RepoIconsBuilder::fast()
+    .http_client(mock("disabled", "Rust", README))
+    .load("language-owner", "disabled")
+    .await;
+
+  assert!(result.is_err());
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::RepoIconsBuilder;
+
+#[tokio::test]
+async fn load_fast_fetches_the_metadata_and_rendered_readme() {
+  let mock = MockClient::new().repo("fast-owner", "widget", &logo_readme(PNG));
+
+  let image = RepoIconsBuilder::new()
+    .http_client(mock.clone())
+    .load_fast("fast-owner", "widget")
+    .await
+    .unwrap()
+    .expect("the readme's logo");
+
+  assert_eq!(image.src.as_str(), PNG);
+  assert_eq!(mock.request_count(), 2, "{:?}", mock.urls());
+  assert!(mock
+    .urls()
+    .iter()
+    .all(|url| url.starts_with("https://api.github.com/")));
+}
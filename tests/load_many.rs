@@ -0,0 +1,95 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::RepoIconsBuilder;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn the_batch_shares_one_concurrency_budget() {
+  let mut mock = MockClient::new().delay(Duration::from_millis(20));
+  let repos = ["one", "two", "three", "four"];
+  for repo in repos {
+    mock = mock.repo("batch-owner", repo, &logo_readme(PNG));
+  }
+
+  let results = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .batch_concurrency(4)
+    .max_concurrent_requests(2)
+    .load_many(&repos.map(|repo| ("batch-owner", repo)))
+    .await;
+
+  assert!(results.iter().all(|(_, icons)| icons.is_ok()));
+  assert_eq!(mock.max_in_flight(), 2);
+}
+
+#[tokio::test]
+async fn the_rate_limit_is_read_from_response_headers() {
+  let reset = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs()
+    + 3600;
+  let rate_limit = format!(
+    r#"{{ "resources": {{ "core": {{ "limit": 5000, "remaining": 5000, "reset": {} }} }} }}"#,
+    reset
+  );
+  let mut mock = MockClient::new()
+    .route("https://api.github.com/rate_limit", 200, &rate_limit)
+    .response_header("X-RateLimit-Limit", "5000")
+    .response_header("X-RateLimit-Remaining", "4000")
+    .response_header("X-RateLimit-Reset", &reset.to_string());
+  let repos = ["one", "two", "three"];
+  for repo in repos {
+    mock = mock.repo("rate-limit-owner", repo, &logo_readme(PNG));
+  }
+
+  let results = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .batch_concurrency(1)
+    .rate_limit_reserve(100)
+    .load_many(&repos.map(|repo| ("rate-limit-owner", repo)))
+    .await;
+
+  assert!(results.iter().all(|(_, icons)| icons.is_ok()));
+  let polls = mock
+    .urls()
+    .iter()
+    .filter(|url| url.ends_with("/rate_limit"))
+    .count();
+  assert_eq!(polls, 1, "{:?}", mock.urls());
+}
+
+#[tokio::test]
+async fn a_failed_repo_doesnt_fail_the_batch() {
+  let mock = MockClient::new()
+    .repo("partial-owner", "first", &logo_readme(PNG))
+    .repo("partial-owner", "last", &logo_readme(PNG))
+    .route(
+      "https://api.github.com/repos/partial-owner/missing",
+      404,
+      r#"{"message":"Not Found"}"#,
+    );
+
+  let results = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .batch_concurrency(3)
+    .load_many(&[
+      ("partial-owner", "first"),
+      ("partial-owner", "missing"),
+      ("partial-owner", "last"),
+    ])
+    .await;
+
+  let repos = results
+    .iter()
+    .map(|((_, repo), _)| repo.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(repos, ["first", "missing", "last"]);
+  assert!(results[0].1.is_ok());
+  match &results[1].1 {
+    Ok(_) => panic!("loaded a repo that doesn't exist"),
+    Err(err) => assert_eq!(err.to_string(), "Not Found"),
+  }
+  assert_eq!(results[2].1.as_ref().unwrap()[0].url.as_str(), PNG);
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::{logo_readme, MockClient, Server, PNG, PNG_BYTES};
+use repo_icons::{Phase, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+fn owner(mock: MockClient, login: &str, owner_type: &str, avatars: &Server) -> MockClient {
+  mock.route(
+    &format!("https://api.github.com/users/{}", login),
+    200,
+    &json!({
+      "login": login,
+      "type": owner_type,
+      "avatar_url": avatars.url(&format!("/u/{}", login)),
+    })
+    .to_string(),
+  )
+}
+
+#[tokio::test]
+async fn an_orgs_avatar_is_its_primary_icon() {
+  let avatars = Server::start().page("/u/avatar-org", "image/png", PNG_BYTES);
+  let mock = owner(MockClient::new(), "avatar-org", "Organization", &avatars);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_owner("avatar-org")
+    .await
+    .unwrap();
+
+  assert_eq!(icons.len(), 1);
+  let primary = icons.primary().unwrap();
+  assert_eq!(primary.icon.kind, RepoIconKind::OrgAvatar);
+  assert_eq!(primary.icon.url.path(), "/u/avatar-org");
+  assert_eq!(primary.icon.label.as_deref(), Some("avatar-org"));
+  // the org doesn't have a profile readme, which isn't a warning
+  assert!(icons.warnings().is_empty());
+}
+
+#[tokio::test]
+async fn a_users_avatar_is_tagged_as_a_user() {
+  let avatars = Server::start().page("/u/avatar-user", "image/png", PNG_BYTES);
+  let mock = owner(MockClient::new(), "avatar-user", "User", &avatars);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load_owner("avatar-user")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::UserAvatar);
+  // users don't have profile readmes in a `.github` repo
+  assert!(!mock.urls().iter().any(|url| url.contains("/.github")));
+}
+
+#[tokio::test]
+async fn an_orgs_profile_logo_ranks_above_its_avatar() {
+  let avatars = Server::start().page("/u/profile-org", "image/png", PNG_BYTES);
+  let api = "https://api.github.com/repos/profile-org/.github";
+  let mock = owner(
+    MockClient::new().repo("profile-org", ".github", ""),
+    "profile-org",
+    "Organization",
+    &avatars,
+  )
+  .route(&format!("{}/contents/profile/README.md", api), 200, "")
+  .route("https://api.github.com/markdown", 200, &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_owner("profile-org")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::ReadmeImage);
+  assert_eq!(icons[0].url.as_str(), PNG);
+  assert_eq!(icons[1].kind, RepoIconKind::OrgAvatar);
+}
+
+#[tokio::test]
+async fn an_orgs_profile_without_a_logo_falls_back_to_its_avatar() {
+  let avatars = Server::start().page("/u/textual-org", "image/png", PNG_BYTES);
+  let api = "https://api.github.com/repos/textual-org/.github";
+  let mock = owner(
+    MockClient::new().repo("textual-org", ".github", ""),
+    "textual-org",
+    "Organization",
+    &avatars,
+  )
+  .route(&format!("{}/contents/profile/README.md", api), 200, "")
+  .route(
+    "https://api.github.com/markdown",
+    200,
+    "<h2>Hi there</h2><p>We build things.</p>",
+  );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_owner("textual-org")
+    .await
+    .unwrap();
+
+  assert_eq!(icons.len(), 1);
+  assert_eq!(icons[0].kind, RepoIconKind::OrgAvatar);
+  assert!(icons.warnings().is_empty());
+}
+
+#[tokio::test]
+async fn a_failing_profile_readme_is_a_warning() {
+  let avatars = Server::start().page("/u/flaky-org", "image/png", PNG_BYTES);
+  let api = "https://api.github.com/repos/flaky-org/.github";
+  let mock = owner(
+    MockClient::new().repo("flaky-org", ".github", ""),
+    "flaky-org",
+    "Organization",
+    &avatars,
+  )
+  .route(
+    &format!("{}/contents/profile/README.md", api),
+    500,
+    r#"{"message":"Server Error"}"#,
+  );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load_owner("flaky-org")
+    .await
+    .unwrap();
+
+  assert_eq!(icons.len(), 1);
+  assert_eq!(icons[0].kind, RepoIconKind::OrgAvatar);
+  assert_eq!(icons.warnings().len(), 1);
+  assert_eq!(icons.warnings()[0].source, Phase::Readme);
+}
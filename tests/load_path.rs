@@ -0,0 +1,89 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+
+#[tokio::test]
+async fn relative_images_resolve_against_the_files_directory() {
+  let api = "https://api.github.com/repos/path-owner/widget";
+  let mock = MockClient::new()
+    .repo("path-owner", "widget", "")
+    .route(
+      &format!("{}/contents/docs/README.md", api),
+      200,
+      "![logo](logo.png)",
+    )
+    .route(
+      "https://api.github.com/markdown",
+      200,
+      r#"<p><img src="logo.png" alt="logo"></p>"#,
+    );
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+
+  let readme = Readme::load_path_with(&options, "path-owner", "widget", "docs/README.md")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/path-owner/widget/main/docs/logo.png"
+  );
+  assert!(images[0].sourced_from_repo);
+  assert!(mock
+    .urls()
+    .contains(&"https://api.github.com/markdown".to_string()));
+}
+
+/// The images of a readme at `path` with the `html`
+async fn images_at(repo: &str, path: &str, html: &str) -> Vec<String> {
+  let api = format!("https://api.github.com/repos/path-owner/{}", repo);
+  let mock = MockClient::new()
+    .repo("path-owner", repo, "")
+    .route(&format!("{}/contents/{}", api, path), 200, "")
+    .route("https://api.github.com/markdown", 200, html);
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  let readme = Readme::load_path_with(&options, "path-owner", repo, path)
+    .await
+    .unwrap();
+  readme
+    .images()
+    .await
+    .into_iter()
+    .map(|image| image.src.to_string())
+    .collect()
+}
+
+#[tokio::test]
+async fn parent_paths_resolve_against_the_files_directory() {
+  let images = images_at(
+    "monorepo",
+    "packages/core/docs/README.md",
+    r#"<p><img src="../../../media/banner.png"><img src="../assets/logo.png" alt="logo"></p>"#,
+  )
+  .await;
+
+  assert_eq!(
+    images,
+    [
+      "https://raw.githubusercontent.com/path-owner/monorepo/main/packages/core/assets/logo.png",
+      "https://raw.githubusercontent.com/path-owner/monorepo/main/media/banner.png",
+    ]
+  );
+}
+
+#[tokio::test]
+async fn parent_paths_are_clamped_at_the_repo_root() {
+  let images = images_at(
+    "nested",
+    "a/b/README.md",
+    r#"<p><img src="../../../../logo.png" alt="logo"></p>"#,
+  )
+  .await;
+
+  assert_eq!(
+    images,
+    ["https://raw.githubusercontent.com/path-owner/nested/main/logo.png"]
+  );
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::StreamExt;
+use repo_icons::{RankingSnapshot, RepoIconsBuilder};
+
+#[tokio::test]
+async fn snapshots_are_emitted_in_order_from_a_single_load() {
+  let mock = MockClient::new().repo("progressive-owner", "widget", &logo_readme(PNG));
+
+  let snapshots = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load_progressive("progressive-owner", "widget")
+    .collect::<Vec<_>>()
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+  match snapshots.as_slice() {
+    [RankingSnapshot::Readme(images), RankingSnapshot::Sizes(sized), RankingSnapshot::Complete(icons)] =>
+    {
+      assert_eq!(images[0].src.as_str(), PNG);
+      assert_eq!(sized[0].url.as_str(), PNG);
+      assert_eq!(icons.closest_match().url.as_str(), PNG);
+    }
+    snapshots => panic!("unexpected snapshots {:?}", snapshots),
+  }
+
+  // the readme isn't fetched again for the final icons
+  let readme_requests = mock
+    .urls()
+    .iter()
+    .filter(|url| url.contains("/readme"))
+    .count();
+  assert_eq!(readme_requests, 1, "{:?}", mock.urls());
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "markdown")]
+
+mod common;
+
+use common::PNG_BYTES;
+use repo_icons::{RepoIconKind, RepoIcons};
+use std::{fs, path::PathBuf};
+
+/// A checkout with a readme & its logo, in a directory of its own
+fn fixture(name: &str, readme: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("repo-icons-{}-{}", name, std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(dir.join("assets")).unwrap();
+  fs::write(dir.join("README.md"), readme).unwrap();
+  fs::write(dir.join("assets/logo.png"), PNG_BYTES).unwrap();
+  dir
+}
+
+#[tokio::test]
+async fn a_local_readmes_logo_is_read_from_disk() {
+  let dir = fixture(
+    "readme",
+    "<p align=\"center\"><img src=\"assets/logo.png\" alt=\"Widget logo\" width=\"200\"></p>\n\n# Widget\n",
+  );
+
+  let icons = RepoIcons::load_from_path(&dir).await.unwrap();
+
+  let logo = &icons[0];
+  assert_eq!(logo.kind, RepoIconKind::ReadmeImage);
+  assert_eq!(logo.url.scheme(), "file");
+  assert_eq!(
+    logo.url.to_file_path().unwrap(),
+    dir.canonicalize().unwrap().join("assets/logo.png")
+  );
+  assert_eq!(logo.label.as_deref(), Some("Widget logo"));
+  assert!(logo.sourced_from_repo);
+  assert_eq!(
+    logo.info.size().map(|size| (size.width, size.height)),
+    Some((1, 1))
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn a_conventional_logo_file_is_found_without_the_readme_using_it() {
+  let dir = fixture("convention", "# Widget\n\nNo images here\n");
+
+  let icons = RepoIcons::load_from_path(&dir).await.unwrap();
+
+  assert_eq!(icons.len(), 1);
+  assert_eq!(icons[0].kind, RepoIconKind::Blob(None));
+  assert!(icons[0].url.path().ends_with("/assets/logo.png"));
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn a_directory_without_a_readme_is_an_error() {
+  let dir = std::env::temp_dir().join(format!("repo-icons-empty-{}", std::process::id()));
+  fs::create_dir_all(&dir).unwrap();
+
+  assert!(RepoIcons::load_from_path(&dir).await.is_err());
+
+  fs::remove_dir_all(dir).unwrap();
+}
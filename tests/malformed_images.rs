@@ -0,0 +1,46 @@
+mod common;
+
+use common::{MockClient, PNG};
+use repo_icons::{Exclusion, Readme, RepoIconsBuilder};
+
+const HTML: &str = r#"<div id="readme"><article>
+  <p><img src="http://[::1" alt="broken"><img src="javascript:alert(1)"><img src="https://exa mple.org/%zz.png"></p>
+  <p align="center"><img src="{}" alt="logo" width="200" height="200"></p>
+  <h1>Widget</h1>
+</article></div>"#;
+
+#[tokio::test]
+async fn a_malformed_image_doesnt_lose_the_logo() {
+  let readme = Readme::new(
+    "malformed-owner",
+    "widget",
+    &HTML.replace("{}", PNG),
+    false,
+    "main",
+    None,
+  );
+
+  let images = readme.images().await;
+  assert_eq!(images.len(), 1);
+  assert_eq!(images[0].src.as_str(), PNG);
+}
+
+#[tokio::test]
+async fn malformed_images_are_reported_as_invalid() {
+  let mock = MockClient::new().repo("malformed-owner", "debugged", &HTML.replace("{}", PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .debug(true)
+    .load("malformed-owner", "debugged")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].url.as_str(), PNG);
+  let broken = icons
+    .candidates()
+    .iter()
+    .find(|candidate| candidate.url == "http://[::1")
+    .unwrap();
+  assert_eq!(broken.excluded, Some(Exclusion::InvalidUrl));
+}
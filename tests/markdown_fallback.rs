@@ -0,0 +1,42 @@
+#![cfg(feature = "markdown")]
+
+mod common;
+
+use common::{MockClient, PNG, SQUARE_PNG};
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn renders_the_raw_readme_when_the_html_endpoint_fails() {
+  let markdown = format!(
+    "<p align=\"center\">\n  <picture>\n    <source media=\"(prefers-color-scheme: dark)\" srcset=\"{}\">\n    <img src=\"{}\" alt=\"logo\" width=\"200\">\n  </picture>\n</p>\n\n# Project\n\nSome text with ![badge]({}).\n",
+    SQUARE_PNG, PNG, PNG
+  );
+  let mock = MockClient::new()
+    .repo_with("markdown-owner", "widget", json!({}), "", &markdown)
+    .route(
+      "https://api.github.com/repos/markdown-owner/widget/readme",
+      500,
+      r#"{"message":"Server Error"}"#,
+    )
+    .accept("html");
+  let options = RepoIconsBuilder::new()
+    .http_client(mock)
+    // the raw readme is only fetched for the icon directive
+    .icon_directive(true);
+
+  let readme = Readme::load_with(&options, "markdown-owner", "widget")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  let logo = images
+    .iter()
+    .find(|image| image.src.as_str() == PNG)
+    .expect("the logo is extracted from the rendered markdown");
+  assert!(logo.is_align_center);
+  assert_eq!(
+    logo.themed().dark.map(|dark| dark.as_str().to_string()),
+    Some(SQUARE_PNG.to_string())
+  );
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, SQUARE_PNG};
+use repo_icons::{RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+fn raw(repo: &str, file: &str) -> String {
+  format!(
+    "https://raw.githubusercontent.com/metadata-owner/{}/HEAD/{}",
+    repo, file
+  )
+}
+
+#[tokio::test]
+async fn a_package_json_logo_is_the_top_candidate() {
+  let package = json!({ "name": "widget", "logo": SQUARE_PNG }).to_string();
+  let mock = MockClient::new()
+    .repo("metadata-owner", "widget", &logo_readme(PNG))
+    .route(&raw("widget", "package.json"), 200, &package);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .metadata_logo(true)
+    .load("metadata-owner", "widget")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::Declared);
+  assert_eq!(icons[0].url.as_str(), SQUARE_PNG);
+  assert!(icons.iter().any(|icon| icon.url.as_str() == PNG));
+}
+
+#[tokio::test]
+async fn the_files_and_fields_are_configurable() {
+  let citation = format!(
+    "cff-version: 1.2.0\ntitle: Widget\nbrand-mark: {}\n",
+    SQUARE_PNG
+  );
+  let package = json!({ "logo": "https://cdn.example.org/ignored.png" }).to_string();
+  let mock = MockClient::new()
+    .repo("metadata-owner", "citation", &logo_readme(PNG))
+    .route(&raw("citation", "CITATION.cff"), 200, &citation)
+    .route(&raw("citation", "package.json"), 200, &package);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .metadata_logo(true)
+    .metadata_logo_fields(&["CITATION.cff"], &["brand-mark"])
+    .load("metadata-owner", "citation")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::Declared);
+  assert_eq!(icons[0].url.as_str(), SQUARE_PNG);
+  assert!(!mock.urls().contains(&raw("citation", "package.json")));
+}
+
+#[tokio::test]
+async fn metadata_files_are_only_fetched_when_enabled() {
+  let mock = MockClient::new().repo("metadata-owner", "disabled", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load("metadata-owner", "disabled")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::ReadmeImage);
+  assert!(!mock
+    .urls()
+    .iter()
+    .any(|url| url.starts_with("https://raw.githubusercontent.com/")));
+}
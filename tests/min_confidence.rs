@@ -0,0 +1,68 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, WIDE_PNG};
+use repo_icons::{RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+const NO_LOGO: &str = r#"<div id="readme"><article><h1>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn a_strong_logo_clears_the_threshold() {
+  let mock = MockClient::new().repo("confidence-owner", "logo", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .min_confidence(0.7)
+    .load("confidence-owner", "logo")
+    .await
+    .unwrap();
+
+  let primary = icons.primary().unwrap();
+  assert_eq!(primary.icon.url.as_str(), PNG);
+  assert!(primary.confidence >= 0.7);
+}
+
+#[tokio::test]
+async fn a_fallback_below_the_threshold_isnt_primary() {
+  let mock = MockClient::new()
+    .repo("confidence-owner", "fallback", NO_LOGO)
+    .route(
+      "https://github.com/confidence-owner/fallback",
+      200,
+      &format!(r#"<meta property="og:image" content="{}">"#, WIDE_PNG),
+    );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .social_preview(true)
+    .min_confidence(0.5)
+    .load("confidence-owner", "fallback")
+    .await
+    .unwrap();
+
+  // the icons themselves are unaffected
+  assert_eq!(icons[0].kind, RepoIconKind::SocialPreview);
+  assert!(icons.primary().is_none());
+}
+
+#[tokio::test]
+async fn without_a_threshold_the_fallback_is_primary() {
+  let mock = MockClient::new().repo_with(
+    "confidence-owner",
+    "language",
+    json!({ "language": "Rust" }),
+    NO_LOGO,
+    "",
+  );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .language_logo(true)
+    .load("confidence-owner", "language")
+    .await
+    .unwrap();
+
+  let primary = icons.primary().unwrap();
+  assert_eq!(primary.icon.kind, RepoIconKind::LanguageLogo);
+  assert!(primary.confidence < 0.5);
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_repo_without_a_readme_loads_an_empty_readme() {
+  let mock = MockClient::new().route(
+    "https://api.github.com/repos/no-readme-owner/widget",
+    200,
+    &json!({
+      "owner": { "login": "no-readme-owner" },
+      "name": "widget",
+      "default_branch": "main",
+      "private": false,
+      "homepage": "https://widget.dev",
+    })
+    .to_string(),
+  );
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  let readme = Readme::load_with(&options, "no-readme-owner", "widget")
+    .await
+    .unwrap();
+
+  assert!(readme.images().await.is_empty());
+  assert_eq!(readme.homepage.unwrap().as_str(), "https://widget.dev/");
+}
+
+#[tokio::test]
+async fn a_missing_repo_is_still_an_error() {
+  let options = RepoIconsBuilder::fast().http_client(MockClient::new());
+
+  assert!(Readme::load_with(&options, "no-readme-owner", "missing")
+    .await
+    .is_err());
+}
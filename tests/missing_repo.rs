@@ -0,0 +1,15 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::RepoIconsBuilder;
+
+#[tokio::test]
+async fn a_nonexistent_repo_rejects_with_githubs_message() {
+  let err = RepoIconsBuilder::fast()
+    .http_client(MockClient::new())
+    .load("missing-owner", "widget")
+    .await
+    .unwrap_err();
+
+  assert_eq!(err.to_string(), "Not Found");
+}
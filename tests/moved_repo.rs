@@ -0,0 +1,48 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_renamed_repo_is_loaded_from_its_canonical_url() {
+  let canonical = "https://api.github.com/repositories/1296269";
+  let mock = MockClient::new()
+    .repo("moved-owner", "new-name", "")
+    .route(
+      "https://api.github.com/repos/moved-owner/old-name",
+      301,
+      &json!({
+        "message": "Moved Permanently",
+        "url": canonical,
+        "documentation_url": "https://docs.github.com/rest/guides/best-practices-for-integrators#follow-redirects",
+      })
+      .to_string(),
+    )
+    .route(
+      canonical,
+      200,
+      &json!({
+        "owner": { "login": "moved-owner" },
+        "name": "new-name",
+        "default_branch": "main",
+        "private": false,
+        "homepage": null,
+      })
+      .to_string(),
+    )
+    .route(
+      "https://api.github.com/repos/moved-owner/old-name/readme",
+      200,
+      &logo_readme(PNG),
+    );
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+
+  let readme = Readme::load_with(&options, "moved-owner", "old-name")
+    .await
+    .unwrap();
+
+  assert_eq!(readme.repo, "new-name");
+  assert_eq!(readme.images().await[0].src.as_str(), PNG);
+  assert!(mock.urls().contains(&canonical.to_string()));
+}
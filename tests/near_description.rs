@@ -0,0 +1,39 @@
+use repo_icons::{Readme, ReadmeImage, Signal};
+
+/// A sponsor's logo, then further down the project's logo above its tagline
+const README: &str = r#"<div id="readme"><article><h1>Widget</h1><p>Docs</p><h2>About</h2>
+<p><img src="https://sponsor.example/logo.png" alt="logo"></p>
+<p>Sponsored by Example Corp, the cloud for everyone.</p>
+<p><img src="https://widget.dev/logo.png" alt="logo"></p>
+<p>A fast and friendly widget toolkit for your terminal.</p>
+</article></div>"#;
+
+fn hosts(images: &[ReadmeImage]) -> Vec<&str> {
+  images
+    .iter()
+    .map(|image| image.src.host_str().unwrap())
+    .collect()
+}
+
+#[tokio::test]
+async fn the_image_by_the_description_ranks_higher() {
+  let mut readme = Readme::new("description-owner", "widget", README, false, "main", None);
+  readme.description = Some("A fast & friendly widget toolkit for the terminal".to_string());
+
+  let images = readme.images_offline().await;
+
+  assert_eq!(hosts(&images), ["widget.dev", "sponsor.example"]);
+  assert!(images[0].near_description);
+  assert!(images[0].signals().contains(&Signal::NearDescription));
+  assert!(!images[1].near_description);
+}
+
+#[tokio::test]
+async fn without_a_description_the_first_ranks_higher() {
+  let readme = Readme::new("description-owner", "widget", README, false, "main", None);
+
+  let images = readme.images_offline().await;
+
+  assert_eq!(hosts(&images), ["sponsor.example", "widget.dev"]);
+  assert!(images.iter().all(|image| !image.near_description));
+}
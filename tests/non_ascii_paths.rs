@@ -0,0 +1,48 @@
+mod common;
+
+use common::{MockClient, PNG_BYTES};
+use repo_icons::{IconFormat, IconInfo, Readme, RepoIcon, RepoIconKind, RepoIconsBuilder};
+
+const RAW: &str = "https://raw.githubusercontent.com/encoding-owner/widget/main/assets/%D0%BB%D0%BE%D0%B3%D0%BE%D1%82%D0%B8%D0%BF.png";
+
+fn readme(src: &str) -> Readme {
+  let html = format!(
+    r#"<div id="readme"><article><img src="{}" alt="logo"></article></div>"#,
+    src
+  );
+  Readme::new("encoding-owner", "widget", &html, false, "main", None)
+}
+
+#[tokio::test]
+async fn raw_and_percent_encoded_filenames_emit_the_same_encoded_url() {
+  for src in [
+    "assets/логотип.png",
+    "assets/%D0%BB%D0%BE%D0%B3%D0%BE%D1%82%D0%B8%D0%BF.png",
+    "https://github.com/encoding-owner/widget/blob/main/assets/логотип.png?raw=true",
+  ] {
+    let readme = readme(src);
+    let image = readme.images_offline().await.remove(0);
+
+    assert_eq!(image.src.as_str(), RAW, "{}", src);
+    assert_eq!(
+      readme.get_branch_and_path(&image.src).await,
+      Some(("main".to_string(), "assets/логотип.png".to_string())),
+      "{}",
+      src
+    );
+  }
+}
+
+#[tokio::test]
+async fn the_encoded_url_is_fetchable() {
+  let readme = readme("assets/логотип.png");
+  let image = readme.images_offline().await.remove(0);
+  let mock = MockClient::new().route_bytes(RAW, 200, PNG_BYTES);
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+
+  let icon = RepoIcon::new(image.src, RepoIconKind::ReadmeImage, IconInfo::SVG);
+  let (_, format) = icon.fetch_bytes_with(&options, 1024).await.unwrap();
+
+  assert_eq!(format, IconFormat::Png);
+  assert_eq!(mock.urls(), [RAW]);
+}
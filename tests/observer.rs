@@ -0,0 +1,50 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{Phase, RepoIconsBuilder, RepoIconsObserver};
+use reqwest::{StatusCode, Url};
+use std::{
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+#[derive(Default)]
+struct Recorder {
+  requests: Mutex<Vec<(String, Option<StatusCode>)>>,
+  phases: Mutex<Vec<Phase>>,
+}
+
+impl RepoIconsObserver for Recorder {
+  fn on_request(&self, url: &Url, _duration: Duration, status: Option<StatusCode>) {
+    let request = (url.to_string(), status);
+    self.requests.lock().unwrap().push(request);
+  }
+
+  fn on_phase(&self, phase: Phase, _duration: Duration) {
+    self.phases.lock().unwrap().push(phase);
+  }
+}
+
+#[tokio::test]
+async fn every_request_and_phase_is_reported() {
+  let mock = MockClient::new().repo("observer-owner", "widget", &logo_readme(PNG));
+  let recorder = Arc::new(Recorder::default());
+
+  RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .observer(recorder.clone())
+    .load("observer-owner", "widget")
+    .await
+    .unwrap();
+
+  let requests = recorder.requests.lock().unwrap();
+  assert_eq!(requests.len(), 2, "{:?}", requests);
+  assert_eq!(requests.len(), mock.request_count());
+  assert!(requests
+    .iter()
+    .all(|(_, status)| *status == Some(StatusCode::OK)));
+
+  let phases = recorder.phases.lock().unwrap();
+  assert!(phases.contains(&Phase::Readme), "{:?}", phases);
+  assert!(phases.contains(&Phase::Sizes), "{:?}", phases);
+}
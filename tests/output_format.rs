@@ -0,0 +1,98 @@
+use repo_icons::{OutputFormat, RepoIcons};
+use serde_json::json;
+
+fn icons() -> RepoIcons {
+  serde_json::from_value(json!([
+    {
+      "url": "https://raw.githubusercontent.com/format-owner/widget/main/logo.png",
+      "headers": {},
+      "kind": "readme_image",
+      "type": "png",
+      "size": "256x256",
+      "debug": { "weight": 42, "signals": ["align_center"] },
+    },
+    {
+      "url": "https://widget.dev/icon.svg?a=1,2",
+      "headers": {},
+      "kind": "site_logo",
+      "type": "svg",
+    },
+  ]))
+  .unwrap()
+}
+
+#[test]
+fn url_is_only_the_top_url() {
+  assert_eq!(
+    icons().format(OutputFormat::Url),
+    "https://raw.githubusercontent.com/format-owner/widget/main/logo.png"
+  );
+}
+
+#[test]
+fn json_round_trips() {
+  let icons = icons();
+  let json = icons.format(OutputFormat::Json);
+
+  let parsed = serde_json::from_str::<RepoIcons>(&json).unwrap();
+  assert_eq!(parsed.len(), 2);
+  assert_eq!(parsed[0].url, icons[0].url);
+  assert_eq!(parsed[1].url, icons[1].url);
+}
+
+#[test]
+fn table_aligns_the_columns() {
+  let table = icons().format(OutputFormat::Table);
+  let lines = table.lines().collect::<Vec<_>>();
+
+  assert_eq!(lines.len(), 3);
+  let columns = lines
+    .iter()
+    .map(|line| line.split_whitespace().collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+  assert_eq!(columns[0], ["source", "weight", "size", "url"]);
+  assert_eq!(
+    columns[1],
+    [
+      "readme_image",
+      "42",
+      "256x256",
+      "https://raw.githubusercontent.com/format-owner/widget/main/logo.png"
+    ]
+  );
+  assert_eq!(
+    columns[2],
+    ["site_logo", "-", "svg", "https://widget.dev/icon.svg?a=1,2"]
+  );
+
+  // every row's url starts in the same column
+  let url_column = lines[0].find("url").unwrap();
+  assert_eq!(lines[1].find("https://"), Some(url_column));
+  assert_eq!(lines[2].find("https://"), Some(url_column));
+}
+
+#[test]
+fn csv_has_a_header_and_quotes_commas() {
+  assert_eq!(
+    icons().format(OutputFormat::Csv),
+    [
+      "source,weight,size,url",
+      "readme_image,42,256x256,https://raw.githubusercontent.com/format-owner/widget/main/logo.png",
+      "site_logo,-,svg,\"https://widget.dev/icon.svg?a=1,2\"",
+    ]
+    .join("\n")
+  );
+}
+
+#[test]
+fn formats_parse_from_their_names() {
+  for (name, format) in [
+    ("url", OutputFormat::Url),
+    ("json", OutputFormat::Json),
+    ("table", OutputFormat::Table),
+    ("csv", OutputFormat::Csv),
+  ] {
+    assert_eq!(name.parse::<OutputFormat>(), Ok(format));
+  }
+  assert!("yaml".parse::<OutputFormat>().is_err());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{logo_readme, MockClient, Server, PNG};
+use repo_icons::{Phase, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+fn options(owner_repo: &str, homepage: &str) -> RepoIconsBuilder {
+  let mock = MockClient::new()
+    .repo_with(
+      "partial-owner",
+      owner_repo,
+      json!({ "homepage": homepage }),
+      &logo_readme(PNG),
+      "",
+    )
+    .route(homepage, 500, "Internal Server Error");
+
+  RepoIconsBuilder::new()
+    .repo_files(false)
+    .prefixed_repos(false)
+    .http_client(mock)
+}
+
+#[tokio::test]
+async fn a_failing_homepage_is_a_warning_not_an_error() {
+  let server = Server::start().page_with("/", 500, "text/plain", b"Internal Server Error", None);
+
+  let icons = options("widget", &server.url("/"))
+    .load("partial-owner", "widget")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::ReadmeImage);
+  assert_eq!(icons[0].url.as_str(), PNG);
+  let warnings = icons.warnings();
+  assert!(!warnings.is_empty());
+  assert!(warnings
+    .iter()
+    .all(|warning| warning.source == Phase::Website));
+  assert!(
+    warnings
+      .iter()
+      .any(|warning| warning.message.contains(&server.url("/"))),
+    "{:?}",
+    warnings
+  );
+}
+
+#[tokio::test]
+async fn an_unreachable_homepage_is_a_warning_not_an_error() {
+  // nothing listens on the port once the listener is dropped
+  let port = std::net::TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+  let homepage = format!("http://127.0.0.1:{}/", port);
+
+  let icons = options("unreachable", &homepage)
+    .load("partial-owner", "unreachable")
+    .await
+    .unwrap();
+
+  assert_eq!(icons[0].url.as_str(), PNG);
+  assert!(icons
+    .warnings()
+    .iter()
+    .any(|warning| warning.source == Phase::Website));
+}
+
+#[tokio::test]
+async fn a_missing_repo_is_still_fatal() {
+  let result = RepoIconsBuilder::fast()
+    .http_client(MockClient::new())
+    .load("partial-owner", "missing")
+    .await;
+
+  assert!(result.is_err());
+}
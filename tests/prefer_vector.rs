@@ -0,0 +1,105 @@
+mod common;
+
+use common::{MockClient, SQUARE_PNG};
+use futures::future::LocalBoxFuture;
+use repo_icons::{
+  CandidateSource, IconInfo, IconKind, RepoContext, RepoIcon, RepoIconKind, RepoIconsBuilder,
+};
+use serde_json::json;
+
+const SVG: &str = "https://cdn.example.org/logo.svg";
+const PNG: &str = "https://cdn.example.org/logo-1024.png";
+
+struct Logos;
+
+impl CandidateSource for Logos {
+  fn discover<'a>(&'a self, _: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let kind = RepoIconKind::Site(IconKind::SiteLogo);
+      let png = serde_json::from_value(json!({ "type": "png", "size": "1024x1024" })).unwrap();
+      vec![
+        RepoIcon::new(PNG.parse().unwrap(), kind.clone(), png),
+        RepoIcon::new(SVG.parse().unwrap(), kind, IconInfo::SVG),
+      ]
+    })
+  }
+}
+
+async fn primary(repo: &str, prefer_vector: bool) -> String {
+  let readme = r#"<div id="readme"><article><h1>Widget</h1></article></div>"#;
+  let mock = MockClient::new().repo("vector-owner", repo, readme);
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .add_source(Logos)
+    .prefer_vector(prefer_vector)
+    .load("vector-owner", repo)
+    .await
+    .unwrap();
+
+  assert_eq!(icons.primary().unwrap().icon.url, icons.closest_match().url);
+  icons.closest_match().url.to_string()
+}
+
+#[tokio::test]
+async fn a_vector_wins_over_a_larger_raster_with_the_flag() {
+  assert_eq!(primary("preferred", true).await, SVG);
+}
+
+#[tokio::test]
+async fn a_larger_raster_wins_without_the_flag() {
+  assert_eq!(primary("default", false).await, PNG);
+}
+
+const PSEUDO_SVG: &str = "https://cdn.example.org/wrapped-logo.svg";
+const REAL_SVG: &str = "https://cdn.example.org/vector-logo.svg";
+
+struct Svgs;
+
+impl CandidateSource for Svgs {
+  fn discover<'a>(&'a self, _: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let kind = RepoIconKind::Site(IconKind::SiteLogo);
+      vec![
+        RepoIcon::new(PSEUDO_SVG.parse().unwrap(), kind.clone(), IconInfo::SVG),
+        RepoIcon::new(REAL_SVG.parse().unwrap(), kind, IconInfo::SVG),
+      ]
+    })
+  }
+}
+
+#[tokio::test]
+async fn an_svg_that_only_embeds_a_png_isnt_preferred() {
+  let readme = r#"<div id="readme"><article><h1>Widget</h1></article></div>"#;
+  let wrapped = format!(
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="512" height="512"><image href="{}" width="512" height="512"/></svg>"#,
+    SQUARE_PNG
+  );
+  let mock = MockClient::new()
+    .repo("vector-owner", "wrapped", readme)
+    .route(PSEUDO_SVG, 200, &wrapped)
+    .route(
+      REAL_SVG,
+      200,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 32 32"><path d="M0 0h32v32H0z"/></svg>"#,
+    );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .svg_sizes(true)
+    .add_source(Svgs)
+    .prefer_vector(true)
+    .load("vector-owner", "wrapped")
+    .await
+    .unwrap();
+
+  let pseudo = icons
+    .iter()
+    .find(|icon| icon.url.as_str() == PSEUDO_SVG)
+    .unwrap();
+  assert!(pseudo.pseudo_vector);
+  assert!(!pseudo.is_vector());
+  // ranked as the 2x2 png it embeds
+  assert_eq!(pseudo.dimensions(), Some((2, 2)));
+  assert_eq!(icons.closest_match().url.as_str(), REAL_SVG);
+}
@@ -0,0 +1,51 @@
+use repo_icons::{IconFormat, Readme, ReadmeImage};
+
+async fn logo() -> ReadmeImage {
+  let readme = Readme::new(
+    "formats-owner",
+    "widget",
+    r#"<div id="readme"><article><h1 align="center"><picture><source type="image/avif" srcset="assets/logo.avif"><source type="image/webp" srcset="assets/logo.webp"><img src="assets/logo.png" alt="Widget" width="200"></picture><br>Widget</h1></article></div>"#,
+    false,
+    "main",
+    None,
+  );
+
+  readme.images_offline().await.remove(0)
+}
+
+fn file(image: &ReadmeImage, formats: &[IconFormat]) -> String {
+  let src = image.preferred_src(formats);
+  src.path().rsplit('/').next().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn the_first_supported_format_is_chosen() {
+  let logo = logo().await;
+
+  assert_eq!(
+    logo
+      .picture_sources
+      .iter()
+      .map(|source| source.format)
+      .collect::<Vec<_>>(),
+    [Some(IconFormat::Avif), Some(IconFormat::Webp)]
+  );
+  assert_eq!(
+    file(&logo, &[IconFormat::Avif, IconFormat::Webp]),
+    "logo.avif"
+  );
+  assert_eq!(
+    file(&logo, &[IconFormat::Webp, IconFormat::Avif]),
+    "logo.webp"
+  );
+  assert_eq!(file(&logo, &[IconFormat::Webp]), "logo.webp");
+}
+
+#[tokio::test]
+async fn without_a_supported_format_its_the_img() {
+  let logo = logo().await;
+
+  assert_eq!(file(&logo, &[]), "logo.png");
+  assert_eq!(file(&logo, &[IconFormat::Svg, IconFormat::Png]), "logo.png");
+  assert_eq!(logo.src.path().rsplit('/').next(), Some("logo.png"));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{logo_readme, MockClient, Server, PNG};
+use repo_icons::RepoIconsBuilder;
+use serde_json::json;
+
+const HOMEPAGE: &str = r#"<html><head><title>Widget</title><link rel="icon" href="/favicon.png"></head><body></body></html>"#;
+
+/// The urls a load of the same repo requests with the preset. Each repo has
+/// an owner of its own, as the owner's repos are cached
+async fn requested(options: RepoIconsBuilder, repo: &str) -> Vec<String> {
+  let owner = format!("preset-{}", repo);
+  let site = Server::start().page("/", "text/html", HOMEPAGE.as_bytes());
+  let homepage = site.url("/");
+  let mock = MockClient::new()
+    .repo_with(
+      &owner,
+      repo,
+      json!({ "homepage": homepage, "language": "Rust" }),
+      &logo_readme(PNG),
+      "",
+    )
+    .route(&homepage, 200, HOMEPAGE)
+    .header("Content-Type", "text/html");
+
+  options
+    .http_client(mock.clone())
+    .load(&owner, repo)
+    .await
+    .unwrap();
+  mock.urls()
+}
+
+fn any(urls: &[String], part: &str) -> bool {
+  urls.iter().any(|url| url.contains(part))
+}
+
+#[tokio::test]
+async fn fast_only_loads_the_readme() {
+  let urls = requested(RepoIconsBuilder::fast(), "fast").await;
+
+  assert_eq!(
+    urls,
+    [
+      "https://api.github.com/repos/preset-fast/fast",
+      "https://api.github.com/repos/preset-fast/fast/readme",
+    ]
+  );
+}
+
+#[tokio::test]
+async fn balanced_adds_the_homepage_and_repo_files() {
+  let urls = requested(RepoIconsBuilder::balanced(), "balanced").await;
+
+  assert!(any(&urls, "127.0.0.1"), "{:?}", urls);
+  assert!(any(&urls, "/balanced/commits"), "{:?}", urls);
+  // the readme's source is only needed for the icon directive
+  assert_eq!(
+    urls.iter().filter(|url| url.ends_with("/readme")).count(),
+    1
+  );
+  assert!(!any(&urls, "api.github.com/users/"), "{:?}", urls);
+  assert!(!any(&urls, "https://github.com/"), "{:?}", urls);
+  assert!(!any(&urls, "/contents/"), "{:?}", urls);
+  assert!(!any(&urls, "raw.githubusercontent.com"), "{:?}", urls);
+}
+
+#[tokio::test]
+async fn thorough_checks_every_source() {
+  let urls = requested(RepoIconsBuilder::thorough(), "thorough").await;
+
+  for part in [
+    "127.0.0.1",
+    "/thorough/commits",
+    "/users/preset-thorough/repos",
+    // the social preview
+    "https://github.com/preset-thorough/thorough",
+    // the metadata files
+    "/thorough/HEAD/package.json",
+    // the secondary readmes
+    "/thorough/contents/docs",
+    "/thorough/contents/.github",
+  ] {
+    assert!(any(&urls, part), "{} isn't in {:?}", part, urls);
+  }
+  // the readme's source, for the icon directive
+  assert_eq!(
+    urls.iter().filter(|url| url.ends_with("/readme")).count(),
+    2
+  );
+}
+
+#[tokio::test]
+async fn thorough_makes_more_requests_than_fast() {
+  let fast = requested(RepoIconsBuilder::fast(), "compared-fast").await;
+  let balanced = requested(RepoIconsBuilder::balanced(), "compared-balanced").await;
+  let thorough = requested(RepoIconsBuilder::thorough(), "compared-thorough").await;
+
+  assert!(fast.len() < balanced.len());
+  assert!(balanced.len() < thorough.len());
+}
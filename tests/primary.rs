@@ -0,0 +1,86 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, SQUARE_PNG};
+use repo_icons::{RepoIconKind, RepoIcons, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_readme_logo_is_confident() {
+  let mock = MockClient::new().repo("primary-owner", "widget", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .load("primary-owner", "widget")
+    .await
+    .unwrap();
+
+  let primary = icons.primary().unwrap();
+  assert_eq!(primary.icon.kind, RepoIconKind::ReadmeImage);
+  assert_eq!(primary.icon.url.as_str(), PNG);
+  assert!(primary.confidence > 0.8, "{}", primary.confidence);
+}
+
+#[tokio::test]
+async fn a_close_runner_up_lowers_the_confidence() {
+  let logo = |src| {
+    format!(
+      r#"<p align="center"><img src="{}" alt="logo" width="200" height="200"></p>"#,
+      src
+    )
+  };
+  let html = format!(
+    r#"<div id="readme"><article>{}{}<h1>Project</h1></article></div>"#,
+    logo(PNG),
+    logo(SQUARE_PNG)
+  );
+  let mock = MockClient::new()
+    .repo("primary-owner", "single", &logo_readme(PNG))
+    .repo("primary-owner", "contested", &html);
+  let options = RepoIconsBuilder::new()
+    .website(false)
+    .repo_files(false)
+    .prefixed_repos(false)
+    .http_client(mock);
+
+  let single = options.load("primary-owner", "single").await.unwrap();
+  let contested = options.load("primary-owner", "contested").await.unwrap();
+
+  let single = single.primary().unwrap().confidence;
+  let contested = contested.primary().unwrap().confidence;
+  assert!(contested < single, "{} isn't below {}", contested, single);
+}
+
+#[tokio::test]
+async fn an_avatar_fallback_isnt_confident() {
+  let icons = serde_json::from_value::<RepoIcons>(json!([
+    {
+      "url": "https://github.com/primary-owner.png",
+      "headers": {},
+      "kind": "user_avatar",
+      "type": "png",
+      "size": "460x460",
+    },
+  ]))
+  .unwrap();
+
+  let primary = icons.primary().unwrap();
+  assert_eq!(primary.icon.kind, RepoIconKind::UserAvatar);
+  assert!(primary.confidence < 0.5, "{}", primary.confidence);
+}
+
+#[tokio::test]
+async fn a_generated_avatar_is_barely_confident() {
+  let icons = serde_json::from_value::<RepoIcons>(json!([
+    {
+      "url": "https://github.com/identicon-owner.png",
+      "headers": {},
+      "kind": "user_avatar",
+      "type": "png",
+      "size": "420x420",
+      "default_avatar": true,
+    },
+  ]))
+  .unwrap();
+
+  assert!(icons.primary().unwrap().confidence < 0.1);
+}
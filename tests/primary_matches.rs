@@ -0,0 +1,60 @@
+use repo_icons::{IconInfo, RepoBlob, RepoIcon, RepoIconKind, RepoIcons};
+use serde_json::json;
+
+fn blob_icon(owner: &str, commit_sha: &str, sha: &str) -> RepoIcon {
+  let blob = RepoBlob {
+    owner: owner.to_string(),
+    repo: "widget".to_string(),
+    commit_sha: commit_sha.to_string(),
+    sha: sha.to_string(),
+    path: "assets/logo.svg".to_string(),
+  };
+  let url = format!(
+    "https://raw.githubusercontent.com/{}/widget/{}/assets/logo.svg",
+    owner, commit_sha
+  );
+  RepoIcon::new(
+    url.parse().unwrap(),
+    RepoIconKind::Blob(Some(blob)),
+    IconInfo::SVG,
+  )
+}
+
+#[test]
+fn a_forks_copy_of_the_logo_is_the_same_image() {
+  let upstream = blob_icon("upstream-owner", "1a2b3c", "d4e5f6");
+  let fork = blob_icon("fork-owner", "7a8b9c", "d4e5f6");
+
+  assert_ne!(upstream.url, fork.url);
+  assert_eq!(upstream.content_hash(), fork.content_hash());
+  assert!(upstream.is_same_image(&fork));
+}
+
+#[test]
+fn a_changed_logo_isnt_the_same_image() {
+  let upstream = blob_icon("upstream-owner", "1a2b3c", "d4e5f6");
+  let fork = blob_icon("fork-owner", "7a8b9c", "0f0f0f");
+
+  assert!(!upstream.is_same_image(&fork));
+}
+
+fn icons(url: &str) -> RepoIcons {
+  serde_json::from_value(json!([{
+    "url": url,
+    "headers": {},
+    "kind": "readme_image",
+    "type": "png",
+    "size": "64x64",
+  }]))
+  .unwrap()
+}
+
+#[test]
+fn primary_icons_without_a_content_hash_match_by_fingerprint() {
+  let a = icons("https://cdn.example.org/logo.png?v=2&theme=dark");
+  let b = icons("https://cdn.example.org/logo.png?theme=dark&v=2");
+  let other = icons("https://cdn.example.org/banner.png");
+
+  assert!(a.primary_matches(&b));
+  assert!(!a.primary_matches(&other));
+}
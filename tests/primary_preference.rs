@@ -0,0 +1,65 @@
+mod common;
+
+use common::MockClient;
+use futures::future::LocalBoxFuture;
+use repo_icons::{
+  CandidateSource, IconKind, PrimaryPreference, RepoContext, RepoIcon, RepoIconKind,
+  RepoIconsBuilder,
+};
+use serde_json::json;
+
+const LOGO: &str = "https://cdn.example.org/logo.png";
+const BANNER: &str = "https://cdn.example.org/banner.png";
+
+struct LogoAndBanner;
+
+impl CandidateSource for LogoAndBanner {
+  fn discover<'a>(&'a self, _: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let icon = |url: &str, size| {
+        let info = serde_json::from_value(json!({ "type": "png", "size": size })).unwrap();
+        RepoIcon::new(
+          url.parse().unwrap(),
+          RepoIconKind::Site(IconKind::SiteLogo),
+          info,
+        )
+      };
+      vec![icon(LOGO, "256x256"), icon(BANNER, "1280x320")]
+    })
+  }
+}
+
+async fn primary(repo: &str, preference: Option<PrimaryPreference>) -> String {
+  let readme = r#"<div id="readme"><article><h1>Widget</h1></article></div>"#;
+  let mock = MockClient::new().repo("preference-owner", repo, readme);
+  let mut options = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .add_source(LogoAndBanner);
+  if let Some(preference) = preference {
+    options = options.primary_preference(preference);
+  }
+
+  let icons = options.load("preference-owner", repo).await.unwrap();
+  icons.primary().unwrap().icon.url.to_string()
+}
+
+#[tokio::test]
+async fn the_logo_preference_picks_the_square_logo() {
+  assert_eq!(primary("logo", Some(PrimaryPreference::Logo)).await, LOGO);
+}
+
+#[tokio::test]
+async fn the_banner_preference_picks_the_wide_banner() {
+  assert_eq!(
+    primary("banner", Some(PrimaryPreference::Banner)).await,
+    BANNER
+  );
+}
+
+#[tokio::test]
+async fn auto_picks_the_highest_ranked() {
+  let auto = primary("auto", Some(PrimaryPreference::Auto)).await;
+
+  assert_eq!(auto, primary("default", None).await);
+  assert_eq!(auto, BANNER);
+}
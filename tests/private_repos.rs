@@ -0,0 +1,77 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{set_token, Readme, RepoIcons, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn private_raw_files_need_the_token_while_cdn_images_dont() {
+  set_token("private-token");
+  let html = r#"<div id="readme"><article><img src="logo.png" alt="logo"><img src="https://cdn.example.org/banner.png"></article></div>"#;
+  let readme = Readme::new("private-owner", "widget", html, true, "main", None);
+
+  let images = readme.images_offline().await;
+  let raw = images
+    .iter()
+    .find(|image| image.src.path().ends_with("/logo.png"))
+    .unwrap();
+  let cdn = images
+    .iter()
+    .find(|image| image.src.path() == "/banner.png")
+    .unwrap();
+
+  assert_eq!(raw.headers["Authorization"], "Bearer private-token");
+  assert!(cdn.headers.is_empty());
+}
+
+#[tokio::test]
+async fn only_icons_without_credentials_are_public() {
+  let icons = serde_json::from_value::<RepoIcons>(json!([
+    {
+      "url": "https://raw.githubusercontent.com/private-owner/widget/main/logo.png",
+      "headers": { "Authorization": "Bearer private-token" },
+      "kind": "readme_image",
+      "type": "png",
+      "size": "64x64",
+    },
+    {
+      "url": "https://widget.dev/favicon.png",
+      "headers": {},
+      "kind": "site_favicon",
+      "type": "png",
+      "size": "64x64",
+    },
+  ]))
+  .unwrap();
+
+  assert!(icons[0].is_auth_gated());
+  assert!(!icons[1].is_auth_gated());
+  let public = icons.public_icons();
+  assert_eq!(public.len(), 1);
+  assert_eq!(public[0].url.as_str(), "https://widget.dev/favicon.png");
+}
+
+#[tokio::test]
+async fn the_collection_records_whether_the_repo_is_private() {
+  let mock = MockClient::new()
+    .repo_with(
+      "private-owner",
+      "secret",
+      json!({ "private": true }),
+      &logo_readme(PNG),
+      "",
+    )
+    .repo("private-owner", "open", &logo_readme(PNG));
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  assert!(options
+    .load("private-owner", "secret")
+    .await
+    .unwrap()
+    .private());
+  assert!(!options
+    .load("private-owner", "open")
+    .await
+    .unwrap()
+    .private());
+}
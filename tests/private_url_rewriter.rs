@@ -0,0 +1,81 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::future::LocalBoxFuture;
+use repo_icons::{
+  CandidateSource, IconInfo, IconKind, RepoContext, RepoIcon, RepoIconKind, RepoIconsBuilder,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use url::Url;
+
+const RAW: &str = "https://raw.githubusercontent.com/rewriter-owner/secret/main/logo.svg";
+
+/// a private repo file, which needs the token to load
+struct PrivateFile;
+
+impl CandidateSource for PrivateFile {
+  fn discover<'a>(&'a self, _: &'a RepoContext) -> LocalBoxFuture<'a, Vec<RepoIcon>> {
+    Box::pin(async move {
+      let headers = HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]);
+      vec![RepoIcon::new_with_headers(
+        RAW.parse().unwrap(),
+        headers,
+        RepoIconKind::Site(IconKind::SiteLogo),
+        IconInfo::SVG,
+      )]
+    })
+  }
+}
+
+fn options(repo: &str) -> RepoIconsBuilder {
+  let mock = MockClient::new().repo_with(
+    "rewriter-owner",
+    repo,
+    json!({ "private": true }),
+    &logo_readme(PNG),
+    "",
+  );
+  RepoIconsBuilder::fast()
+    .http_client(mock)
+    .add_source(PrivateFile)
+}
+
+#[tokio::test]
+async fn private_icons_are_rewritten_to_the_proxy() {
+  let icons = options("secret")
+    .private_url_rewriter(|url: &Url, headers: &HashMap<String, String>| {
+      assert_eq!(headers["Authorization"], "Bearer token");
+      let mut proxied = Url::parse("https://myapp.example/icon-proxy").unwrap();
+      proxied.query_pairs_mut().append_pair("url", url.as_str());
+      proxied
+    })
+    .load("rewriter-owner", "secret")
+    .await
+    .unwrap();
+
+  let rewritten = icons
+    .iter()
+    .find(|icon| icon.url.host_str() == Some("myapp.example"))
+    .unwrap();
+  assert_eq!(
+    rewritten.url.as_str(),
+    "https://myapp.example/icon-proxy?url=https%3A%2F%2Fraw.githubusercontent.com%2Frewriter-owner%2Fsecret%2Fmain%2Flogo.svg"
+  );
+  assert!(rewritten.headers.is_empty());
+  assert!(!rewritten.is_auth_gated());
+
+  // public icons are left as is
+  assert!(icons.iter().any(|icon| icon.url.as_str() == PNG));
+}
+
+#[tokio::test]
+async fn private_icons_keep_their_headers_without_a_rewriter() {
+  let icons = options("unrewritten")
+    .load("rewriter-owner", "unrewritten")
+    .await
+    .unwrap();
+
+  let raw = icons.iter().find(|icon| icon.url.as_str() == RAW).unwrap();
+  assert_eq!(raw.headers["Authorization"], "Bearer token");
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{MockClient, PNG, SQUARE_PNG};
+use repo_icons::{Exclusion, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+
+/// A sponsor's logo linking to their site, beside the project's logo linking
+/// to its homepage
+fn readme() -> String {
+  format!(
+    r#"<div id="readme"><article><p align="center"><a href="https://sponsor.example"><img src="{}" alt="sponsor logo" width="200" height="200"></a> <a href="https://widget.dev"><img src="{}" alt="Widget" width="200" height="200"></a></p><h1>Widget</h1></article></div>"#,
+    SQUARE_PNG, PNG
+  )
+}
+
+async fn load(repo: &str, only_project_linked: bool) -> repo_icons::RepoIcons {
+  let mock = MockClient::new().repo_with(
+    "linked-owner",
+    repo,
+    json!({ "homepage": "https://widget.dev" }),
+    &readme(),
+    "",
+  );
+
+  // without fast's stop on a confident logo, which would stop at the sponsor
+  RepoIconsBuilder::new()
+    .http_client(mock)
+    .website(false)
+    .repo_files(false)
+    .prefixed_repos(false)
+    .only_project_linked(only_project_linked)
+    .debug(true)
+    .load("linked-owner", repo)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn only_the_homepage_linked_logo_remains() {
+  let icons = load("only-linked", true).await;
+
+  let readme_icons = icons
+    .iter()
+    .filter(|icon| icon.kind == RepoIconKind::ReadmeImage)
+    .collect::<Vec<_>>();
+  assert_eq!(readme_icons.len(), 1);
+  assert_eq!(readme_icons[0].url.as_str(), PNG);
+
+  let sponsor = icons
+    .candidates()
+    .iter()
+    .find(|candidate| candidate.url == SQUARE_PNG)
+    .unwrap();
+  assert_eq!(sponsor.excluded, Some(Exclusion::NotProjectLinked));
+}
+
+#[tokio::test]
+async fn external_logos_are_kept_by_default() {
+  let icons = load("all-linked", false).await;
+
+  assert!(icons
+    .candidates()
+    .iter()
+    .all(|candidate| candidate.excluded != Some(Exclusion::NotProjectLinked)));
+  assert!(icons
+    .candidates()
+    .iter()
+    .any(|candidate| candidate.url == SQUARE_PNG));
+}
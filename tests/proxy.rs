@@ -0,0 +1,51 @@
+mod common;
+
+use common::{Server, PNG_BYTES};
+use repo_icons::{Readme, RepoIconsBuilder};
+use reqwest::Proxy;
+
+#[tokio::test]
+async fn github_requests_tunnel_through_the_proxy() {
+  // the proxy refuses to tunnel, so the load fails after reaching it
+  let proxy = Server::start();
+
+  let result = RepoIconsBuilder::fast()
+    .proxy(Proxy::all(proxy.url("")).unwrap())
+    .load("proxy-owner", "widget")
+    .await;
+
+  assert!(result.is_err());
+  assert!(
+    proxy.requests().contains(&"api.github.com:443".to_string()),
+    "{:?}",
+    proxy.requests()
+  );
+}
+
+#[tokio::test]
+async fn image_checks_go_through_the_proxy() {
+  let proxy = Server::start().page("http://widget.example/logo.png", "image/png", PNG_BYTES);
+  let html = r#"<div id="readme"><img src="http://widget.example/logo.png" alt="logo"><img src="http://widget.example/missing.png"></div>"#;
+  let readme = Readme::new("proxy-owner", "images", html, false, "main", None);
+  let options = RepoIconsBuilder::fast().proxy(Proxy::http(proxy.url("")).unwrap());
+
+  let images = readme.images_offline().await;
+  let logo = images
+    .iter()
+    .find(|image| image.src.path() == "/logo.png")
+    .unwrap();
+  let missing = images
+    .iter()
+    .find(|image| image.src.path() == "/missing.png")
+    .unwrap();
+
+  assert!(logo.is_live_with(&options).await);
+  assert!(!missing.is_live_with(&options).await);
+  assert_eq!(
+    proxy.requests(),
+    [
+      "http://widget.example/logo.png",
+      "http://widget.example/missing.png"
+    ]
+  );
+}
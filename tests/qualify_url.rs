@@ -0,0 +1,47 @@
+use repo_icons::{Exclusion, Readme};
+
+async fn resolve(src: &str) -> Result<String, Option<Exclusion>> {
+  let html = format!(
+    r#"<div id="readme"><article><img src="{}"></article></div>"#,
+    src
+  );
+  let readme = Readme::new("qualify-owner", "widget", &html, false, "main", None);
+
+  let (mut images, mut excluded) = readme.images_with_excluded().await;
+  match images.pop() {
+    Some(image) => Ok(image.src.to_string()),
+    None => Err(excluded.pop().and_then(|candidate| candidate.excluded)),
+  }
+}
+
+#[tokio::test]
+async fn protocol_relative_srcs_use_https() {
+  assert_eq!(
+    resolve("//cdn.example.com/logo.png").await,
+    Ok("https://cdn.example.com/logo.png".to_string())
+  );
+}
+
+#[tokio::test]
+async fn absolute_srcs_are_untouched() {
+  assert_eq!(
+    resolve("http://example.org/logo.png?v=2").await,
+    Ok("http://example.org/logo.png?v=2".to_string())
+  );
+}
+
+#[tokio::test]
+async fn root_relative_srcs_are_relative_to_the_repo() {
+  assert_eq!(
+    resolve("/assets/logo.png").await,
+    Ok("https://raw.githubusercontent.com/qualify-owner/widget/main/assets/logo.png".to_string())
+  );
+}
+
+#[tokio::test]
+async fn junk_srcs_are_skipped() {
+  assert_eq!(
+    resolve("http://[invalid").await,
+    Err(Some(Exclusion::InvalidUrl))
+  );
+}
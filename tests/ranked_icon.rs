@@ -0,0 +1,30 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{RepoIconsBuilder, ScoringProfile};
+
+#[tokio::test]
+async fn reasons_follow_the_loads_scoring_profile() {
+  let mock = MockClient::new().repo("ranked-owner", "widget", &logo_readme(PNG));
+  let profile = ScoringProfile {
+    size_attrs: 100,
+    align_center: 0,
+    logo_mention: 0,
+    in_primary_heading: 0,
+    ..ScoringProfile::default()
+  };
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .debug(true)
+    .scoring_profile(profile)
+    .load("ranked-owner", "widget")
+    .await
+    .unwrap();
+
+  let top = icons.top(1);
+  let reasons = top[0].reason.split(", ").collect::<Vec<_>>();
+  assert_eq!(reasons[0], "has a set size", "{:?}", reasons);
+  assert!(!reasons.contains(&"centered"), "{:?}", reasons);
+  assert!(!reasons.contains(&"logo keyword"), "{:?}", reasons);
+}
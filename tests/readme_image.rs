@@ -0,0 +1,29 @@
+use repo_icons::{ProjectLink, Readme, ReadmeImage};
+
+#[tokio::test]
+async fn alt_and_link_href_round_trip_through_json() {
+  let html = r#"<div id="readme"><a href="https://widget.dev/"><img src="https://example.org/logo.png" alt="Widget logo"></a></div>"#;
+  let readme = Readme::new(
+    "image-owner",
+    "widget",
+    html,
+    false,
+    "main",
+    Some("https://widget.dev".parse().unwrap()),
+  );
+
+  let image = readme.images().await.remove(0);
+  assert_eq!(image.links_to, Some(ProjectLink::Website));
+
+  let json = serde_json::to_value(&image).unwrap();
+  assert_eq!(json["alt"], "Widget logo");
+  assert_eq!(json["link_href"], "https://widget.dev/");
+
+  let parsed = serde_json::from_value::<ReadmeImage>(json).unwrap();
+  assert_eq!(parsed.alt.as_deref(), Some("Widget logo"));
+  assert_eq!(
+    parsed.link_href.as_ref().map(|href| href.as_str()),
+    Some("https://widget.dev/")
+  );
+  assert_eq!(parsed, image);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+
+const START: &str = r#"<div id="readme"><article>"#;
+
+fn image(i: usize) -> String {
+  format!(r#"<p><img src="https://cdn.example.org/{}.png"></p>"#, i)
+}
+
+fn many_images(count: usize) -> String {
+  let images = (0..count).map(image).collect::<String>();
+  format!("{}{}</article></div>", START, images)
+}
+
+#[tokio::test]
+async fn max_images_caps_the_images_processed() {
+  let mut readme = Readme::new(
+    "limits-owner",
+    "widget",
+    &many_images(5000),
+    false,
+    "main",
+    None,
+  );
+  readme.max_images = Some(50);
+
+  let images = readme.images().await;
+
+  assert_eq!(images.len(), 50);
+  // the earliest images are kept
+  assert!(images.iter().all(|image| image.document_index < 50));
+}
+
+#[tokio::test]
+async fn max_readme_bytes_truncates_the_readme_before_parsing() {
+  let mock = MockClient::new().repo("limits-owner", "huge", &many_images(5000));
+  // up to the end of the 100th image
+  let max_bytes = START.len() + (0..100).map(|i| image(i).len()).sum::<usize>();
+  let options = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .max_readme_bytes(max_bytes);
+
+  let readme = Readme::load_with(&options, "limits-owner", "huge")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(images.len(), 100);
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "image")]
+
+mod common;
+
+use common::MockClient;
+use image::{DynamicImage, ImageOutputFormat, Rgba, RgbaImage};
+use repo_icons::{IconInfo, RepoIcon, RepoIconKind, RepoIconsBuilder};
+use serde_json::json;
+use std::{collections::HashMap, io::Cursor};
+
+fn icon(url: &str, info: serde_json::Value) -> RepoIcon {
+  let info = serde_json::from_value::<IconInfo>(info).unwrap();
+  let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+  RepoIcon::new_with_headers(
+    url.parse().unwrap(),
+    headers,
+    RepoIconKind::ReadmeImage,
+    info,
+  )
+}
+
+fn decode(png: &[u8]) -> RgbaImage {
+  image::load_from_memory(png).unwrap().to_rgba8()
+}
+
+#[tokio::test]
+async fn a_wide_raster_is_padded_to_a_square_png() {
+  let url = "https://cdn.example.org/wide.png";
+  let mut source = Vec::new();
+  DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 20, Rgba([255, 0, 0, 255])))
+    .write_to(&mut Cursor::new(&mut source), ImageOutputFormat::Png)
+    .unwrap();
+  let mock = MockClient::new().route_bytes(url, 200, &source);
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+
+  let png = icon(url, json!({ "type": "png", "size": "40x20" }))
+    .render_png_with(&options, 64)
+    .await
+    .unwrap();
+
+  let rendered = decode(&png);
+  assert_eq!(rendered.dimensions(), (64, 64));
+  // the padding above & below is transparent
+  assert_eq!(rendered.get_pixel(32, 2)[3], 0);
+  assert_eq!(rendered.get_pixel(32, 61)[3], 0);
+  assert_eq!(*rendered.get_pixel(32, 32), Rgba([255, 0, 0, 255]));
+  // the icon's headers are sent
+  assert_eq!(
+    mock.request_header(0, "Authorization").as_deref(),
+    Some("Bearer secret")
+  );
+}
+
+#[tokio::test]
+async fn a_size_of_zero_is_an_error() {
+  let icon = icon(common::PNG, json!({ "type": "png", "size": "1x1" }));
+
+  assert!(icon.render_png(0).await.is_err());
+}
+
+#[cfg(feature = "svg")]
+#[tokio::test]
+async fn an_svg_is_rendered_at_the_size() {
+  let url = "https://cdn.example.org/logo.svg";
+  let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><rect width="10" height="10" fill="#00f"/></svg>"##;
+  let mock = MockClient::new().route_bytes(url, 200, svg);
+  let options = RepoIconsBuilder::new().http_client(mock);
+
+  let png = icon(url, json!({ "type": "svg" }))
+    .render_png_with(&options, 32)
+    .await
+    .unwrap();
+
+  let rendered = decode(&png);
+  assert_eq!(rendered.dimensions(), (32, 32));
+  assert_eq!(*rendered.get_pixel(16, 16), Rgba([0, 0, 255, 255]));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{add_repo_alias, ProjectLink, Readme, RepoIconsBuilder};
+
+#[tokio::test]
+async fn an_aliased_repos_files_are_sourced_from_the_repo() {
+  add_repo_alias(("alias-mirror", "widget"), ("alias-upstream", "widget"));
+  let readme = r#"<div id="readme"><article><p align="center"><a href="https://github.com/Alias-Upstream/widget/blob/main/docs/guide.md"><img src="https://raw.githubusercontent.com/alias-upstream/widget/main/logo.png" alt="logo"></a></p><h1>Widget</h1></article></div>"#;
+  let mock = MockClient::new().repo("alias-mirror", "widget", readme);
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+
+  let readme = Readme::load_with(&options, "alias-mirror", "widget")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert!(images[0].sourced_from_repo);
+  assert_eq!(images[0].links_to, Some(ProjectLink::Repo));
+  // the alias is checked before looking up the upstream's redirects
+  assert!(!mock
+    .urls()
+    .iter()
+    .any(|url| url.contains("repos/alias-upstream/")));
+}
+
+#[tokio::test]
+async fn other_repos_arent_aliased() {
+  add_repo_alias(("alias-fork", "widget"), ("alias-origin", "widget"));
+  let readme = Readme::new(
+    "alias-fork",
+    "widget",
+    r#"<div id="readme"><img src="https://raw.githubusercontent.com/alias-stranger/widget/main/logo.png" alt="logo"></div>"#,
+    false,
+    "main",
+    None,
+  );
+
+  let images = readme.images_offline().await;
+
+  assert!(!images[0].sourced_from_repo);
+}
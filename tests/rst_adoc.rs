@@ -0,0 +1,71 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn renders_an_rst_file_with_its_html_media_type() {
+  let contents = "https://api.github.com/repos/rst-owner/widget/contents/README.rst";
+  let mock = MockClient::new()
+    .repo("rst-owner", "widget", "")
+    .route(
+      contents,
+      200,
+      ".. repo-icon: docs/icon.svg\n\n.. image:: docs/logo.png\n   :alt: Widget logo\n   :align: center\n\nWidget\n======\n",
+    )
+    .accept("raw")
+    .route(
+      contents,
+      200,
+      r#"<div id="readme" class="rst"><article><img alt="Widget logo" class="align-center" src="docs/logo.png"><h1>Widget</h1></article></div>"#,
+    )
+    .accept("html");
+  let options = RepoIconsBuilder::fast().http_client(mock.clone());
+
+  let readme = Readme::load_path_with(&options, "rst-owner", "widget", "README.rst")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/rst-owner/widget/main/docs/logo.png"
+  );
+  assert!(images[0].is_align_center);
+  assert!(images[0].is_logo_candidate());
+  assert_eq!(
+    readme.declared_icon().await.unwrap().0.as_str(),
+    "https://raw.githubusercontent.com/rst-owner/widget/main/docs/icon.svg"
+  );
+  // github's markdown api would render the rst as a paragraph of text
+  assert!(!mock
+    .urls()
+    .contains(&"https://api.github.com/markdown".to_string()));
+}
+
+#[tokio::test]
+async fn centers_an_asciidoc_readme_logo() {
+  let html = r#"<div id="readme" class="asciidoc"><article><div class="imageblock text-center"><div class="content"><img src="images/logo.png" alt="Widget logo"></div></div><h1>Widget</h1></article></div>"#;
+  let source = "= Widget\n// repo-icon: images/icon.svg\n\n[.text-center]\nimage::images/logo.png[Widget logo]\n";
+  let mock = MockClient::new().repo_with("adoc-owner", "widget", json!({}), html, source);
+  let options = RepoIconsBuilder::new()
+    .http_client(mock)
+    .icon_directive(true);
+
+  let readme = Readme::load_with(&options, "adoc-owner", "widget")
+    .await
+    .unwrap();
+  let images = readme.images().await;
+
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/adoc-owner/widget/main/images/logo.png"
+  );
+  assert!(images[0].is_align_center);
+  assert!(images[0].is_logo_candidate());
+  assert_eq!(
+    readme.declared_icon().await.unwrap().0.as_str(),
+    "https://raw.githubusercontent.com/adoc-owner/widget/main/images/icon.svg"
+  );
+}
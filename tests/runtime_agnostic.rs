@@ -0,0 +1,40 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use futures::executor::block_on;
+use repo_icons::{RepoIconKind, RepoIconsBuilder};
+use std::time::Duration;
+
+#[test]
+fn a_full_load_runs_without_tokio() {
+  let mock = MockClient::new().repo("runtime-owner", "widget", &logo_readme(PNG));
+
+  let icons = block_on(
+    RepoIconsBuilder::fast()
+      .http_client(mock.clone())
+      .load("runtime-owner", "widget"),
+  )
+  .unwrap();
+
+  assert_eq!(icons[0].kind, RepoIconKind::ReadmeImage);
+  assert_eq!(icons[0].url.as_str(), PNG);
+  assert!(mock
+    .urls()
+    .iter()
+    .all(|url| url.starts_with("https://api.github.com/")));
+}
+
+#[test]
+fn a_deadline_doesnt_need_tokios_timer() {
+  let mock = MockClient::new().repo("runtime-owner", "deadline", &logo_readme(PNG));
+
+  let icons = block_on(
+    RepoIconsBuilder::fast()
+      .http_client(mock)
+      .deadline(Duration::from_secs(5))
+      .load("runtime-owner", "deadline"),
+  )
+  .unwrap();
+
+  assert_eq!(icons[0].url.as_str(), PNG);
+}
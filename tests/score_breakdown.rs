@@ -0,0 +1,63 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{RepoIconsBuilder, ScoringProfile, Signal};
+
+#[tokio::test]
+async fn the_breakdown_sums_to_the_weight() {
+  let mock = MockClient::new().repo("breakdown-owner", "widget", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .collect_breakdown(true)
+    .load("breakdown-owner", "widget")
+    .await
+    .unwrap();
+
+  let debug = icons[0].debug.as_ref().expect("the breakdown is collected");
+  let total: i16 = debug.score_breakdown.iter().map(|(_, points)| points).sum();
+  assert_eq!(total, debug.weight as i16);
+
+  let signals = debug
+    .score_breakdown
+    .iter()
+    .map(|(signal, _)| *signal)
+    .collect::<Vec<_>>();
+  assert_eq!(signals, debug.signals);
+  for signal in [
+    Signal::AlignCenter,
+    Signal::SizeAttrs,
+    Signal::LogoMention,
+    Signal::CenteredFirstImage,
+  ] {
+    assert!(
+      signals.contains(&signal),
+      "{:?} isn't in {:?}",
+      signal,
+      signals
+    );
+  }
+}
+
+#[tokio::test]
+async fn the_breakdown_uses_the_scoring_profile() {
+  let mock = MockClient::new().repo("breakdown-owner", "profiled", &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .collect_breakdown(true)
+    .scoring_profile(ScoringProfile {
+      logo_mention: 0,
+      ..ScoringProfile::default()
+    })
+    .load("breakdown-owner", "profiled")
+    .await
+    .unwrap();
+
+  let debug = icons[0].debug.as_ref().unwrap();
+  assert!(debug.score_breakdown.contains(&(Signal::LogoMention, 0)));
+  let align_center = ScoringProfile::default().align_center as i16;
+  assert!(debug
+    .score_breakdown
+    .contains(&(Signal::AlignCenter, align_center)));
+}
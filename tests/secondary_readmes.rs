@@ -0,0 +1,53 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::RepoIconsBuilder;
+
+#[tokio::test]
+async fn secondary_readmes_reuse_the_loaded_metadata() {
+  let api = "https://api.github.com/repos/secondary-owner/widget";
+  let mock = MockClient::new()
+    .repo(
+      "secondary-owner",
+      "widget",
+      r#"<div id="readme"><article><h1>Project</h1></article></div>"#,
+    )
+    .route(&format!("{}/branches/main", api), 200, "{}")
+    .route(
+      &format!("{}/contents/docs", api),
+      200,
+      r#"[{"name":"README.md","path":"docs/README.md","type":"file"}]"#,
+    )
+    .route(
+      &format!("{}/contents/docs/README.md", api),
+      200,
+      "# Project",
+    )
+    .route("https://api.github.com/markdown", 200, &logo_readme(PNG));
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .branch("main")
+    .scan_all_readmes(true)
+    .load("secondary-owner", "widget")
+    .await
+    .unwrap();
+
+  assert!(icons.iter().any(|icon| icon.url.as_str() == PNG));
+
+  let urls = mock.urls();
+  let fetched = |url: &str| {
+    urls
+      .iter()
+      .filter(|u| u.split('?').next() == Some(url))
+      .count()
+  };
+  assert_eq!(fetched(api), 1, "{:?}", urls);
+  assert_eq!(fetched(&format!("{}/branches/main", api)), 1, "{:?}", urls);
+  assert_eq!(
+    fetched(&format!("{}/contents/docs/README.md", api)),
+    1,
+    "{:?}",
+    urls
+  );
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG, WIDE_PNG};
+use repo_icons::{RepoIconKind, RepoIconsBuilder};
+
+const README: &str = r#"<div id="readme"><article><h1>Project</h1></article></div>"#;
+
+fn repo_page(og_image: &str) -> String {
+  format!(
+    r#"<html><head><meta property="og:image" content="{}"><meta property="og:image:alt" content="Widget banner"></head></html>"#,
+    og_image
+  )
+}
+
+#[tokio::test]
+async fn a_custom_social_preview_is_a_candidate() {
+  let mock = MockClient::new()
+    .repo("preview-owner", "custom", README)
+    .route(
+      "https://github.com/preview-owner/custom",
+      200,
+      &repo_page(WIDE_PNG),
+    );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock)
+    .social_preview(true)
+    .load("preview-owner", "custom")
+    .await
+    .unwrap();
+
+  let preview = icons
+    .iter()
+    .find(|icon| icon.kind == RepoIconKind::SocialPreview)
+    .expect("the social preview");
+  assert_eq!(preview.url.as_str(), WIDE_PNG);
+  assert_eq!(preview.label.as_deref(), Some("Widget banner"));
+}
+
+#[tokio::test]
+async fn the_generated_social_preview_is_skipped() {
+  let mock = MockClient::new()
+    .repo("preview-owner", "generic", &logo_readme(PNG))
+    .route(
+      "https://github.com/preview-owner/generic",
+      200,
+      &repo_page("https://opengraph.githubassets.com/0a1b2c/preview-owner/generic"),
+    );
+
+  let icons = RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .social_preview(true)
+    .load("preview-owner", "generic")
+    .await
+    .unwrap();
+
+  assert!(mock
+    .urls()
+    .contains(&"https://github.com/preview-owner/generic".to_string()));
+  let kinds = icons.iter().map(|icon| &icon.kind).collect::<Vec<_>>();
+  assert_eq!(kinds, [&RepoIconKind::ReadmeImage]);
+}
@@ -0,0 +1,82 @@
+use repo_icons::{IconInfo, RepoIcon, RepoIconKind, RepoIcons, SortStrategy};
+use serde_json::json;
+
+fn icon(name: &str, info: IconInfo) -> RepoIcon {
+  let url = format!("https://example.org/{}", name).parse().unwrap();
+  RepoIcon::new(url, RepoIconKind::ReadmeImage, info)
+}
+
+/// `kind` is `png` / `jpeg`, `size` is `{width}x{height}`
+fn info(kind: &str, size: &str) -> IconInfo {
+  serde_json::from_value(json!({ "type": kind, "size": size })).unwrap()
+}
+
+fn icons() -> RepoIcons {
+  let icons = vec![
+    icon("small-square.png", info("png", "64x64")),
+    icon("wide.png", info("png", "512x128")),
+    icon("unsized.svg", IconInfo::SVG),
+    icon("nearly-square.jpg", info("jpeg", "200x180")),
+  ];
+  serde_json::from_value(serde_json::to_value(icons).unwrap()).unwrap()
+}
+
+fn sorted(strategy: SortStrategy) -> Vec<String> {
+  icons()
+    .sorted_by(strategy)
+    .iter()
+    .map(|icon| icon.url.path().trim_start_matches('/').to_string())
+    .collect()
+}
+
+#[test]
+fn weight_keeps_the_default_order() {
+  assert_eq!(
+    sorted(SortStrategy::Weight),
+    [
+      "small-square.png",
+      "wide.png",
+      "unsized.svg",
+      "nearly-square.jpg"
+    ]
+  );
+}
+
+#[test]
+fn pixel_area_is_largest_first_with_unknown_sizes_last() {
+  assert_eq!(
+    sorted(SortStrategy::PixelArea),
+    [
+      "wide.png",
+      "nearly-square.jpg",
+      "small-square.png",
+      "unsized.svg"
+    ]
+  );
+}
+
+#[test]
+fn squareness_is_closest_to_square_first_with_unknown_sizes_last() {
+  assert_eq!(
+    sorted(SortStrategy::Squareness),
+    [
+      "small-square.png",
+      "nearly-square.jpg",
+      "wide.png",
+      "unsized.svg"
+    ]
+  );
+}
+
+#[test]
+fn prefer_vector_puts_svgs_first() {
+  assert_eq!(
+    sorted(SortStrategy::PreferVector),
+    [
+      "unsized.svg",
+      "small-square.png",
+      "wide.png",
+      "nearly-square.jpg"
+    ]
+  );
+}
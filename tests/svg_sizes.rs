@@ -0,0 +1,67 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{IconInfo, IconKind, RepoIcon, RepoIconKind, RepoIconsBuilder, VectorSize};
+
+const SVG: &str = "https://cdn.example.org/logo.svg";
+
+/// The notional size read from the `svg` the icon points to
+async fn size(svg: &str) -> Option<(u32, u32)> {
+  let mock = MockClient::new()
+    .route(SVG, 200, svg)
+    .header("Content-Type", "image/svg+xml");
+  let options = RepoIconsBuilder::new().http_client(mock);
+  let mut icon = RepoIcon::new(
+    SVG.parse().unwrap(),
+    RepoIconKind::Site(IconKind::SiteLogo),
+    IconInfo::SVG,
+  );
+
+  icon.load_vector_size_with(&options).await.unwrap();
+  assert!(icon.is_vector());
+  assert_eq!(
+    icon.dimensions(),
+    icon
+      .vector_size
+      .map(|VectorSize { width, height }| (width, height))
+  );
+  icon.dimensions()
+}
+
+#[tokio::test]
+async fn the_width_and_height_are_the_size() {
+  let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="48" height="32"></svg>"#;
+
+  assert_eq!(size(svg).await, Some((48, 32)));
+}
+
+#[tokio::test]
+async fn the_view_box_is_the_size_without_dimensions() {
+  let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 50"></svg>"#;
+
+  assert_eq!(size(svg).await, Some((100, 50)));
+}
+
+#[tokio::test]
+async fn a_missing_dimension_keeps_the_view_box_aspect() {
+  let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" viewBox="0,0,100,50"></svg>"#;
+
+  assert_eq!(size(svg).await, Some((200, 100)));
+}
+
+#[tokio::test]
+async fn pixel_units_are_read_and_percentages_ignored() {
+  let pixels = r#"<svg xmlns="http://www.w3.org/2000/svg" width="64px" height="32px"></svg>"#;
+  let percentages = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100%" height="100%" viewBox="0 0 24 24"></svg>"#;
+
+  assert_eq!(size(pixels).await, Some((64, 32)));
+  assert_eq!(size(percentages).await, Some((24, 24)));
+}
+
+#[tokio::test]
+async fn an_svg_without_a_size_has_none() {
+  let svg =
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="100%"><path d="M0 0h16v16H0z"/></svg>"#;
+
+  assert_eq!(size(svg).await, None);
+}
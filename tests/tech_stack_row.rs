@@ -0,0 +1,34 @@
+use repo_icons::{Readme, ScoringProfile, Signal};
+
+/// A row of "built with" logos, each linking to its technology's site, above
+/// the project's own logo
+const README: &str = r#"<div id="readme"><article><p align="center">Built with <a href="https://react.dev"><img src="https://cdn.example.org/react.png" width="40" height="40"></a> <a href="https://nodejs.org"><img src="https://cdn.example.org/node.png" width="40" height="40"></a> <a href="https://postgresql.org"><img src="https://cdn.example.org/postgres.png" width="40" height="40"></a></p>
+<p align="center"><img src="assets/widget.png" alt="Widget" width="200" height="200"></p>
+<h1>Widget</h1></article></div>"#;
+
+#[tokio::test]
+async fn the_logo_outranks_the_tech_stack_row() {
+  let readme = Readme::new("stack-owner", "widget", README, false, "main", None);
+
+  let images = readme.images_offline().await;
+
+  assert_eq!(images.len(), 4);
+  assert_eq!(
+    images[0].src.as_str(),
+    "https://raw.githubusercontent.com/stack-owner/widget/main/assets/widget.png"
+  );
+  assert!(!images[0].in_tech_stack_row);
+
+  for member in &images[1..] {
+    assert!(member.in_tech_stack_row, "{}", member.src);
+    assert!(member.signals().contains(&Signal::TechStackRow));
+    assert!(member.weight() < images[0].weight(), "{}", member.src);
+  }
+
+  // without the penalty, the row's first image (the centered first image) would win
+  let unpenalized = ScoringProfile {
+    tech_stack_row_penalty: 0,
+    ..ScoringProfile::default()
+  };
+  assert!(images[1].weight_with(&unpenalized) > images[0].weight_with(&unpenalized));
+}
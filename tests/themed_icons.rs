@@ -0,0 +1,44 @@
+use repo_icons::Readme;
+use url::Url;
+
+fn readme(html: &str) -> Readme {
+  Readme::new("themed-owner", "widget", html, false, "main", None)
+}
+
+/// The file the url points to, as the sources & the image are resolved to
+/// different raw urls of the repo
+fn file(url: &Url) -> &str {
+  url.path().rsplit('/').next().unwrap()
+}
+
+#[tokio::test]
+async fn a_picture_is_a_single_themed_icon() {
+  let readme = readme(
+    r#"<div id="readme"><article><h1 align="center"><picture><source media="(prefers-color-scheme: dark)" srcset="assets/logo-dark.png"><source media="(prefers-color-scheme: light)" srcset="assets/logo-light.png"><img src="assets/logo.png" alt="Widget" width="200"></picture><br>Widget</h1></article></div>"#,
+  );
+
+  let images = readme.images_offline().await;
+
+  // the sources aren't images of their own
+  assert_eq!(images.len(), 1, "{:?}", images);
+  let themed = images[0].themed();
+  assert_eq!(file(&themed.light), "logo-light.png");
+  assert_eq!(themed.dark.as_ref().map(file), Some("logo-dark.png"));
+  assert_eq!(
+    themed.urls().into_iter().map(file).collect::<Vec<_>>(),
+    ["logo-light.png", "logo-dark.png"]
+  );
+}
+
+#[tokio::test]
+async fn an_image_without_variants_is_only_light() {
+  let readme = readme(
+    r#"<div id="readme"><article><h1 align="center"><img src="assets/logo.png" alt="Widget" width="200"><br>Widget</h1></article></div>"#,
+  );
+
+  let themed = readme.images_offline().await[0].themed();
+
+  assert_eq!(file(&themed.light), "logo.png");
+  assert_eq!(themed.dark, None);
+  assert_eq!(themed.urls().len(), 1);
+}
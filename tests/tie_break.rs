@@ -0,0 +1,26 @@
+use repo_icons::Readme;
+
+#[tokio::test]
+async fn equal_weight_images_keep_their_document_order() {
+  // the later image sorts first by url, so only the document order puts it second
+  let html = r#"<div id="readme"><article>
+    <h1>Widget</h1>
+    <p><img src="https://example.org/first.png"></p>
+    <h2>Usage</h2>
+    <p><img src="https://example.org/z.png"><img src="https://example.org/a.png"></p>
+  </article></div>"#;
+  let readme = Readme::new("tie-owner", "widget", html, false, "main", None);
+
+  for _ in 0..10 {
+    let images = readme.images().await;
+    let tied = images
+      .iter()
+      .filter(|image| image.src.path() != "/first.png")
+      .collect::<Vec<_>>();
+
+    assert_eq!(tied[0].weight(), tied[1].weight());
+    assert_eq!(tied[0].src.path(), "/z.png");
+    assert_eq!(tied[1].src.path(), "/a.png");
+    assert!(tied[0].document_index < tied[1].document_index);
+  }
+}
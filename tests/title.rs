@@ -0,0 +1,48 @@
+use repo_icons::Readme;
+
+fn title(heading: &str) -> Option<String> {
+  let html = format!(
+    r#"<div id="readme"><article>{}<p>A framework</p></article></div>"#,
+    heading
+  );
+  Readme::new("title-owner", "next.js", &html, false, "main", None).title()
+}
+
+#[test]
+fn a_text_heading_drops_its_trailing_badge() {
+  assert_eq!(
+    title(
+      r#"<h1>Next.js <img src="https://img.shields.io/npm/v/next.svg" alt="npm version"></h1>"#
+    )
+    .as_deref(),
+    Some("Next.js")
+  );
+}
+
+#[test]
+fn an_image_only_heading_uses_the_alt() {
+  assert_eq!(
+    title(r#"<h1 align="center"><img src="logo.png" alt="Next.js"></h1>"#).as_deref(),
+    Some("Next.js")
+  );
+}
+
+#[test]
+fn a_badge_alt_isnt_a_title() {
+  assert_eq!(
+    title(r#"<h1><img src="https://img.shields.io/npm/v/next.svg" alt="npm version"></h1>"#),
+    None
+  );
+}
+
+#[test]
+fn only_the_first_line_of_a_multi_line_heading_is_used() {
+  assert_eq!(
+    title("<h1>Next.js<br>The React Framework</h1>").as_deref(),
+    Some("Next.js")
+  );
+  assert_eq!(
+    title("<h1>\n  Next.js\n  The React Framework\n</h1>").as_deref(),
+    Some("Next.js")
+  );
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{get_token, set_token, set_token_provider, RepoIconsBuilder};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static MINTED: AtomicUsize = AtomicUsize::new(0);
+
+#[tokio::test]
+async fn the_provider_is_called_for_every_request() {
+  set_token_provider(|| {
+    let token = MINTED.fetch_add(1, Ordering::SeqCst);
+    Some(format!("installation-{}", token))
+  });
+
+  let mock = MockClient::new().repo("provider-owner", "widget", &logo_readme(PNG));
+  RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .icon_directive(true)
+    .load("provider-owner", "widget")
+    .await
+    .unwrap();
+
+  let authorizations = (0..mock.request_count())
+    .map(|nth| mock.request_header(nth, "authorization").unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(authorizations.len(), 3);
+  assert!(MINTED.load(Ordering::SeqCst) >= authorizations.len());
+  for (nth, authorization) in authorizations.iter().enumerate() {
+    assert!(authorization.starts_with("Bearer installation-"));
+    // each request gets a freshly minted token
+    assert!(!authorizations[nth + 1..].contains(authorization));
+  }
+
+  set_token("static-token");
+  assert_eq!(get_token().as_deref(), Some("static-token"));
+  assert_eq!(get_token().as_deref(), Some("static-token"));
+}
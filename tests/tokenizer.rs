@@ -0,0 +1,171 @@
+#![cfg(feature = "tokenizer")]
+
+use repo_icons::{parse_simple_readme, Readme};
+use scraper::{Html, Selector};
+
+/// A readme as github renders it: a linked logo, a heading with its anchor
+/// icon, badges and a syntax highlighted code block
+const GITHUB: &str = r##"<div id="readme" class="md" data-path="README.md"><article class="markdown-body entry-content container-lg" itemprop="text"><p align="center">
+  <a href="https://widget.dev" rel="nofollow"><img src="https://raw.githubusercontent.com/widget/widget/main/assets/logo.png" alt="Widget logo" width="200" style="max-width: 100%;"></a>
+</p>
+<div class="markdown-heading" dir="auto"><h1 tabindex="-1" class="heading-element" dir="auto">Widget</h1><a id="user-content-widget" class="anchor" aria-label="Permalink: Widget" href="#widget"><svg class="octicon octicon-link" viewBox="0 0 16 16" version="1.1" width="16" height="16" aria-hidden="true"><path d="m7.775 3.275 1.25-1.25a3.5 3.5 0 1 1 4.95 4.95l-2.5 2.5a3.5 3.5 0 0 1-4.95 0Z"></path></svg></a></div>
+<p dir="auto">A fast &amp; friendly widget toolkit for the terminal.</p>
+<p dir="auto"><a href="https://github.com/widget/widget/actions"><img src="https://github.com/widget/widget/actions/workflows/ci.yml/badge.svg" alt="CI" style="max-width: 100%;"></a> <a href="https://crates.io/crates/widget" rel="nofollow"><img src="https://camo.githubusercontent.com/0a1b/68747470733a2f2f696d672e736869656c64732e696f2f6372617465732f762f776964676574" alt="crates.io" data-canonical-src="https://img.shields.io/crates/v/widget" style="max-width: 100%;"></a></p>
+<div class="highlight highlight-source-rust notranslate position-relative overflow-auto" dir="auto"><pre><span class="pl-k">use</span> widget<span class="pl-kos">::</span><span class="pl-v">Widget</span><span class="pl-kos">;</span>
+
+<span class="pl-k">fn</span> <span class="pl-en">main</span><span class="pl-kos">(</span><span class="pl-kos">)</span> <span class="pl-kos">{</span>
+  <span class="pl-v">Widget</span><span class="pl-kos">::</span><span class="pl-en">new</span><span class="pl-kos">(</span><span class="pl-s"><span class="pl-pds">"</span>terminal toolkit<span class="pl-pds">"</span></span><span class="pl-kos">)</span><span class="pl-kos">;</span>
+<span class="pl-kos">}</span></pre><div class="zeroclipboard-container"><clipboard-copy aria-label="Copy" class="ClipboardButton btn" value="use widget::Widget;" tabindex="0" role="button"><svg aria-hidden="true" height="16" viewBox="0 0 16 16" version="1.1" width="16" class="octicon octicon-copy js-clipboard-copy-icon"><path d="M0 6.75C0 5.784.784 5 1.75 5h1.5Z"></path></svg></clipboard-copy></div></div>
+</article></div>"##;
+
+/// Tables with & without their `<tbody>`, one holding a row of logos
+const TABLES: &str = r#"<div id="readme"><article><table>
+<thead>
+<tr>
+<th>Backend</th>
+<th>Status</th>
+</tr>
+</thead>
+<tbody>
+<tr>
+<td>Linux</td>
+<td><img src="https://img.shields.io/badge/linux-passing-green" alt="linux"></td>
+</tr>
+</tbody>
+</table>
+<table><tr><td align="center"><img src="docs/logo.svg" alt="widget" width="120" height="120"></td><td><h2>Widget</h2></td></tr></table>
+<table><td><a href="https://rust-lang.org"><img src="rust.png" width="40"></a></td><td><a href="https://tokio.rs"><img src="tokio.png" width="40"></a></td><td><a href="https://serde.rs"><img src="serde.png" width="40"></a></td></table>
+</article></div>"#;
+
+/// Light & dark logos, collapsed & open `<details>` and nested lists
+const STRUCTURE: &str = r#"<div id="readme"><article><h1 align="center">
+  <picture>
+    <source media="(prefers-color-scheme: dark)" srcset="assets/logo-dark.png">
+    <source media="(prefers-color-scheme: light)" srcset="assets/logo-light.png">
+    <img alt="Widget" src="assets/logo-light.png">
+  </picture>
+  <br>Widget
+</h1>
+<ul>
+<li><p>Fast</p>
+<ul>
+<li>really <em>fast</em></li>
+<li><img src="assets/speed.png" alt="speed"></li>
+</ul>
+</li>
+<li>Friendly</li>
+</ul>
+<details><summary><img src="assets/screenshot.png" alt="screenshot"></summary><img src="assets/demo.gif" alt="demo"></details>
+<details open><summary>More</summary><p><img src="assets/diagram.png" alt="diagram"></p></details>
+<dl><dt>Term</dt><dd>Definition</dd></dl>
+</article></div>"#;
+
+/// An inline svg logo, an embedded one and images that aren't shown
+const EMBEDS: &str = r##"<div id="readme"><article><p align="center"><svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 100 100" width="100" height="100" preserveAspectRatio="xMidYMid"><title>Widget logo</title><defs><linearGradient id="g" gradientUnits="userSpaceOnUse"><stop offset="0" stop-color="#f00"/></linearGradient><clipPath id="c"><rect width="100" height="100"/></clipPath></defs><use xlink:href="#shape"/><style><![CDATA[ .a > .b { fill: url(#g) } ]]></style><circle cx="50" cy="50" r="40" fill="url(#g)" clip-path="url(#c)"></circle></svg></p>
+<p><object data="assets/logo.svg" type="image/svg+xml" width="64"></object></p>
+<p hidden><img src="assets/hidden.png" alt="logo"></p>
+<div style="display: none"><img src="assets/print.png"></div>
+<p><img src="assets/decoy.png" aria-hidden="true" alt="logo"></p>
+<!-- a comment -->
+<p><img src="assets/divider.png"></p><p><img src="assets/divider.png"></p><p><img src="assets/divider.png"></p>
+<pre>
+plain &lt;code&gt; without highlighting</pre>
+<pre><code>fn main() {}
+</code></pre>
+<p>Caf&eacute; &#x1F600; &copy; <b>bold <i>and italic</i></b> <a href="#usage">usage</a></p>
+<custom-element some-attr="1"><img src="assets/custom.png" alt="widget"></custom-element>
+</article></div>"##;
+
+/// Html the tree builder repairs, which scraper parses instead
+const MALFORMED: [&str; 9] = [
+  r#"<p align="center"><h1><img src="logo.png"></h1></p>"#,
+  r#"<div><p><img src="logo.png"></div>"#,
+  r#"<b><i><img src="logo.png"></b></i>"#,
+  r#"<table><tr><td>cell</td></tr>stray text</table>"#,
+  r#"<!DOCTYPE html><p><img src="logo.png"></p>"#,
+  r#"<p><img src="logo.png"><script>document.write("<img src=x>")</script></p>"#,
+  r#"<p><img src="logo.png"></br></p>"#,
+  r#"<ul><li>one<li>two</ul>"#,
+  r#"<pre><span><img src="logo.png"></span></pre>"#,
+];
+
+fn readme(document: Html) -> Readme {
+  let mut readme = Readme::from_document("widget", "widget", document, false, "main", None);
+  readme.description = Some("A fast and friendly widget toolkit for the terminal".to_string());
+  readme.topics = vec!["terminal".to_string()];
+  readme
+}
+
+/// The readme's images & title are the same in both documents
+async fn assert_same_images(html: &str) {
+  let tokenized = readme(parse_simple_readme(html).expect("a simple readme"));
+  let parsed = readme(Html::parse_document(html));
+
+  let images = tokenized.images_offline().await;
+  assert!(!images.is_empty());
+  assert_eq!(images, parsed.images_offline().await);
+  assert_eq!(tokenized.title(), parsed.title());
+}
+
+fn text(document: &Html) -> Vec<String> {
+  document.root_element().text().map(String::from).collect()
+}
+
+#[tokio::test]
+async fn github_readmes_have_the_same_images() {
+  assert_same_images(GITHUB).await;
+}
+
+#[tokio::test]
+async fn the_spans_of_highlighted_code_are_left_out() {
+  let tokenized = parse_simple_readme(GITHUB).unwrap();
+  let parsed = Html::parse_document(GITHUB);
+
+  let spans = |document: &Html| {
+    document
+      .select(&Selector::parse("pre span").unwrap())
+      .count()
+  };
+  assert_eq!(spans(&tokenized), 0);
+  assert!(spans(&parsed) > 0);
+  // each span's text is still a text node of its own
+  assert_eq!(text(&tokenized), text(&parsed));
+}
+
+#[tokio::test]
+async fn other_readmes_are_the_same_document() {
+  for html in [TABLES, STRUCTURE, EMBEDS] {
+    let tokenized = parse_simple_readme(html).expect("a simple readme");
+    let parsed = Html::parse_document(html);
+
+    assert_eq!(
+      tokenized.root_element().html(),
+      parsed.root_element().html()
+    );
+    assert_eq!(text(&tokenized), text(&parsed));
+    assert_same_images(html).await;
+  }
+}
+
+#[test]
+fn malformed_html_is_left_to_scraper() {
+  for html in MALFORMED {
+    assert!(parse_simple_readme(html).is_none(), "{}", html);
+  }
+}
+
+#[tokio::test]
+async fn readmes_are_the_same_either_way() {
+  for html in MALFORMED {
+    let readme = Readme::new("widget", "widget", html, false, "main", None);
+    let parsed = Readme::from_document(
+      "widget",
+      "widget",
+      Html::parse_document(html),
+      false,
+      "main",
+      None,
+    );
+    assert_eq!(readme.images_offline().await, parsed.images_offline().await);
+  }
+}
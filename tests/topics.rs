@@ -0,0 +1,37 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{KeywordMention, Readme, RepoIconsBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_topic_in_the_path_boosts_the_image() {
+  let html = r#"<div id="readme"><article><p><img src="https://cdn.example.org/diagram.png"></p><p><img src="https://cdn.example.org/next-js-mark.png"></p></article></div>"#;
+  let mut readme = Readme::new("topics-owner", "framework", html, false, "main", None);
+  readme.topics = vec!["nextjs".to_string()];
+
+  let images = readme.images().await;
+
+  assert_eq!(images[0].src.path(), "/next-js-mark.png");
+  assert!(images[0].keyword_mentions.contains(&KeywordMention::Topic));
+  assert!(images[0].weight() > images[1].weight());
+  assert!(images[1].keyword_mentions.is_empty());
+}
+
+#[tokio::test]
+async fn topics_are_loaded_from_the_repo_lowercased_without_separators() {
+  let mock = MockClient::new().repo_with(
+    "topics-owner",
+    "loaded",
+    json!({ "topics": ["Next-JS", "react"] }),
+    "",
+    "",
+  );
+  let options = RepoIconsBuilder::fast().http_client(mock);
+
+  let readme = Readme::load_with(&options, "topics-owner", "loaded")
+    .await
+    .unwrap();
+
+  assert_eq!(readme.topics, ["nextjs", "react"]);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{logo_readme, MockClient, PNG};
+use repo_icons::{get_user_agent, set_user_agent, RepoIconsBuilder};
+use reqwest::header::HeaderValue;
+
+#[tokio::test]
+async fn the_configured_user_agent_reaches_every_request() {
+  assert!(set_user_agent(" ").is_err());
+  set_user_agent("egress-approved/1.0").unwrap();
+  assert_eq!(get_user_agent().as_deref(), Some("egress-approved/1.0"));
+
+  let mock = MockClient::new().repo("agent-owner", "widget", &logo_readme(PNG));
+  RepoIconsBuilder::fast()
+    .http_client(mock.clone())
+    .load("agent-owner", "widget")
+    .await
+    .unwrap();
+
+  assert!(mock.request_count() > 0);
+  for nth in 0..mock.request_count() {
+    assert_eq!(
+      mock.request_header(nth, "user-agent").as_deref(),
+      Some("egress-approved/1.0"),
+      "{}",
+      mock.urls()[nth]
+    );
+  }
+
+  let readme = mock
+    .urls()
+    .iter()
+    .position(|url| url.ends_with("/readme"))
+    .unwrap();
+  assert!(mock
+    .request_header(readme, "accept")
+    .unwrap()
+    .contains("application/vnd.github.html"));
+}
+
+#[tokio::test]
+async fn the_builders_user_agent_overrides_the_global_one() {
+  set_user_agent("egress-approved/1.0").unwrap();
+
+  let mock = MockClient::new().repo("agent-owner", "gadget", &logo_readme(PNG));
+  RepoIconsBuilder::fast()
+    .user_agent(HeaderValue::from_static("builder-agent/1.0"))
+    .http_client(mock.clone())
+    .load("agent-owner", "gadget")
+    .await
+    .unwrap();
+
+  assert!(mock.request_count() > 0);
+  for nth in 0..mock.request_count() {
+    assert_eq!(
+      mock.request_header(nth, "user-agent").as_deref(),
+      Some("builder-agent/1.0"),
+      "{}",
+      mock.urls()[nth]
+    );
+  }
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::MockClient;
+use repo_icons::{Readme, RepoIconsBuilder};
+
+async fn is_live(mock: &MockClient, src: &str) -> bool {
+  let html = format!(r#"<div id="readme"><img src="{}" alt="logo"></div>"#, src);
+  let readme = Readme::new("live-owner", "widget", &html, false, "main", None);
+  let image = readme.images_offline().await.remove(0);
+
+  let options = RepoIconsBuilder::new().http_client(mock.clone());
+  image.is_live_with(&options).await
+}
+
+#[tokio::test]
+async fn head_requests_decide_liveness() {
+  let mock = MockClient::new().route("https://images.example.com/logo.png", 200, "");
+  assert!(is_live(&mock, "https://images.example.com/logo.png").await);
+  assert_eq!(mock.request_count(), 1);
+
+  assert!(!is_live(&mock, "https://images.example.com/missing.png").await);
+}
+
+#[tokio::test]
+async fn refused_head_requests_fall_back_to_a_ranged_get() {
+  for status in [403, 405, 501] {
+    let mock = MockClient::new()
+      .route("https://images.example.com/logo.png", status, "")
+      .method("HEAD")
+      .route("https://images.example.com/logo.png", 206, "")
+      .method("GET");
+    assert!(is_live(&mock, "https://images.example.com/logo.png").await);
+    assert_eq!(mock.request_count(), 2);
+    assert_eq!(
+      mock.request_header(1, "range").as_deref(),
+      Some("bytes=0-0")
+    );
+  }
+}
+
+#[tokio::test]
+async fn failed_ranged_gets_arent_live() {
+  let mock = MockClient::new().route("https://images.example.com/logo.png", 405, "");
+  assert!(!is_live(&mock, "https://images.example.com/logo.png").await);
+  assert_eq!(mock.request_count(), 2);
+}